@@ -1,3 +1,4 @@
+use gix::refs::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
 use tempfile::{Builder as TempBuilder, TempDir};
 
 pub struct TempRepository {
@@ -104,8 +105,11 @@ struct CommitSpec {
     committer_name: Option<String>,
     committer_email: Option<String>,
     seconds: Option<gix::date::SecondsSinceUnixEpoch>,
+    offset: i32,
     files: Vec<(String, Vec<u8>)>,
+    removed_files: Vec<String>,
     extra_parents: Vec<gix::ObjectId>,
+    gpgsig: Option<String>,
 }
 
 impl CommitSpec {
@@ -118,8 +122,11 @@ impl CommitSpec {
             committer_name: None,
             committer_email: None,
             seconds: None,
+            offset: 0,
             files: Vec::new(),
+            removed_files: Vec::new(),
             extra_parents: Vec::new(),
+            gpgsig: None,
         }
     }
 
@@ -134,7 +141,9 @@ impl CommitSpec {
         let seconds = self.seconds.unwrap_or(DEFAULT_TIME);
         let name = self.author_name.as_deref().unwrap_or(DEFAULT_NAME);
         let email = self.author_email.as_deref().unwrap_or(DEFAULT_EMAIL);
-        get_signature_at_time_as(seconds, name, email)
+        let mut signature = get_signature_at_time_as(seconds, name, email);
+        signature.time.offset = self.offset;
+        signature
     }
 
     fn committer_signature(&self) -> gix::actor::Signature {
@@ -149,12 +158,14 @@ impl CommitSpec {
             .as_deref()
             .or(self.author_email.as_deref())
             .unwrap_or(DEFAULT_EMAIL);
-        get_signature_at_time_as(seconds, name, email)
+        let mut signature = get_signature_at_time_as(seconds, name, email);
+        signature.time.offset = self.offset;
+        signature
     }
 
     /// Create the commit on the given repository, returning its ID
     fn execute<'r>(&self, repo: &'r gix::Repository) -> eyre::Result<gix::Id<'r>> {
-        let tree_id = if self.files.is_empty() {
+        let tree_id = if self.files.is_empty() && self.removed_files.is_empty() {
             repo.head_tree_id()
                 .unwrap_or_else(|_| repo.empty_tree().id())
         } else {
@@ -162,6 +173,9 @@ impl CommitSpec {
                 .head_tree_id()
                 .unwrap_or_else(|_| repo.empty_tree().id());
             let mut editor = repo.edit_tree(base_tree_id)?;
+            for path in &self.removed_files {
+                editor.remove(path)?;
+            }
             for (path, content) in &self.files {
                 let blob_id: gix::ObjectId = repo.write_blob(content)?.into();
                 editor.upsert(path, gix::object::tree::EntryKind::Blob, blob_id)?;
@@ -184,7 +198,35 @@ impl CommitSpec {
             parents.push(repo.find_commit(*oid)?.id());
         }
         let message = self.message();
-        let commit_id = repo.commit_as(committed, authored, "HEAD", &message, tree_id, parents)?;
+        let commit_id = match &self.gpgsig {
+            None => repo.commit_as(committed, authored, "HEAD", &message, tree_id, parents)?,
+            Some(signature) => {
+                let commit = gix::objs::Commit {
+                    tree: tree_id.into(),
+                    parents: parents.into_iter().map(|p| p.detach()).collect(),
+                    author: authored.into(),
+                    committer: committed.into(),
+                    encoding: None,
+                    message: message.into(),
+                    extra_headers: vec![("gpgsig".into(), signature.as_str().into())],
+                };
+                let commit_id = repo.write_object(&commit)?;
+                repo.edit_reference(RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            mode: RefLog::AndReference,
+                            force_create_reflog: false,
+                            message: "commit (signed)".into(),
+                        },
+                        expected: PreviousValue::Any,
+                        new: gix::refs::Target::Object(commit_id.detach()),
+                    },
+                    name: "HEAD".try_into()?,
+                    deref: true,
+                })?;
+                commit_id
+            }
+        };
         Ok(commit_id)
     }
 }
@@ -217,11 +259,30 @@ impl<'r> PendingCommit<'r> {
         self
     }
 
+    /// Set the timezone of the author and committer, in seconds east of UTC
+    pub fn timezone(mut self, offset: i32) -> Self {
+        self.spec.offset = offset;
+        self
+    }
+
     pub fn file(mut self, path: &str, content: &[u8]) -> Self {
         self.spec.files.push((path.to_owned(), content.to_vec()));
         self
     }
 
+    /// Remove a file from the tree. Combine with [file](Self::file) to rename it.
+    pub fn remove(mut self, path: &str) -> Self {
+        self.spec.removed_files.push(path.to_owned());
+        self
+    }
+
+    /// Write a `gpgsig` header with the given signature, like
+    /// [PGP_SIGNATURE](super::signatures::PGP_SIGNATURE). Nothing checks that it's valid.
+    pub fn gpgsig(mut self, signature: &str) -> Self {
+        self.spec.gpgsig = Some(signature.to_owned());
+        self
+    }
+
     /// Add an additional parent to this commit by branch name.
     ///
     /// Used together with [TempRepository::merge] to construct merges with more than two
@@ -246,7 +307,7 @@ impl<'r> PendingCommit<'r> {
 
 /// Deferred operations for Builder
 enum BuildOp {
-    Commit(CommitSpec),
+    Commit(Box<CommitSpec>),
     Branch { name: String },
     LightweightTag { name: String },
     AnnotatedTag { name: String, message: String },
@@ -366,14 +427,35 @@ impl CommitBuilder {
         self
     }
 
+    /// Set the timezone of the author and committer, in seconds east of UTC
+    pub fn timezone(mut self, offset: i32) -> Self {
+        self.spec.offset = offset;
+        self
+    }
+
     pub fn file(mut self, path: &str, content: &[u8]) -> Self {
         self.spec.files.push((path.to_owned(), content.to_vec()));
         self
     }
 
+    /// Remove a file from the tree. Combine with [file](Self::file) to rename it.
+    pub fn remove(mut self, path: &str) -> Self {
+        self.spec.removed_files.push(path.to_owned());
+        self
+    }
+
+    /// Write a `gpgsig` header with the given signature, like
+    /// [PGP_SIGNATURE](super::signatures::PGP_SIGNATURE). Nothing checks that it's valid.
+    pub fn gpgsig(mut self, signature: &str) -> Self {
+        self.spec.gpgsig = Some(signature.to_owned());
+        self
+    }
+
     /// Explicitly finalize the current commit and return the Builder
     pub fn finish(mut self) -> Builder {
-        self.builder.operations.push(BuildOp::Commit(self.spec));
+        self.builder
+            .operations
+            .push(BuildOp::Commit(Box::new(self.spec)));
         self.builder
     }
 
@@ -0,0 +1,21 @@
+//! Commit signatures in each format that Git supports, to pass to
+//! [gpgsig](super::repository::PendingCommit::gpgsig)
+//!
+//! Only the armor matters; the signatures themselves are garbage, and wouldn't verify.
+
+pub const PGP_SIGNATURE: &str = "-----BEGIN PGP SIGNATURE-----
+
+iHUEABYKAB0WIQRFakeFakeFakeFakeFakeFakeFakeFakeFakeBQJnAAAAAAoJEFak
+eFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFake==
+=Fake
+-----END PGP SIGNATURE-----";
+
+pub const SSH_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgFakeFakeFakeFakeFakeFakeFa
+keFakeFakeFakeFakeFakeAAAAA2dpdAAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQy
+-----END SSH SIGNATURE-----";
+
+pub const X509_SIGNATURE: &str = "-----BEGIN SIGNED MESSAGE-----
+MIAGCSqGSIb3DQEHAqCAMIACAQExDTALBglghkgBZQMEAgEwgAYJKoZIhvcNAQcBAACg
+ggFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFakeFa
+-----END SIGNED MESSAGE-----";
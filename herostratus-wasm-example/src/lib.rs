@@ -0,0 +1,97 @@
+//! An example Herostratus rule, compiled to WebAssembly
+//!
+//! Grants "Web Scale" to anyone who mentions WebAssembly in a commit subject, and counts the
+//! mentions in its cache to demonstrate the cache round-trip. Build it with
+//!
+//! ```sh
+//! cargo build --release --target wasm32-unknown-unknown -p herostratus-wasm-example
+//! ```
+//!
+//! and list `target/wasm32-unknown-unknown/release/herostratus_wasm_example.wasm` in the
+//! `rules.wasm` array in `config.toml`. See docs/user/external-rules.md for the ABI.
+
+use std::cell::Cell;
+
+use serde_json::{Value, json};
+
+const META: &str = r#"{
+    "id": 1001,
+    "human_id": "web-scale",
+    "name": "Web Scale",
+    "description": "Mention WebAssembly in a commit subject",
+    "kind": "per-user",
+    "consumes": ["subject"]
+}"#;
+
+thread_local! {
+    static MENTIONS: Cell<u64> = const { Cell::new(0) };
+}
+
+fn handle(request: &Value) -> Value {
+    match request["type"].as_str() {
+        Some("init_cache") => {
+            let mentions = request["cache"]["mentions"].as_u64().unwrap_or(0);
+            MENTIONS.set(mentions);
+            json!({})
+        }
+        Some("observation") => {
+            let subject = request["observation"]["subject"]
+                .as_str()
+                .unwrap_or_default()
+                .to_lowercase();
+            if subject.contains("wasm") || subject.contains("webassembly") {
+                MENTIONS.set(MENTIONS.get() + 1);
+                json!({"grants": [{}]})
+            } else {
+                json!({})
+            }
+        }
+        Some("fini_cache") => json!({"cache": {"mentions": MENTIONS.get()}}),
+        _ => json!({}),
+    }
+}
+
+/// Hand ownership of `bytes` to the host, packed as `(ptr << 32) | len`
+///
+/// The host frees it with [herostratus_dealloc].
+fn to_host(bytes: Vec<u8>) -> u64 {
+    let len = bytes.len();
+    let ptr = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+    ((ptr as usize as u64) << 32) | len as u64
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn herostratus_version() -> u32 {
+    1
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn herostratus_meta() -> u64 {
+    to_host(META.as_bytes().to_vec())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn herostratus_alloc(len: u32) -> *mut u8 {
+    let buf = vec![0u8; len as usize].into_boxed_slice();
+    Box::into_raw(buf) as *mut u8
+}
+
+/// # Safety
+///
+/// `ptr` and `len` must come from [herostratus_alloc], or from a buffer handed to the host.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn herostratus_dealloc(ptr: *mut u8, len: u32) {
+    let slice = std::ptr::slice_from_raw_parts_mut(ptr, len as usize);
+    drop(unsafe { Box::from_raw(slice) });
+}
+
+/// # Safety
+///
+/// `ptr` and `len` must describe a buffer from [herostratus_alloc].
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn herostratus_call(ptr: *const u8, len: u32) -> u64 {
+    let request = unsafe { std::slice::from_raw_parts(ptr, len as usize) };
+    let request: Value = serde_json::from_slice(request).unwrap_or(Value::Null);
+    let response = handle(&request);
+    to_host(serde_json::to_vec(&response).unwrap_or_else(|_| b"{}".to_vec()))
+}
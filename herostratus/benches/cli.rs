@@ -32,7 +32,29 @@ fn check_self<P: AsRef<Path>>(repo: P, reference: &str) -> gungraun::Command {
         .build()
 }
 
+// Only diff-based rules, so that most of the work is in the observers, which run ahead of the rules.
+// The inline bench observes each commit on the calling thread instead, to compare against.
+#[binary_benchmark]
+#[bench::v0_2_0(WORKSPACE_ROOT, "v0.2.0", &["whitespace-only", "empty-commit", "added-first-ci"], None)]
+#[bench::v0_2_0_inline(WORKSPACE_ROOT, "v0.2.0", &["whitespace-only", "empty-commit", "added-first-ci"], Some("0"))]
+fn check_self_diff_rules<P: AsRef<Path>>(
+    repo: P,
+    reference: &str,
+    rules: &[&str],
+    lookahead: Option<&str>,
+) -> gungraun::Command {
+    let mut cmd = gungraun::Command::new(env!("CARGO_BIN_EXE_herostratus"));
+    cmd.arg("check").arg(repo.as_ref()).arg(reference);
+    for rule in rules {
+        cmd.arg("--rule").arg(rule);
+    }
+    if let Some(lookahead) = lookahead {
+        cmd.env("HEROSTRATUS_LOOKAHEAD", lookahead);
+    }
+    cmd.build()
+}
+
 // See: https://gungraun.github.io/gungraun/latest/html/index.html
 binary_benchmark_group!(name = add; benchmarks = add_url);
-binary_benchmark_group!(name = check; benchmarks = check_self);
+binary_benchmark_group!(name = check; benchmarks = check_self, check_self_diff_rules);
 main!(binary_benchmark_groups = add, check);
@@ -60,7 +60,7 @@ fn main() {
             name: name.clone(),
             path: path.clone(),
         };
-        if name.ends_with(".html") {
+        if name.ends_with(".html") || name.ends_with(".xml") {
             templates.push(asset);
         } else {
             static_assets.push(asset);
@@ -6,7 +6,7 @@ use super::grant::Grant;
 use super::meta::{AchievementKind, Meta};
 
 /// Whether an achievement was granted or revoked.
-#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, clap::ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum EventKind {
     Grant,
@@ -64,6 +64,14 @@ pub struct AchievementLog {
 }
 
 impl AchievementLog {
+    /// The path to the events log for the given repository.
+    pub fn path_for(data_dir: &Path, repo_name: &str) -> PathBuf {
+        data_dir
+            .join("export")
+            .join("events")
+            .join(format!("{repo_name}.csv"))
+    }
+
     /// Load an achievement log from a CSV file, or create an empty log.
     ///
     /// If `path` is `None` or the file does not exist, returns an empty log.
@@ -96,6 +104,12 @@ impl AchievementLog {
         })
     }
 
+    /// Create an in-memory log from events that were already loaded, e.g. from an export
+    /// directory. [Self::save] is a no-op.
+    pub fn from_events(events: Vec<AchievementEvent>) -> Self {
+        Self { path: None, events }
+    }
+
     /// Write the full log to CSV. No-op if path is `None`.
     #[tracing::instrument(target = "perf", skip_all)]
     pub fn save(&self) -> eyre::Result<()> {
@@ -197,6 +211,11 @@ impl AchievementLog {
         holder
     }
 
+    /// Every event in the log, oldest first.
+    pub fn events(&self) -> &[AchievementEvent] {
+        &self.events
+    }
+
     /// Keep only events for which the predicate returns `true`. Used for pruning stale grants
     /// when a rule's cache is invalidated.
     pub fn retain(&mut self, predicate: impl FnMut(&AchievementEvent) -> bool) {
@@ -205,13 +224,19 @@ impl AchievementLog {
 
     /// All grant events that have no subsequent revocation for the same achievement+user.
     pub fn active_grants(&self) -> impl Iterator<Item = &AchievementEvent> {
+        self.active_grant_indices().map(|i| &self.events[i])
+    }
+
+    /// The indices into [AchievementLog::events] of the [AchievementLog::active_grants].
+    pub fn active_grant_indices(&self) -> impl Iterator<Item = usize> {
         // Collect active grants by scanning all events
-        let mut active: Vec<&AchievementEvent> = Vec::new();
-        for event in &self.events {
+        let mut active: Vec<usize> = Vec::new();
+        for (index, event) in self.events.iter().enumerate() {
             match event.event {
-                EventKind::Grant => active.push(event),
+                EventKind::Grant => active.push(index),
                 EventKind::Revoke => {
-                    active.retain(|g| {
+                    active.retain(|&g| {
+                        let g = &self.events[g];
                         !(g.achievement_id == event.achievement_id
                             && g.user_email == event.user_email)
                     });
@@ -326,6 +351,7 @@ mod tests {
             name: "Test",
             description: "test achievement",
             kind,
+            points: Meta::DEFAULT_POINTS,
         }
     }
 
@@ -565,6 +591,8 @@ mod tests {
                 .iter()
                 .any(|e| e.achievement_id == "a" && e.user_email == "carol@example.com")
         );
+        let indices: Vec<_> = log.active_grant_indices().collect();
+        assert_eq!(indices, [1, 2]);
     }
 
     #[test]
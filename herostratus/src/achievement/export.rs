@@ -1,6 +1,7 @@
 use std::path::Path;
 
-use crate::achievement::meta::AchievementKind;
+use crate::achievement::meta::{AchievementKind, Meta, id_matches};
+use crate::config::RulesConfig;
 use crate::rules::RulePlugin;
 
 /// A row in the achievement catalog CSV.
@@ -11,6 +12,31 @@ pub struct AchievementRow {
     pub name: String,
     pub description: String,
     pub kind: String,
+    /// Catalogs written before points existed don't have this column
+    #[serde(default = "default_points")]
+    pub points: u32,
+}
+
+fn default_points() -> u32 {
+    Meta::DEFAULT_POINTS
+}
+
+impl AchievementRow {
+    fn new(meta: &Meta, config: &RulesConfig) -> Self {
+        Self {
+            id: meta.id,
+            human_id: meta.human_id.to_string(),
+            name: meta.name.to_string(),
+            description: meta.description.to_string(),
+            kind: kind_label(&meta.kind),
+            points: config.points_for(meta),
+        }
+    }
+
+    /// Check if a user-provided string matches this achievement. See [Meta::id_matches].
+    pub fn id_matches(&self, id: &str) -> bool {
+        id_matches(self.id, &self.human_id, id)
+    }
 }
 
 fn kind_label(kind: &AchievementKind) -> String {
@@ -22,11 +48,42 @@ fn kind_label(kind: &AchievementKind) -> String {
     }
 }
 
+/// The inverse of the labels in the `kind` column of the catalog CSV
+pub fn parse_kind_label(label: &str) -> Option<AchievementKind> {
+    match label {
+        "per-user" => Some(AchievementKind::PerUser { recurrent: false }),
+        "per-user-repeat" => Some(AchievementKind::PerUser { recurrent: true }),
+        "global" => Some(AchievementKind::Global { revocable: false }),
+        "global-revocable" => Some(AchievementKind::Global { revocable: true }),
+        _ => None,
+    }
+}
+
+/// Build the achievement catalog for the given rules and the meta-achievements, sorted by ID.
+pub fn achievement_rows(
+    rules: &[Box<dyn RulePlugin>],
+    config: &RulesConfig,
+) -> Vec<AchievementRow> {
+    let mut rows: Vec<_> = rules
+        .iter()
+        .map(|rule| AchievementRow::new(rule.meta(), config))
+        .collect();
+    for meta in super::meta_achievements::meta_achievement_metas() {
+        rows.push(AchievementRow::new(&meta, config));
+    }
+    rows.sort_by_key(|r| r.id);
+    rows
+}
+
 /// Write the achievement catalog CSV to `{data_dir}/export/achievements.csv`.
 ///
 /// This is rewritten on every run from the compiled-in [Meta](super::Meta) data. It changes only
 /// when Herostratus is updated with new rules or when the enabled rule set changes.
-pub fn write_achievements_csv(data_dir: &Path, rules: &[Box<dyn RulePlugin>]) -> eyre::Result<()> {
+pub fn write_achievements_csv(
+    data_dir: &Path,
+    rules: &[Box<dyn RulePlugin>],
+    config: &RulesConfig,
+) -> eyre::Result<()> {
     let path = data_dir.join("export").join("achievements.csv");
     if let Some(parent) = path.parent()
         && !parent.exists()
@@ -34,32 +91,7 @@ pub fn write_achievements_csv(data_dir: &Path, rules: &[Box<dyn RulePlugin>]) ->
         std::fs::create_dir_all(parent)?;
     }
 
-    let mut rows: Vec<_> = rules
-        .iter()
-        .map(|rule| {
-            let meta = rule.meta();
-            AchievementRow {
-                id: meta.id,
-                human_id: meta.human_id.to_string(),
-                name: meta.name.to_string(),
-                description: meta.description.to_string(),
-                kind: kind_label(&meta.kind),
-            }
-        })
-        .collect();
-
-    for meta in super::meta_achievements::meta_achievement_metas() {
-        rows.push(AchievementRow {
-            id: meta.id,
-            human_id: meta.human_id.to_string(),
-            name: meta.name.to_string(),
-            description: meta.description.to_string(),
-            kind: kind_label(&meta.kind),
-        });
-    }
-
-    rows.sort_by_key(|r| r.id);
-
+    let rows = achievement_rows(rules, config);
     let mut writer = csv::Writer::from_path(&path)?;
     for row in &rows {
         writer.serialize(row)?;
@@ -136,6 +168,33 @@ pub fn upsert_repository_csv(
     Ok(())
 }
 
+/// Remove a repository row from `{data_dir}/export/repositories.csv`.
+///
+/// Returns whether a row was removed. Missing files are not an error.
+pub fn remove_repository_csv(data_dir: &Path, name: &str) -> eyre::Result<bool> {
+    let path = data_dir.join("export").join("repositories.csv");
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let mut reader = csv::Reader::from_path(&path)?;
+    let mut rows: Vec<RepositoryRow> = reader.deserialize().collect::<Result<_, _>>()?;
+    let before = rows.len();
+    rows.retain(|r| r.name != name);
+    if rows.len() == before {
+        return Ok(false);
+    }
+
+    let mut writer = csv::Writer::from_path(&path)?;
+    for row in &rows {
+        writer.serialize(row)?;
+    }
+    writer.flush()?;
+
+    tracing::debug!("Removed repository {name:?} from {path:?}");
+    Ok(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,7 +208,7 @@ mod tests {
         let num_rules = rules.len();
         let num_meta = super::super::meta_achievements::meta_achievement_metas().len();
 
-        write_achievements_csv(dir.path(), &rules).unwrap();
+        write_achievements_csv(dir.path(), &rules, &RulesConfig::default()).unwrap();
 
         let path = dir.path().join("export/achievements.csv");
         assert!(path.exists());
@@ -249,6 +308,24 @@ mod tests {
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn removes_repository_row() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!remove_repository_csv(dir.path(), "repo-a").unwrap());
+
+        upsert_repository_csv(dir.path(), "repo-a", "file:///a", None, "HEAD", 1).unwrap();
+        upsert_repository_csv(dir.path(), "repo-b", "file:///b", None, "HEAD", 2).unwrap();
+
+        assert!(remove_repository_csv(dir.path(), "repo-a").unwrap());
+        assert!(!remove_repository_csv(dir.path(), "repo-a").unwrap());
+
+        let path = dir.path().join("export/repositories.csv");
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let rows: Vec<RepositoryRow> = reader.deserialize().map(|r| r.unwrap()).collect();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "repo-b");
+    }
+
     #[test]
     fn repository_csv_empty_commit_url_prefix() {
         let dir = tempfile::tempdir().unwrap();
@@ -281,5 +358,15 @@ mod tests {
             kind_label(&AchievementKind::Global { revocable: true }),
             "global-revocable"
         );
+
+        for recurrent in [false, true] {
+            let kind = AchievementKind::PerUser { recurrent };
+            assert_eq!(parse_kind_label(&kind_label(&kind)), Some(kind));
+        }
+        for revocable in [false, true] {
+            let kind = AchievementKind::Global { revocable };
+            assert_eq!(parse_kind_label(&kind_label(&kind)), Some(kind));
+        }
+        assert_eq!(parse_kind_label("per-repo"), None);
     }
 }
@@ -1,5 +1,7 @@
 use chrono::{DateTime, Utc};
 
+use crate::observer::CommitContext;
+
 /// What a rule returns to indicate "grant this achievement to this person."
 ///
 /// Contains only what the engine needs to record the grant. The achievement identity comes from the
@@ -36,4 +38,22 @@ impl Grant {
         self.description_override = Some(description);
         self
     }
+
+    /// Copies of this grant for each of the commit's co-authors.
+    ///
+    /// Only a grant to the author of the commit described by `ctx` is shared; grants to anybody
+    /// else, or for other commits, return nothing.
+    pub fn shared_with_co_authors(&self, ctx: &CommitContext) -> Vec<Grant> {
+        if self.commit != ctx.oid || self.user_email != ctx.author_email {
+            return Vec::new();
+        }
+        ctx.co_authors
+            .iter()
+            .map(|co_author| Grant {
+                user_name: co_author.name.clone(),
+                user_email: co_author.email.clone(),
+                ..self.clone()
+            })
+            .collect()
+    }
 }
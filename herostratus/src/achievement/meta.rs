@@ -19,9 +19,16 @@ pub struct Meta {
 
     /// Variation semantics -- how the engine enforces this achievement.
     pub kind: AchievementKind,
+
+    /// Points for each grant on the leaderboards, before bonuses. May be overridden by
+    /// `rules.points` in the config. See [scoring](super::scoring).
+    pub points: u32,
 }
 
 impl Meta {
+    /// Points for achievements that aren't particularly hard to earn
+    pub const DEFAULT_POINTS: u32 = 10;
+
     /// Construct a [`Grant`] from a [`CommitContext`].
     pub fn grant(&self, ctx: &CommitContext) -> Grant {
         Grant {
@@ -37,13 +44,21 @@ impl Meta {
     /// Check if a user-provided string matches this achievement.
     /// Accepts: "1", "H1", "fixup", "H1-fixup".
     pub fn id_matches(&self, id: &str) -> bool {
-        id == self.id.to_string()
-            || id == format!("H{}", self.id)
-            || id == self.human_id
-            || id == format!("H{}-{}", self.id, self.human_id)
+        id_matches(self.id, self.human_id, id)
     }
 }
 
+/// Check if a user-provided string matches the achievement with the given numeric and human IDs.
+///
+/// Shared by [Meta::id_matches] and the exported [AchievementRow](super::AchievementRow)s, which
+/// don't have a [Meta].
+pub fn id_matches(numeric_id: usize, human_id: &str, id: &str) -> bool {
+    id == numeric_id.to_string()
+        || id == format!("H{numeric_id}")
+        || id == human_id
+        || id == format!("H{numeric_id}-{human_id}")
+}
+
 /// How the engine enforces an achievement's variation semantics.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum AchievementKind {
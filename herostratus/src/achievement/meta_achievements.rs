@@ -17,6 +17,7 @@ const ACHIEVEMENT_FARMER: Meta = Meta {
     name: "Achievement Farmer",
     description: "Farm the most achievements in the repository",
     kind: AchievementKind::Global { revocable: true },
+    points: 10,
 };
 
 /// Return the [Meta] for each meta-achievement.
@@ -115,6 +116,7 @@ mod tests {
             name: "test",
             description: "test",
             kind: AchievementKind::PerUser { recurrent: true },
+            points: Meta::DEFAULT_POINTS,
         };
         let grant = Grant {
             commit: gix::ObjectId::null(gix::hash::Kind::Sha1),
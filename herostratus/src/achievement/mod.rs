@@ -7,15 +7,19 @@ mod meta;
 mod meta_achievements;
 mod pipeline;
 mod pipeline_checkpoint;
+pub mod scoring;
 
 pub use achievement_log::{
-    AchievementEvent as AchievementLogEvent, EventKind as AchievementEventKind,
+    AchievementEvent as AchievementLogEvent, AchievementLog, EventKind as AchievementEventKind,
+};
+pub use export::{
+    AchievementRow, RepositoryRow, achievement_rows, parse_kind_label, remove_repository_csv,
+    upsert_repository_csv, write_achievements_csv,
 };
-pub use export::{AchievementRow, RepositoryRow, upsert_repository_csv};
 pub use grant::Grant;
 pub use meta::{AchievementKind, Meta};
 pub use meta_achievements::meta_achievement_metas;
-pub use pipeline::{GrantStats, grant};
+pub use pipeline::{GrantFilter, GrantStats, grant};
 
 #[derive(Debug)]
 pub struct Achievement {
@@ -32,6 +36,8 @@ pub struct Achievement {
     pub user_name: String,
     /// The mailmap-resolved user email
     pub user_email: String,
+    /// When the grant (or revocation) took effect. See [Grant.timestamp](Grant::timestamp).
+    pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
 /// An achievement event emitted by the pipeline.
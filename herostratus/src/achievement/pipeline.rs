@@ -1,5 +1,7 @@
-use std::collections::HashSet;
+use std::collections::{HashSet, VecDeque};
+use std::mem::Discriminant;
 use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
 
 use eyre::WrapErr;
@@ -8,9 +10,12 @@ use super::achievement_log::AchievementLog;
 use super::pipeline_checkpoint::{CheckpointAction, Continuation, PipelineCheckpoint};
 use crate::achievement::{Achievement, AchievementEvent};
 use crate::cache::{CheckpointCache, RuleCache};
-use crate::config::Config;
+use crate::config::{Config, RulesConfig};
+use crate::git::filter::CommitFilter;
 use crate::git::mailmap::MailmapResolver;
-use crate::observer::{ObserverData, ObserverEngine, builtin_observers};
+use crate::observer::{
+    EngineCommand, Observation, ObserverData, ObserverEngine, builtin_observers,
+};
 use crate::rules::{RuleEngine, RuleOutput, RulePlugin};
 
 /// Delete per-rule caches and prune stale grants from the events log for rules whose version
@@ -77,6 +82,56 @@ fn apply_invalidation(
     Ok(())
 }
 
+/// Restrict the rules and commits that [grant] processes
+///
+/// Intended for the stateless `check` command, since filtering would leave gaps in the checkpoint
+/// and achievement log of a tracked repository.
+#[derive(Debug, Default, Clone)]
+pub struct GrantFilter {
+    /// Only run the rules matching these IDs. Overrides `rules.exclude` and `rules.include`.
+    pub rules: Vec<String>,
+    /// Don't run the rules matching these IDs. Applied after [GrantFilter::rules].
+    pub exclude_rules: Vec<String>,
+    pub commits: CommitFilter,
+}
+
+impl GrantFilter {
+    /// Build the rules selected by the filter.
+    ///
+    /// Every ID must match at least one rule, so that typos aren't silently ignored.
+    pub(crate) fn build_rules(
+        &self,
+        config: &RulesConfig,
+    ) -> eyre::Result<Vec<Box<dyn RulePlugin>>> {
+        let rules = if self.rules.is_empty() {
+            crate::rules::all_rules(config)
+        } else {
+            let config = RulesConfig {
+                exclude: None,
+                include: None,
+                ..config.clone()
+            };
+            crate::rules::all_rules(&config)
+        };
+
+        for id in self.rules.iter().chain(&self.exclude_rules) {
+            if !rules.iter().any(|r| r.meta().id_matches(id)) {
+                eyre::bail!("Rule {id:?} not found. Use --list-rules to see the available rules");
+            }
+        }
+
+        Ok(rules
+            .into_iter()
+            .filter(|r| {
+                let meta = r.meta();
+                (self.rules.is_empty() || self.rules.iter().any(|id| meta.id_matches(id)))
+                    && !self.exclude_rules.iter().any(|id| meta.id_matches(id))
+            })
+            .collect())
+    }
+}
+
+#[derive(Default)]
 pub struct GrantStats {
     pub num_commits_processed: u64,
     pub num_achievements_generated: u64,
@@ -93,20 +148,17 @@ pub fn grant(
     data_dir: Option<&Path>,
     name: &str,
     repo_config: Option<&crate::config::RepositoryConfig>,
+    filter: &GrantFilter,
     on_event: impl FnMut(AchievementEvent),
 ) -> eyre::Result<GrantStats> {
-    let default_rc = crate::config::RulesConfig::default();
-    let rules_config = config.and_then(|c| c.rules.as_ref()).unwrap_or(&default_rc);
-    let rules = crate::rules::builtin_rules(rules_config);
-
-    let global_mailmap = config.and_then(|c| c.mailmap_file.as_deref());
-    let repo_mailmap = config
-        .and_then(|c| c.repositories.get(name))
-        .and_then(|rc| rc.mailmap_file.as_deref());
-
-    let snapshot = repo.open_mailmap();
-    let mailmap = MailmapResolver::new(snapshot, global_mailmap, repo_mailmap)?;
+    if let Some(rc) = repo_config
+        && rc.tracks_several_references()
+    {
+        return grant_branches(config, rc, repo, depth, data_dir, name, filter, on_event);
+    }
 
+    let (rules, mailmap) = pipeline_inputs(config, repo, name, filter)?;
+    let co_authors = credits_co_authors(config);
     run_grant(
         reference,
         repo,
@@ -115,11 +167,166 @@ pub fn grant(
         name,
         rules,
         mailmap,
+        co_authors,
         repo_config,
+        &filter.commits,
         on_event,
     )
 }
 
+/// Build the rules and mailmap for a single [Pipeline]
+fn pipeline_inputs(
+    config: Option<&Config>,
+    repo: &gix::Repository,
+    name: &str,
+    filter: &GrantFilter,
+) -> eyre::Result<(Vec<Box<dyn RulePlugin>>, MailmapResolver)> {
+    let default_rc = RulesConfig::default();
+    let rules_config = config.and_then(|c| c.rules.as_ref()).unwrap_or(&default_rc);
+    let rules = filter.build_rules(rules_config)?;
+
+    let global_mailmap = config.and_then(|c| c.mailmap_file.as_deref());
+    let repo_mailmap = config
+        .and_then(|c| c.repositories.get(name))
+        .and_then(|rc| rc.mailmap_file.as_deref());
+
+    let snapshot = repo.open_mailmap();
+    let mailmap = MailmapResolver::new(snapshot, global_mailmap, repo_mailmap)?;
+    Ok((rules, mailmap))
+}
+
+fn credits_co_authors(config: Option<&Config>) -> bool {
+    config
+        .and_then(|c| c.rules.as_ref())
+        .is_some_and(RulesConfig::credits_co_authors)
+}
+
+/// Run the pipeline on each branch tracked by a repository, one after the other
+///
+/// Each branch has its own checkpoint, but they all share the repository's rule caches and events
+/// log. The walk for each branch hides the commits already processed for the other branches, so
+/// that commits reachable from several branches are only processed once.
+#[allow(clippy::too_many_arguments)]
+fn grant_branches(
+    config: Option<&Config>,
+    repo_config: &crate::config::RepositoryConfig,
+    repo: &mut gix::Repository,
+    depth: Option<usize>,
+    data_dir: Option<&Path>,
+    name: &str,
+    filter: &GrantFilter,
+    mut on_event: impl FnMut(AchievementEvent),
+) -> eyre::Result<GrantStats> {
+    if repo.is_shallow() {
+        eyre::bail!(
+            "{name:?} tracks several branches, but is a shallow clone; fetch it to get its full history"
+        );
+    }
+    let branches = crate::git::rev::resolve_references(&repo_config.tracked_references(), repo)?;
+    tracing::info!(
+        "Checking branches {:?} of {name:?}",
+        branches.iter().map(|(b, _)| b).collect::<Vec<_>>()
+    );
+    // The first tracked branch is never a glob, so it's always resolved first
+    let checkpoint_for = |index: usize| match data_dir {
+        Some(dir) => CheckpointCache::for_branch(dir, name, &branches[index].0, index == 0),
+        None => Ok(CheckpointCache::in_memory()),
+    };
+
+    let mut stats = GrantStats::default();
+    let co_authors = credits_co_authors(config);
+    let mut processed = Vec::new();
+    // The branches whose walk --depth cut short in this run, so their tips can't be hidden
+    let mut truncated = Vec::new();
+    for (index, (branch, tip)) in branches.iter().enumerate() {
+        let (rules, mailmap) = pipeline_inputs(config, repo, name, filter)?;
+        let current: Vec<(usize, u32)> = rules.iter().map(|r| (r.meta().id, r.version())).collect();
+
+        // Skip the commits processed for the branches before this one, and the commits that the
+        // other branches processed with the current rules in previous runs
+        let mut hidden = processed.clone();
+        for other in (0..branches.len()).filter(|i| *i != index && !truncated.contains(i)) {
+            let checkpoint = checkpoint_for(other)?;
+            if let Some(commit) = checkpoint.data.commit
+                && checkpoint.data.has_processed_all(&current)
+            {
+                hidden.push(commit);
+            }
+        }
+
+        // Only the first branch invalidates the changed rules, since they share caches and a log
+        let pipeline = Pipeline::with_checkpoint(
+            repo,
+            rules,
+            mailmap,
+            data_dir,
+            name,
+            checkpoint_for(index)?,
+            index == 0,
+        )?
+        .with_co_authors(co_authors);
+        let mut oids = walk_filtered(*tip, hidden, repo, &filter.commits)?.peekable();
+        if oids.peek().is_none() {
+            tracing::info!("Every commit on {branch:?} was already processed");
+            let mut checkpoint = checkpoint_for(index)?;
+            checkpoint.data.commit = Some(*tip);
+            checkpoint.data.rules = current;
+            checkpoint.save()?;
+            processed.push(*tip);
+            continue;
+        }
+
+        tracing::info!("Checking branch {branch:?} of {name:?} ...");
+        let branch_stats = match depth {
+            Some(depth) => {
+                let mut walked = 0;
+                let taken = oids.by_ref().take(depth).inspect(|_| walked += 1);
+                let branch_stats = pipeline.run(taken, &mut on_event)?;
+                // Only hide this branch from the next ones if --depth didn't cut its walk short,
+                // or the commits past the cutoff would never be processed for any branch
+                if walked < depth || oids.peek().is_none() {
+                    processed.push(*tip);
+                } else {
+                    truncated.push(index);
+                }
+                branch_stats
+            }
+            None => {
+                processed.push(*tip);
+                pipeline.run(oids, &mut on_event)?
+            }
+        };
+        stats.num_commits_processed += branch_stats.num_commits_processed;
+        stats.num_achievements_generated += branch_stats.num_achievements;
+        stats.elapsed += branch_stats.elapsed;
+    }
+    Ok(stats)
+}
+
+/// Walk backwards from `rev`, skipping the commits reachable from `hidden`, and those that don't
+/// match the `commit_filter`
+fn walk_filtered<'r>(
+    rev: gix::ObjectId,
+    hidden: Vec<gix::ObjectId>,
+    repo: &'r gix::Repository,
+    commit_filter: &'r CommitFilter,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<gix::ObjectId>> + 'r> {
+    let oids = crate::git::rev::walk_hidden(rev, hidden, repo)
+        .wrap_err(format!("Failed to rev-walk rev: {rev:?}"))?;
+    // Wrap Ok values and skip errors (existing behavior for stateless mode)
+    Ok(oids.filter_map(|o| match o {
+        Ok(o) => match commit_filter.matches(o, repo) {
+            Ok(true) => Some(Ok(o)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        },
+        Err(e) => {
+            tracing::error!("Skipping OID: {e:?}");
+            None
+        }
+    }))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_grant(
     reference: &str,
@@ -129,19 +336,17 @@ fn run_grant(
     name: &str,
     rules: Vec<Box<dyn RulePlugin>>,
     mailmap: MailmapResolver,
+    co_authors: bool,
     repo_config: Option<&crate::config::RepositoryConfig>,
+    commit_filter: &CommitFilter,
     on_event: impl FnMut(AchievementEvent),
 ) -> eyre::Result<GrantStats> {
-    let rev = crate::git::rev::parse(reference, repo)
+    let (rev, hidden) = crate::git::rev::parse_range(reference, repo)
         .wrap_err(format!("Failed to rev-parse: {reference:?}"))?;
 
-    if let Some(data_dir) = data_dir {
-        super::export::write_achievements_csv(data_dir, &rules)?;
-    }
-
     // Build the pipeline first (briefly borrows &repo, but ObserverEngine clones into owned
     // storage, so the borrow does not persist after construction).
-    let pipeline = Pipeline::new(repo, rules, mailmap, data_dir, name)?;
+    let pipeline = Pipeline::new(repo, rules, mailmap, data_dir, name)?.with_co_authors(co_authors);
 
     // Choose the iteration strategy based on whether we have a repo config and the repo is
     // shallow. DeepeningRevWalk transparently fetches more history as needed; for non-shallow
@@ -149,6 +354,9 @@ fn run_grant(
     if let Some(rc) = repo_config
         && repo.is_shallow()
     {
+        if !hidden.is_empty() || !commit_filter.is_empty() {
+            eyre::bail!("Revision ranges and commit filters are not supported on shallow clones");
+        }
         let batch_size = crate::git::clone::DEFAULT_SHALLOW_DEPTH;
         let oids = crate::git::deepen::DeepeningRevWalk::new(rev, repo, rc.clone(), batch_size)?;
         if let Some(depth) = depth {
@@ -159,16 +367,7 @@ fn run_grant(
             Ok(map_stats(stats))
         }
     } else {
-        let oids = crate::git::rev::walk(rev, repo)
-            .wrap_err(format!("Failed to rev-walk rev: {rev:?}"))?;
-        // Wrap Ok values and skip errors (existing behavior for stateless mode)
-        let oids = oids.filter_map(|o| match o {
-            Ok(o) => Some(Ok(o)),
-            Err(e) => {
-                tracing::error!("Skipping OID: {e:?}");
-                None
-            }
-        });
+        let oids = walk_filtered(rev, hidden, repo, commit_filter)?;
         if let Some(depth) = depth {
             let stats = pipeline.run(oids.take(depth), on_event)?;
             Ok(map_stats(stats))
@@ -187,9 +386,77 @@ fn map_stats(stats: PipelineStats) -> GrantStats {
     }
 }
 
+/// How many commits the [ObserverEngine] may run ahead of the [RuleEngine].
+///
+/// Enough to keep the observer thread busy while rules process a commit that's slow to evaluate,
+/// without buffering an unbounded amount of history in memory.
+const LOOKAHEAD: usize = 16;
+
+/// Overrides [LOOKAHEAD], for benchmarking. 0 observes each commit on the calling thread, right
+/// before its rules are evaluated, as Herostratus did before the [ObserverEngine] got its own
+/// thread.
+const LOOKAHEAD_ENV: &str = "HEROSTRATUS_LOOKAHEAD";
+
+fn lookahead_from_env() -> usize {
+    std::env::var(LOOKAHEAD_ENV)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(LOOKAHEAD)
+}
+
 /// Drives the [ObserverEngine] and [RuleEngine] together, streaming [Achievement]s via a callback.
+///
+/// The [ObserverEngine] runs on its own thread, observing up to [LOOKAHEAD] commits ahead of the
+/// [RuleStage], which runs on the calling thread.
 struct Pipeline {
     observer_engine: ObserverEngine,
+    rules: RuleStage,
+    /// How many commits the [ObserverEngine] may run ahead, or 0 to run it inline
+    lookahead: usize,
+}
+
+/// How the [RuleStage] hands commits to the [ObserverEngine], and gets their [ObserverData] back
+enum ObserverStage<'a> {
+    /// The engine runs on its own thread
+    Thread {
+        commands: &'a mpsc::SyncSender<EngineCommand>,
+        data: &'a mpsc::Receiver<Vec<ObserverData>>,
+    },
+    /// The engine observes each commit on the calling thread as soon as it's sent
+    Inline {
+        engine: &'a mut ObserverEngine,
+        observed: VecDeque<Vec<ObserverData>>,
+    },
+}
+
+impl ObserverStage<'_> {
+    fn send(&mut self, command: EngineCommand) -> eyre::Result<()> {
+        match self {
+            Self::Thread { commands, .. } => commands
+                .send(command)
+                .map_err(|_| eyre::eyre!("The observer engine stopped unexpectedly")),
+            Self::Inline { engine, observed } => {
+                match command {
+                    EngineCommand::Process(oid) => observed.push_back(engine.process_commit(oid)?),
+                    EngineCommand::RetireAllExcept(needed) => engine.retire_all_except(&needed),
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn recv(&mut self) -> eyre::Result<Vec<ObserverData>> {
+        match self {
+            Self::Thread { data, .. } => data.recv().ok(),
+            Self::Inline { observed, .. } => observed.pop_front(),
+        }
+        .ok_or_else(|| eyre::eyre!("The observer engine stopped unexpectedly"))
+    }
+}
+
+/// Everything downstream of the [ObserverEngine]: rule evaluation, the checkpoint, and the
+/// achievement log
+struct RuleStage {
     rule_engine: RuleEngine,
     achievement_log: AchievementLog,
     checkpoint: PipelineCheckpoint,
@@ -199,11 +466,6 @@ struct Pipeline {
     all_enabled_rules: Vec<(usize, u32)>,
 }
 
-struct CommitResult {
-    num_achievements: u64,
-    early_exit: bool,
-}
-
 /// Statistics from a completed pipeline run.
 struct PipelineStats {
     num_commits_processed: u64,
@@ -224,6 +486,27 @@ impl Pipeline {
         mailmap: MailmapResolver,
         data_dir: Option<&Path>,
         repo_name: &str,
+    ) -> eyre::Result<Self> {
+        let checkpoint = if let Some(dir) = data_dir {
+            CheckpointCache::from_data_dir(dir, repo_name)?
+        } else {
+            CheckpointCache::in_memory()
+        };
+        Self::with_checkpoint(repo, rules, mailmap, data_dir, repo_name, checkpoint, true)
+    }
+
+    /// Build a pipeline that resumes from the given checkpoint, rather than the repository's
+    ///
+    /// If `invalidate`, the caches and grants of rules whose version changed since the checkpoint
+    /// are discarded before the walk.
+    pub fn with_checkpoint(
+        repo: &gix::Repository,
+        rules: Vec<Box<dyn RulePlugin>>,
+        mailmap: MailmapResolver,
+        data_dir: Option<&Path>,
+        repo_name: &str,
+        checkpoint: CheckpointCache,
+        invalidate: bool,
     ) -> eyre::Result<Self> {
         let needed: HashSet<_> = rules.iter().flat_map(|r| r.consumes()).copied().collect();
         let observers: Vec<_> = builtin_observers()
@@ -234,20 +517,13 @@ impl Pipeline {
         let observer_engine = ObserverEngine::new(repo, observers, mailmap)?;
         let rule_engine = RuleEngine::new(rules);
 
-        let log_path = data_dir.map(|d| {
-            d.join("export")
-                .join("events")
-                .join(format!("{repo_name}.csv"))
-        });
+        let log_path = data_dir.map(|d| AchievementLog::path_for(d, repo_name));
 
-        let checkpoint = if let Some(dir) = data_dir {
-            CheckpointCache::from_data_dir(dir, repo_name)?
-        } else {
-            CheckpointCache::in_memory()
-        };
         let checkpoint = PipelineCheckpoint::new(checkpoint);
 
-        if let Some(dir) = data_dir {
+        if let Some(dir) = data_dir
+            && invalidate
+        {
             let current = rule_engine.active_rules_with_versions();
             let invalidated_ids = checkpoint.classify_invalidated(&current);
             let invalidated: Vec<(usize, &str)> = rule_engine
@@ -263,66 +539,102 @@ impl Pipeline {
 
         Ok(Self {
             observer_engine,
-            rule_engine,
-            achievement_log,
-            checkpoint,
-            data_dir: data_dir.map(Path::to_path_buf),
-            repo_name: repo_name.to_string(),
-            all_enabled_rules,
+            lookahead: lookahead_from_env(),
+            rules: RuleStage {
+                rule_engine,
+                achievement_log,
+                checkpoint,
+                data_dir: data_dir.map(Path::to_path_buf),
+                repo_name: repo_name.to_string(),
+                all_enabled_rules,
+            },
         })
     }
 
+    /// Also grant to the co-authors of each commit, for the rules that opt in
+    pub fn with_co_authors(mut self, enabled: bool) -> Self {
+        self.rules.rule_engine = self.rules.rule_engine.with_co_authors(enabled);
+        self
+    }
+
     /// Process all commits and stream achievements to the callback.
     ///
     /// Consumes the pipeline since it is a one-shot operation.
     #[tracing::instrument(target = "perf", skip_all)]
     pub fn run(
-        mut self,
+        self,
         oids: impl IntoIterator<Item = eyre::Result<gix::ObjectId>>,
         mut on_event: impl FnMut(AchievementEvent),
     ) -> eyre::Result<PipelineStats> {
         let start = Instant::now();
-        let mut num_commits: u64 = 0;
-        let mut num_achievements: u64 = 0;
-
-        if let Some(data_dir) = &self.data_dir {
-            let repo_name = &self.repo_name;
-            self.rule_engine.init_caches(|human_id| {
+        let Self {
+            mut observer_engine,
+            mut rules,
+            lookahead,
+        } = self;
+
+        if let Some(data_dir) = &rules.data_dir {
+            let repo_name = &rules.repo_name;
+            rules.rule_engine.init_caches(|human_id| {
                 let cache = RuleCache::from_rule_name(data_dir, repo_name, human_id)?;
                 Ok(cache.data)
             })?;
         }
 
-        for oid in oids {
-            let oid = oid?;
-            let result = self.on_commit(oid, &mut on_event)?;
-            num_achievements += result.num_achievements;
-            if result.early_exit {
-                break;
-            }
-            num_commits += 1;
-        }
+        let (walked, observed) = if lookahead == 0 {
+            let mut observers = ObserverStage::Inline {
+                engine: &mut observer_engine,
+                observed: VecDeque::new(),
+            };
+            (rules.walk(oids, &mut observers, 1, &mut on_event), Ok(()))
+        } else {
+            // One extra command slot for the RetireAllExcept sent when reaching the checkpoint
+            let (commands, command_rx) = mpsc::sync_channel(lookahead + 1);
+            let (data_tx, data) = mpsc::sync_channel(lookahead);
+            std::thread::scope(|s| {
+                let observer = std::thread::Builder::new()
+                    .name("observer-engine".into())
+                    .spawn_scoped(s, move || observer_engine.run(command_rx, &data_tx))
+                    .expect("failed to spawn the observer engine thread");
+                let mut observers = ObserverStage::Thread {
+                    commands: &commands,
+                    data: &data,
+                };
+                let walked = rules.walk(oids, &mut observers, lookahead, &mut on_event);
+                // Let the observer thread exit, even if we stopped early
+                drop(commands);
+                drop(data);
+                let observed = observer
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+                (walked, observed)
+            })
+        };
+        // An observer error closes the channels, so it's the root cause of any walk error
+        observed?;
+        let (num_commits, mut num_achievements) = walked?;
 
         tracing::debug!("Finalizing rules ...");
-        let outputs = self.rule_engine.finalize();
-        num_achievements += self.emit(outputs, &mut on_event);
+        let outputs = rules.rule_engine.finalize();
+        num_achievements += rules.emit(outputs, &mut on_event);
 
         tracing::debug!("Evaluating meta-achievements ...");
-        let meta_outputs = super::meta_achievements::evaluate(&self.achievement_log);
-        num_achievements += self.emit(meta_outputs, &mut on_event);
+        let meta_outputs = super::meta_achievements::evaluate(&rules.achievement_log);
+        num_achievements += rules.emit(meta_outputs, &mut on_event);
 
-        self.checkpoint
-            .save_checkpoint(self.all_enabled_rules.clone())?;
+        rules
+            .checkpoint
+            .save_checkpoint(rules.all_enabled_rules.clone())?;
 
-        if let Some(data_dir) = &self.data_dir {
-            let repo_name = &self.repo_name;
-            self.rule_engine.fini_caches(|human_id, data| {
+        if let Some(data_dir) = &rules.data_dir {
+            let repo_name = &rules.repo_name;
+            rules.rule_engine.fini_caches(|human_id, data| {
                 let cache = RuleCache::new_for_rule(data_dir, repo_name, human_id, data);
                 cache.save()
             })?;
         }
 
-        self.achievement_log.save()?;
+        rules.achievement_log.save()?;
 
         let elapsed = start.elapsed();
         tracing::info!(
@@ -335,62 +647,103 @@ impl Pipeline {
             elapsed,
         })
     }
+}
 
-    /// Process a single commit: checkpoint decision, observer dispatch, and rule evaluation.
+impl RuleStage {
+    /// Send each commit to the observers and evaluate the rules on the observations they send
+    /// back, keeping at most `lookahead` commits in flight.
     ///
-    /// Returns whether the loop should exit early and how many achievements were emitted.
-    #[tracing::instrument(target = "perf", name = "Pipeline::on_commit", skip_all)]
-    fn on_commit(
+    /// Returns the number of commits processed and achievements emitted.
+    fn walk(
         &mut self,
-        oid: gix::ObjectId,
+        oids: impl IntoIterator<Item = eyre::Result<gix::ObjectId>>,
+        observers: &mut ObserverStage,
+        lookahead: usize,
         on_event: &mut impl FnMut(AchievementEvent),
-    ) -> eyre::Result<CommitResult> {
-        let mut num_achievements = 0;
+    ) -> eyre::Result<(u64, u64)> {
+        let mut in_flight = 0usize;
+        let mut num_commits: u64 = 0;
+        let mut num_achievements: u64 = 0;
 
-        match self.checkpoint.on_commit(oid) {
-            Continuation::Process => {}
-            Continuation::ReachedCheckpoint => {
-                match self
-                    .checkpoint
-                    .resolve(&self.rule_engine.active_rules_with_versions())
-                {
-                    CheckpointAction::EarlyExit => {
-                        return Ok(CommitResult {
-                            num_achievements: 0,
-                            early_exit: true,
-                        });
+        for oid in oids {
+            let oid = oid?;
+
+            if let Continuation::ReachedCheckpoint = self.checkpoint.on_commit(oid) {
+                // Deciding which rules to retire needs the rules to have seen every commit before
+                // the checkpoint, and the observers retired by that decision must not observe the
+                // checkpoint commit. So wait for the observer thread to catch up.
+                for _ in 0..std::mem::take(&mut in_flight) {
+                    num_achievements += self.on_commit_data(observers.recv()?, on_event);
+                }
+                match self.on_checkpoint(on_event)? {
+                    (n, None) => {
+                        num_achievements += n;
+                        break;
                     }
-                    CheckpointAction::Retire { rule_ids } => {
-                        let changed: Vec<&str> = self
-                            .rule_engine
-                            .iter_rules()
-                            .filter(|r| !rule_ids.contains(&r.meta().id))
-                            .map(|r| r.meta().human_id)
-                            .collect();
-                        tracing::info!("Rules {changed:?} were changed since the last checkpoint");
-
-                        let data_dir = &self.data_dir;
-                        let repo_name = &self.repo_name;
-                        let outputs = self.rule_engine.retire(&rule_ids, |human_id, data| {
-                            save_rule_cache(data_dir, repo_name, human_id, data)
-                        })?;
-                        num_achievements += self.emit(outputs, on_event);
-                        self.observer_engine
-                            .retire_all_except(&self.rule_engine.consumed());
+                    (n, Some(needed)) => {
+                        num_achievements += n;
+                        observers.send(EngineCommand::RetireAllExcept(needed))?;
                     }
                 }
             }
+
+            if in_flight == lookahead {
+                num_achievements += self.on_commit_data(observers.recv()?, on_event);
+                in_flight -= 1;
+            }
+            observers.send(EngineCommand::Process(oid))?;
+            in_flight += 1;
+            num_commits += 1;
         }
 
-        // TODO: This runs the git diff directly in this call graph. For performance's sake, we
-        // may want to run the ObserverEngine on a separate thread, or possibly run the diffs
-        // on a dedicated thread within the ObserverEngine. There might be pipelining benefits
-        // to keep the diff computation unblocked by the rule processing. But this would be
-        // really tricky to implement when we consider the checkpoint, which requires retiring
-        // Observers and Rules when we hit a checkpoint. That doesn't preclude parallelism,
-        // but it does make it trickier.
-        let data = self.observer_engine.process_commit(oid)?;
-        let _guard = tracing::info_span!(target: "perf", "RuleEngine::on_commit").entered();
+        for _ in 0..in_flight {
+            num_achievements += self.on_commit_data(observers.recv()?, on_event);
+        }
+        Ok((num_commits, num_achievements))
+    }
+
+    /// Decide what to do on reaching the checkpoint commit, retiring rules if needed.
+    ///
+    /// Returns the number of achievements emitted by retired rules, and the observations still
+    /// needed by the remaining rules, or `None` to exit early.
+    fn on_checkpoint(
+        &mut self,
+        on_event: &mut impl FnMut(AchievementEvent),
+    ) -> eyre::Result<(u64, Option<HashSet<Discriminant<Observation>>>)> {
+        let rule_ids = match self
+            .checkpoint
+            .resolve(&self.rule_engine.active_rules_with_versions())
+        {
+            CheckpointAction::EarlyExit => return Ok((0, None)),
+            CheckpointAction::Retire { rule_ids } => rule_ids,
+        };
+
+        let changed: Vec<&str> = self
+            .rule_engine
+            .iter_rules()
+            .filter(|r| !rule_ids.contains(&r.meta().id))
+            .map(|r| r.meta().human_id)
+            .collect();
+        tracing::info!("Rules {changed:?} were changed since the last checkpoint");
+
+        let data_dir = &self.data_dir;
+        let repo_name = &self.repo_name;
+        let outputs = self.rule_engine.retire(&rule_ids, |human_id, data| {
+            save_rule_cache(data_dir, repo_name, human_id, data)
+        })?;
+        let num_achievements = self.emit(outputs, on_event);
+        Ok((num_achievements, Some(self.rule_engine.consumed())))
+    }
+
+    /// Dispatch one commit's [ObserverData] to the rules, returning how many achievements were
+    /// emitted.
+    #[tracing::instrument(target = "perf", name = "RuleEngine::on_commit", skip_all)]
+    fn on_commit_data(
+        &mut self,
+        data: Vec<ObserverData>,
+        on_event: &mut impl FnMut(AchievementEvent),
+    ) -> u64 {
+        let mut num_achievements = 0;
         for msg in data {
             match msg {
                 ObserverData::CommitStart(ctx) => {
@@ -405,11 +758,7 @@ impl Pipeline {
                 }
             }
         }
-
-        Ok(CommitResult {
-            num_achievements,
-            early_exit: false,
-        })
+        num_achievements
     }
 
     /// Resolve rule outputs through the achievement log and emit events via the callback.
@@ -434,6 +783,7 @@ impl Pipeline {
                         commit: revoke.commit,
                         user_name: revoke.user_name.clone(),
                         user_email: revoke.user_email.clone(),
+                        timestamp: revoke.timestamp,
                     };
                     tracing::info!(
                         "revoked achievement: {:?} from {}",
@@ -452,6 +802,7 @@ impl Pipeline {
                     commit: resolution.grant.commit,
                     user_name: resolution.grant.user_name,
                     user_email: resolution.grant.user_email,
+                    timestamp: resolution.grant.timestamp,
                 };
                 tracing::info!(
                     "granted achievement: {:?} to {:?} for commit {}",
@@ -480,6 +831,8 @@ fn save_rule_cache(
     Ok(())
 }
 
+// The channels only close early when the observer thread stops on an error, which Pipeline::run
+// reports in place of these.
 #[cfg(test)]
 mod tests {
     use herostratus_tests::fixtures::repository;
@@ -1025,6 +1378,16 @@ mod tests {
         data_dir: Option<&Path>,
         repo_name: &str,
         rule_ids: &[usize],
+    ) -> (PipelineStats, Vec<AchievementEvent>) {
+        run_pipeline_with_lookahead(repo, data_dir, repo_name, rule_ids, LOOKAHEAD)
+    }
+
+    fn run_pipeline_with_lookahead(
+        repo: &gix::Repository,
+        data_dir: Option<&Path>,
+        repo_name: &str,
+        rule_ids: &[usize],
+        lookahead: usize,
     ) -> (PipelineStats, Vec<AchievementEvent>) {
         let head = crate::git::rev::parse("HEAD", repo).unwrap();
         let oids: Vec<_> = crate::git::rev::walk(head, repo)
@@ -1035,7 +1398,9 @@ mod tests {
             .into_iter()
             .filter(|r| rule_ids.contains(&r.meta().id))
             .collect();
-        let pipeline = Pipeline::new(repo, rules, default_mailmap(), data_dir, repo_name).unwrap();
+        let mut pipeline =
+            Pipeline::new(repo, rules, default_mailmap(), data_dir, repo_name).unwrap();
+        pipeline.lookahead = lookahead;
         let mut events = Vec::new();
         let stats = pipeline
             .run(oids.into_iter().map(Ok), |e| events.push(e))
@@ -1082,6 +1447,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inline_observers_match_threaded() {
+        let temp_repo = repository::Builder::new()
+            .commit("fixup! something")
+            .author("Alice", "alice@example.com")
+            .commit("Hi")
+            .commit("fixup! something else")
+            .author("Bob", "bob@example.com")
+            .build()
+            .unwrap();
+
+        let mut results = Vec::new();
+        for lookahead in [0, LOOKAHEAD] {
+            let data_dir = tempfile::tempdir().unwrap();
+            let dir = Some(data_dir.path());
+            // Run 2 retires H001 at the checkpoint, and sends the observers a RetireAllExcept
+            let (_, events1) =
+                run_pipeline_with_lookahead(&temp_repo.repo, dir, "test-repo", &[1], lookahead);
+            let (stats2, events2) =
+                run_pipeline_with_lookahead(&temp_repo.repo, dir, "test-repo", &[1, 2], lookahead);
+            let summary: Vec<_> = grants(&events1)
+                .into_iter()
+                .chain(grants(&events2))
+                .map(|a| (a.descriptor_id, a.commit, a.user_email.clone()))
+                .collect();
+            results.push((stats2.num_commits_processed, summary));
+        }
+        assert_eq!(results[0], results[1]);
+        assert_eq!(results[0].1.len(), 3, "{:?}", results[0]);
+    }
+
+    #[test]
+    fn checkpoint_retire_with_commits_in_flight() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let temp_repo = repository::Builder::new()
+            .commit("fixup! old")
+            .author("Alice", "alice@example.com")
+            .commit("Hi")
+            .build()
+            .unwrap();
+
+        // Run 1: only H001
+        run_pipeline_with_rules(&temp_repo.repo, Some(data_dir.path()), "test-repo", &[1]);
+
+        // Enough new commits that the observer thread is still working ahead of the rules when
+        // the checkpoint is reached
+        temp_repo
+            .commit("fixup! new")
+            .author("Bob", "bob@example.com")
+            .create()
+            .unwrap();
+        for i in 0..2 * LOOKAHEAD {
+            temp_repo
+                .commit(&format!("Commit number {i}"))
+                .create()
+                .unwrap();
+        }
+
+        // Run 2: H001 + H002. H001 sees only the new commits, H002 sees all of them.
+        let (stats, events) =
+            run_pipeline_with_rules(&temp_repo.repo, Some(data_dir.path()), "test-repo", &[1, 2]);
+        assert_eq!(stats.num_commits_processed as usize, 2 * LOOKAHEAD + 3);
+        let grants = grants(&events);
+        let fixups: Vec<_> = grants
+            .iter()
+            .filter(|a| a.descriptor_id == 1)
+            .map(|a| a.user_email.as_str())
+            .collect();
+        assert_eq!(fixups, ["bob@example.com"]);
+        assert!(
+            grants.iter().any(|a| a.descriptor_id == 2),
+            "expected H002 for the old \"Hi\" commit: {events:?}"
+        );
+    }
+
     #[test]
     fn checkpoint_converges_after_retire() {
         let data_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,243 @@
+//! Leaderboard scoring, computed from the achievement events logs
+//!
+//! Each grant is worth the achievement's base [points](super::Meta::points), multiplied for the
+//! rarer kinds of achievements:
+//!
+//! | Kind                       | Multiplier | Why                                           |
+//! | -------------------------- | ---------- | --------------------------------------------- |
+//! | Per-user                   | 1x         | Anyone can earn it                            |
+//! | Per-user, repeat           | 1x         | Plus a milestone bonus, see below             |
+//! | Global, revocable          | 2x         | Only one holder at a time, lost when revoked  |
+//! | Global                     | 3x         | Only one holder, ever                         |
+//!
+//! Every [MILESTONE]th grant of the same repeatable achievement to the same user in a repository
+//! earns the base points again as a bonus. Revoked grants are worth nothing.
+use std::collections::{BTreeMap, HashMap};
+
+use super::export::parse_kind_label;
+use super::{AchievementEventKind, AchievementKind, AchievementLogEvent, AchievementRow, Meta};
+
+/// Repeatable achievements earn a bonus on every grant that's a multiple of this
+pub const MILESTONE: usize = 5;
+
+/// A user's standing on a leaderboard
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ScoreEntry {
+    pub user_name: String,
+    pub user_email: String,
+    pub points: u64,
+    /// The number of grants that count towards `points`
+    pub achievements: usize,
+}
+
+/// Leaderboards, each sorted from the highest to lowest score
+#[derive(Debug, Default)]
+pub struct Leaderboards {
+    pub overall: Vec<ScoreEntry>,
+    /// Keyed by repository name
+    pub by_repository: BTreeMap<String, Vec<ScoreEntry>>,
+    /// Keyed by achievement human ID
+    pub by_achievement: BTreeMap<String, Vec<ScoreEntry>>,
+}
+
+fn kind_multiplier(kind: AchievementKind) -> u64 {
+    match kind {
+        AchievementKind::PerUser { .. } => 1,
+        AchievementKind::Global { revocable: true } => 2,
+        AchievementKind::Global { revocable: false } => 3,
+    }
+}
+
+/// A grant that hasn't been revoked, and what it's worth
+struct ScoredGrant<'a> {
+    event: &'a AchievementLogEvent,
+    points: u64,
+}
+
+/// Score the grants in one repository's events log
+fn score_repository<'a>(
+    catalog: &HashMap<&str, &AchievementRow>,
+    events: &'a [AchievementLogEvent],
+) -> Vec<ScoredGrant<'a>> {
+    let mut active: Vec<ScoredGrant<'a>> = Vec::new();
+    let mut repeats: HashMap<(&str, &str), usize> = HashMap::new();
+
+    for event in events {
+        match event.event {
+            AchievementEventKind::Grant => {
+                let row = catalog.get(event.achievement_id.as_str());
+                // Achievements from rules that have since been removed are still worth something
+                let base = u64::from(row.map_or(Meta::DEFAULT_POINTS, |r| r.points));
+                let kind = row
+                    .and_then(|r| parse_kind_label(&r.kind))
+                    .unwrap_or(AchievementKind::PerUser { recurrent: false });
+
+                let mut points = base * kind_multiplier(kind);
+                if kind == (AchievementKind::PerUser { recurrent: true }) {
+                    let count = repeats
+                        .entry((&event.achievement_id, &event.user_email))
+                        .or_default();
+                    *count += 1;
+                    if (*count).is_multiple_of(MILESTONE) {
+                        points += base;
+                    }
+                }
+                active.push(ScoredGrant { event, points });
+            }
+            AchievementEventKind::Revoke => active.retain(|g| {
+                !(g.event.achievement_id == event.achievement_id
+                    && g.event.user_email == event.user_email)
+            }),
+        }
+    }
+    active
+}
+
+/// Sum up the grants per user, highest score first
+fn leaderboard<'a>(grants: impl IntoIterator<Item = &'a ScoredGrant<'a>>) -> Vec<ScoreEntry> {
+    let mut by_user: BTreeMap<&str, ScoreEntry> = BTreeMap::new();
+    for grant in grants {
+        let entry = by_user
+            .entry(&grant.event.user_email)
+            .or_insert_with(|| ScoreEntry {
+                user_name: grant.event.user_name.clone(),
+                user_email: grant.event.user_email.clone(),
+                points: 0,
+                achievements: 0,
+            });
+        entry.points += grant.points;
+        entry.achievements += 1;
+    }
+    let mut entries: Vec<_> = by_user.into_values().collect();
+    entries.sort_by(|a, b| {
+        b.points
+            .cmp(&a.points)
+            .then_with(|| a.user_name.to_lowercase().cmp(&b.user_name.to_lowercase()))
+    });
+    entries
+}
+
+/// Compute the leaderboards from the achievement catalog and each repository's events log.
+pub fn score(
+    catalog: &[AchievementRow],
+    events: &BTreeMap<String, Vec<AchievementLogEvent>>,
+) -> Leaderboards {
+    let catalog: HashMap<&str, &AchievementRow> =
+        catalog.iter().map(|r| (r.human_id.as_str(), r)).collect();
+
+    let scored: BTreeMap<&str, Vec<ScoredGrant<'_>>> = events
+        .iter()
+        .map(|(repo, events)| (repo.as_str(), score_repository(&catalog, events)))
+        .collect();
+
+    let mut by_achievement: BTreeMap<String, Vec<&ScoredGrant<'_>>> = BTreeMap::new();
+    for grant in scored.values().flatten() {
+        by_achievement
+            .entry(grant.event.achievement_id.clone())
+            .or_default()
+            .push(grant);
+    }
+
+    Leaderboards {
+        overall: leaderboard(scored.values().flatten()),
+        by_repository: scored
+            .iter()
+            .map(|(repo, grants)| (repo.to_string(), leaderboard(grants)))
+            .collect(),
+        by_achievement: by_achievement
+            .into_iter()
+            .map(|(id, grants)| (id, leaderboard(grants)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+
+    fn row(human_id: &str, kind: &str, points: u32) -> AchievementRow {
+        AchievementRow {
+            id: 0,
+            human_id: human_id.to_string(),
+            name: human_id.to_string(),
+            description: String::new(),
+            kind: kind.to_string(),
+            points,
+        }
+    }
+
+    fn event(kind: AchievementEventKind, achievement_id: &str, user: &str) -> AchievementLogEvent {
+        AchievementLogEvent {
+            timestamp: Utc::now(),
+            event: kind,
+            achievement_id: achievement_id.to_string(),
+            commit: gix::ObjectId::null(gix::hash::Kind::Sha1),
+            user_name: user.to_string(),
+            user_email: format!("{}@example.com", user.to_lowercase()),
+            name_override: None,
+            description_override: None,
+        }
+    }
+
+    fn grant(achievement_id: &str, user: &str) -> AchievementLogEvent {
+        event(AchievementEventKind::Grant, achievement_id, user)
+    }
+
+    fn points(board: &[ScoreEntry]) -> Vec<(&str, u64)> {
+        board
+            .iter()
+            .map(|e| (e.user_name.as_str(), e.points))
+            .collect()
+    }
+
+    #[test]
+    fn kinds_and_milestones() {
+        let catalog = [
+            row("once", "per-user", 10),
+            row("repeat", "per-user-repeat", 10),
+            row("first", "global", 20),
+        ];
+        let mut events = vec![grant("once", "Alice"), grant("first", "Bob")];
+        events.extend(std::iter::repeat_with(|| grant("repeat", "Alice")).take(MILESTONE));
+        let events = BTreeMap::from([("repo".to_string(), events)]);
+
+        let boards = score(&catalog, &events);
+        // 10 + 5 * 10 + a 10 point milestone bonus
+        assert_eq!(points(&boards.overall), [("Alice", 70), ("Bob", 60)]);
+        assert_eq!(boards.overall[0].achievements, 1 + MILESTONE);
+        assert_eq!(points(&boards.by_achievement["first"]), [("Bob", 60)]);
+    }
+
+    #[test]
+    fn revoked_grants_are_worth_nothing() {
+        let catalog = [row("best", "global-revocable", 10)];
+        let events = BTreeMap::from([
+            (
+                "repo-a".to_string(),
+                vec![
+                    grant("best", "Alice"),
+                    event(AchievementEventKind::Revoke, "best", "Alice"),
+                    grant("best", "Bob"),
+                ],
+            ),
+            ("repo-b".to_string(), vec![grant("best", "Alice")]),
+        ]);
+
+        let boards = score(&catalog, &events);
+        assert_eq!(points(&boards.overall), [("Alice", 20), ("Bob", 20)]);
+        assert_eq!(points(&boards.by_repository["repo-a"]), [("Bob", 20)]);
+        assert_eq!(points(&boards.by_repository["repo-b"]), [("Alice", 20)]);
+    }
+
+    #[test]
+    fn unknown_achievements_use_default_points() {
+        let events = BTreeMap::from([("repo".to_string(), vec![grant("removed", "Alice")])]);
+        let boards = score(&[], &events);
+        assert_eq!(
+            points(&boards.overall),
+            [("Alice", u64::from(Meta::DEFAULT_POINTS))]
+        );
+    }
+}
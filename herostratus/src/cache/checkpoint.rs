@@ -53,7 +53,9 @@ impl Checkpoint {
 
 /// A checkpoint for a specific repository / branch pair
 ///
-/// Saved to `<data dir>/cache/<name>/checkpoint.json`
+/// Saved to `<data dir>/cache/<name>/checkpoint.json`. Repositories that track several branches
+/// save the checkpoints for every branch but the first to
+/// `<data dir>/cache/<name>/checkpoints/<branch>.json`.
 pub type CheckpointCache = JsonFileCache<Checkpoint>;
 
 impl CheckpointCache {
@@ -66,6 +68,28 @@ impl CheckpointCache {
             .join("checkpoint.json");
         Self::load(cache_path)
     }
+
+    /// Load the checkpoint cache for one of the branches tracked by the given repository
+    ///
+    /// The first tracked branch uses the same checkpoint as [from_data_dir](Self::from_data_dir),
+    /// so that adding branches to a repository doesn't re-process its history.
+    pub fn for_branch<P: AsRef<std::path::Path>>(
+        data_dir: P,
+        name: &str,
+        branch: &str,
+        first: bool,
+    ) -> eyre::Result<Self> {
+        if first {
+            return Self::from_data_dir(data_dir, name);
+        }
+        let cache_path = data_dir
+            .as_ref()
+            .join("cache")
+            .join(name)
+            .join("checkpoints")
+            .join(format!("{branch}.json"));
+        Self::load(cache_path)
+    }
 }
 
 fn serialize_object_id<S>(
@@ -1,5 +1,9 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
+
 #[derive(Debug, clap::Parser)]
 #[clap(about, verbatim_doc_comment, version)]
 pub struct Args {
@@ -39,6 +43,10 @@ pub struct Args {
     #[clap(long)]
     pub list_rules: bool,
 
+    /// List all available integrations and exit
+    #[clap(long)]
+    pub list_integrations: bool,
+
     #[command(subcommand)]
     pub command: Option<Command>,
 }
@@ -47,10 +55,25 @@ pub struct Args {
 pub enum Command {
     Check(CheckArgs),
     Add(AddArgs),
+    Remove(RemoveArgs),
     CheckOne(CheckOneArgs),
     CheckAll(CheckAllArgs),
     FetchAll(FetchAllArgs),
     Render(RenderArgs),
+    Explain(ExplainArgs),
+    Query(QueryArgs),
+}
+
+/// How achievement events are written to stdout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable, one event per line
+    #[default]
+    Text,
+    /// One JSON object per line, followed by a final stats record per repository
+    ///
+    /// Intended for piping into other tools. See docs/user/output-formats.md for the schema.
+    Jsonl,
 }
 
 /// Statelessly process the given file path and reference
@@ -63,7 +86,8 @@ pub struct CheckArgs {
 
     /// The Git reference or revision to process
     ///
-    /// All commits reachable from this reference will be processed
+    /// All commits reachable from this reference will be processed. May also be a revision range
+    /// like `main..feature` to only process the commits reachable from `feature` but not `main`.
     #[clap(default_value = "HEAD")]
     pub reference: String,
 
@@ -74,8 +98,62 @@ pub struct CheckArgs {
     /// Print a summary of achievements to stdout
     #[clap(short, long)]
     pub summary: bool,
-    // TODO: Rule filtering
-    // TODO: Commit filtering
+
+    /// Only run this rule
+    ///
+    /// May be the rule ID, human ID, or pretty ID. May be given multiple times. Overrides
+    /// `rules.exclude` and `rules.include` from the config file.
+    #[clap(long = "rule")]
+    pub rules: Vec<String>,
+
+    /// Don't run this rule
+    ///
+    /// May be given multiple times. Applied after '--rule'.
+    #[clap(long = "exclude-rule")]
+    pub exclude_rules: Vec<String>,
+
+    /// Only process commits committed on or after this date
+    ///
+    /// Either an RFC 3339 timestamp, or a YYYY-MM-DD date (midnight UTC).
+    #[clap(long, value_parser = parse_date)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only process commits committed before this date
+    ///
+    /// Either an RFC 3339 timestamp, or a YYYY-MM-DD date (midnight UTC).
+    #[clap(long, value_parser = parse_date)]
+    pub until: Option<DateTime<Utc>>,
+
+    /// Only process commits whose "Author Name <email>" contains this, ignoring case
+    #[clap(long)]
+    pub author: Option<String>,
+
+    /// The format used to print achievement events to stdout
+    #[clap(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// The integrations to run, in addition to printing events to stdout
+    ///
+    /// May be given multiple times. Overrides `integrations.enabled` from the config file. Use
+    /// `--list-integrations` to see the available integrations.
+    #[clap(long = "integration")]
+    pub integrations: Vec<String>,
+
+    /// Only process commits that change these paths
+    ///
+    /// Paths are relative to the repository root, match everything beneath them, and may contain
+    /// glob patterns. Commits are compared against their first parent.
+    #[clap(last = true)]
+    pub pathspecs: Vec<String>,
+}
+
+fn parse_date(s: &str) -> Result<DateTime<Utc>, String> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(s) {
+        return Ok(time.to_utc());
+    }
+    chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+        .map(|date| date.and_time(chrono::NaiveTime::MIN).and_utc())
+        .map_err(|_| format!("{s:?} is neither an RFC 3339 timestamp nor a YYYY-MM-DD date"))
 }
 
 /// Add a repository to be processed later
@@ -103,6 +181,13 @@ pub struct AddArgs {
     /// default HEAD.
     pub branch: Option<String>,
 
+    /// Additional branches to check, like `release/*`
+    ///
+    /// May be given multiple times. Globs are expanded against the remote's branches every fetch.
+    /// Commits reachable from several branches are only checked once.
+    #[clap(long = "also-branch")]
+    pub also_branches: Vec<String>,
+
     /// The path to clone the repository
     ///
     /// Given a URL like `https://github.com/Notgnoshi/herostratus.git`, the repository will be
@@ -166,6 +251,33 @@ pub struct AddArgs {
     pub commit_url_prefix: Option<String>,
 }
 
+/// Stop tracking a repository added with `herostratus add`
+///
+/// By default only the config file entry is removed. Use '--purge' to also delete the clone and
+/// everything generated for the repository.
+#[derive(Debug, clap::Args)]
+pub struct RemoveArgs {
+    /// The repository name from the config file, or its clone URL
+    ///
+    /// A URL must match exactly one configured repository; use the name to disambiguate between
+    /// multiple branches of the same URL.
+    pub repository: String,
+
+    /// Also delete the clone, the checkpoint and rule caches, the achievement events log, and the
+    /// repository's row in repositories.csv
+    ///
+    /// Clones outside the application data directory, or shared with another configured
+    /// repository, are left alone.
+    #[clap(long)]
+    pub purge: bool,
+
+    /// With '--purge', keep the achievement events log and the repositories.csv row
+    ///
+    /// Use this to retire a repository while still showing its achievements on the static site.
+    #[clap(long, requires = "purge")]
+    pub keep_events: bool,
+}
+
 /// Process rules on all cloned repositories
 #[derive(Debug, clap::Args)]
 pub struct CheckAllArgs {
@@ -177,9 +289,28 @@ pub struct CheckAllArgs {
     #[clap(short, long)]
     pub depth: Option<usize>,
 
-    /// Print a summary of achievements to stdout
+    /// Print a summary of achievements, and the top scorers across all repositories, to stdout
     #[clap(short, long)]
     pub summary: bool,
+
+    /// The format used to print achievement events to stdout
+    #[clap(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// The integrations to run, in addition to printing events to stdout
+    ///
+    /// May be given multiple times. Overrides `integrations.enabled` from the config file. Use
+    /// `--list-integrations` to see the available integrations.
+    #[clap(long = "integration")]
+    pub integrations: Vec<String>,
+
+    /// How many repositories to fetch and check concurrently
+    ///
+    /// With '--jobs 1', achievements are printed as they're granted. Otherwise each repository's
+    /// achievements are printed once it has been fully checked, in the same order as with
+    /// '--jobs 1'.
+    #[clap(short, long, default_value = "1")]
+    pub jobs: NonZeroUsize,
 }
 
 /// Process rules on a single configured repository
@@ -201,11 +332,26 @@ pub struct CheckOneArgs {
     /// Print a summary of achievements to stdout
     #[clap(short, long)]
     pub summary: bool,
+
+    /// The format used to print achievement events to stdout
+    #[clap(short, long, value_enum, default_value_t)]
+    pub format: OutputFormat,
+
+    /// The integrations to run, in addition to printing events to stdout
+    ///
+    /// May be given multiple times. Overrides `integrations.enabled` from the config file. Use
+    /// `--list-integrations` to see the available integrations.
+    #[clap(long = "integration")]
+    pub integrations: Vec<String>,
 }
 
 /// Fetch each repository
 #[derive(Debug, clap::Args)]
-pub struct FetchAllArgs;
+pub struct FetchAllArgs {
+    /// How many repositories to fetch concurrently
+    #[clap(short, long, default_value = "1")]
+    pub jobs: NonZeroUsize,
+}
 
 /// Render a static HTML site from exported achievement data
 #[derive(Debug, clap::Args)]
@@ -236,13 +382,130 @@ pub struct RenderArgs {
     ///
     /// When omitted, built-in templates are used. When provided, templates are loaded from this
     /// directory instead. Expected templates: base.html, index.html, achievements.html,
-    /// achievement_detail.html, repo.html, user.html, users.html.
+    /// achievement_detail.html, repo.html, user.html, users.html, leaderboard.html, and the
+    /// feed.xml Atom feed.
     #[clap(short, long)]
     pub templates: Option<PathBuf>,
+
+    /// A CSS file to include in every page, after the built-in styles
+    ///
+    /// Copied to extra.css in the output directory. Ignored if --templates replaces base.html.
+    #[clap(long)]
+    pub extra_css: Option<PathBuf>,
+
+    /// An HTML snippet to insert at the top of every page, above the navigation links
+    ///
+    /// Ignored if --templates replaces base.html.
+    #[clap(long)]
+    pub header_html: Option<PathBuf>,
+
+    /// An HTML snippet to insert into the footer of every page
+    ///
+    /// Ignored if --templates replaces base.html.
+    #[clap(long)]
+    pub footer_html: Option<PathBuf>,
+
+    /// Also write the site's data as JSON files next to the HTML pages
+    ///
+    /// See docs/user/json-api.md for the schema.
+    #[clap(long)]
+    pub emit_json: bool,
+
+    /// Images to use as avatars instead of the generated identicons, keyed by email
+    ///
+    /// Read from `integrations.static_site.avatars` in the config file, rather than the CLI.
+    #[clap(skip)]
+    pub avatars: BTreeMap<String, PathBuf>,
+}
+
+/// Explain how the observers and rules see a single commit
+///
+/// Prints the commit's mailmap-resolved author, every observation emitted for it, and whether each
+/// rule granted an achievement. Like `check`, this is stateless, so rules that depend on earlier
+/// commits are evaluated as if this were the only commit.
+#[derive(Debug, clap::Args)]
+pub struct ExplainArgs {
+    /// An absolute or relative path to a Git repository
+    ///
+    /// May be a bare repository. May be the path to the .git directory.
+    pub path: PathBuf,
+
+    /// The commit to explain
+    #[clap(default_value = "HEAD")]
+    pub commit: String,
+
+    /// Only explain this rule
+    ///
+    /// May be the rule ID, human ID, or pretty ID. May be given multiple times. Overrides
+    /// `rules.exclude` and `rules.include` from the config file.
+    #[clap(long = "rule")]
+    pub rules: Vec<String>,
+}
+
+/// How query results are written to stdout
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum QueryFormat {
+    /// A Markdown table, one event per row
+    #[default]
+    Table,
+    /// A JSON array of events
+    Json,
+}
+
+/// Search the exported achievement events of every repository
+///
+/// Events are printed oldest first. Each grant notes whether it is still held, or has since been
+/// revoked.
+#[derive(Debug, clap::Args)]
+pub struct QueryArgs {
+    /// Path to the export data directory
+    ///
+    /// This is the directory containing achievements.csv and the events/ subdirectory. Defaults to
+    /// the export/ subdirectory of the Herostratus data directory.
+    #[clap(short, long)]
+    pub export_dir: Option<PathBuf>,
+
+    /// Only show events for users with this email or name, ignoring case
+    #[clap(long)]
+    pub user: Option<String>,
+
+    /// Only show events for this achievement
+    ///
+    /// May be the rule ID, human ID, or pretty ID.
+    #[clap(long)]
+    pub achievement: Option<String>,
+
+    /// Only show events from this repository
+    #[clap(long)]
+    pub repository: Option<String>,
+
+    /// Only show grants, or only show revocations
+    #[clap(long, value_enum)]
+    pub event: Option<crate::achievement::AchievementEventKind>,
+
+    /// Only show grants that haven't been revoked
+    #[clap(long)]
+    pub active: bool,
+
+    /// Only show events on or after this date
+    ///
+    /// Either an RFC 3339 timestamp, or a YYYY-MM-DD date (midnight UTC).
+    #[clap(long, value_parser = parse_date)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only show events before this date
+    ///
+    /// Either an RFC 3339 timestamp, or a YYYY-MM-DD date (midnight UTC).
+    #[clap(long, value_parser = parse_date)]
+    pub until: Option<DateTime<Utc>>,
+
+    /// The format used to print the matching events
+    #[clap(short, long, value_enum, default_value_t)]
+    pub format: QueryFormat,
 }
 
 impl From<&CheckAllArgs> for FetchAllArgs {
-    fn from(_args: &CheckAllArgs) -> FetchAllArgs {
-        FetchAllArgs
+    fn from(args: &CheckAllArgs) -> FetchAllArgs {
+        FetchAllArgs { jobs: args.jobs }
     }
 }
@@ -2,11 +2,16 @@ use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::time::{Duration, Instant};
 
-use crate::achievement::{AchievementEvent, grant};
-use crate::cli::{CheckAllArgs, CheckArgs, CheckOneArgs};
+use crate::achievement::{
+    Achievement, AchievementEvent, AchievementLog, GrantFilter, GrantStats, achievement_rows,
+    grant, scoring,
+};
+use crate::cli::{CheckAllArgs, CheckArgs, CheckOneArgs, OutputFormat};
 use crate::commands::fetch_all::{FetchStat, current_rule_versions, fetch_one};
-use crate::config::Config;
+use crate::config::{Config, RepositoryConfig};
 use crate::git::clone::find_local_repository;
+use crate::git::filter::CommitFilter;
+use crate::integration::{Integrations, RepositoryContext};
 
 #[derive(Clone, Debug, Default)]
 pub struct CheckStat {
@@ -49,58 +54,111 @@ pub fn check(args: &CheckArgs, config: Option<&Config>) -> eyre::Result<CheckSta
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or("".into());
 
-    check_impl(
+    let mut integrations = Integrations::new(&args.integrations, config)?;
+    let repo = RepositoryContext {
+        name: &name,
+        reference: &args.reference,
+        git_dir: &args.path,
+        data_dir: None,
         config,
-        &name,
-        &args.path,
-        &args.reference,
-        args.depth,
-        None,
-        None,
-    )
+        repo_config: None,
+    };
+    let filter = GrantFilter {
+        rules: args.rules.clone(),
+        exclude_rules: args.exclude_rules.clone(),
+        commits: CommitFilter {
+            since: args.since,
+            until: args.until,
+            author: args.author.clone(),
+            pathspecs: args.pathspecs.clone(),
+        },
+    };
+    check_impl(&repo, args.depth, &filter, args.format, &mut integrations)
 }
 
+/// Check a repository, printing and publishing each event as it's generated
 fn check_impl(
-    config: Option<&Config>,
-    name: &str,
-    path: &Path,
-    reference: &str,
+    repo: &RepositoryContext,
     depth: Option<usize>,
-    data_dir: Option<&Path>,
-    repo_config: Option<&crate::config::RepositoryConfig>,
+    filter: &GrantFilter,
+    format: OutputFormat,
+    integrations: &mut Integrations,
 ) -> eyre::Result<CheckStat> {
-    tracing::info!("Checking repository {path:?}, reference {reference:?} for achievements ...");
-    let mut repo = find_local_repository(path)?;
+    let prior = load_prior(repo)?;
+    integrations.on_repository_start(repo, &prior)?;
+
+    let mut integration_error = None;
+    let run = run_repository(repo, depth, filter, |e| {
+        process_event(repo.name, e, format);
+        if integration_error.is_none()
+            && let Err(err) = integrations.on_event(repo, e)
+        {
+            integration_error = Some(err);
+        }
+    })?;
+    if let Some(err) = integration_error {
+        return Err(err);
+    }
+    finish_repository(repo, run, format, integrations)
+}
+
+/// The achievement log from previous runs, or an empty log for stateless runs
+fn load_prior(repo: &RepositoryContext) -> eyre::Result<AchievementLog> {
+    let log_path = repo
+        .data_dir
+        .map(|d| AchievementLog::path_for(d, repo.name));
+    AchievementLog::load(log_path.as_deref())
+}
+
+/// A repository that has been checked, but whose stats have not been published
+struct RepositoryRun {
+    events: Vec<AchievementEvent>,
+    stats: GrantStats,
+}
+
+/// Run the pipeline on a repository, without printing or publishing anything.
+///
+/// Safe to call from several threads at once, so long as each is checking a different repository.
+fn run_repository(
+    repo: &RepositoryContext,
+    depth: Option<usize>,
+    filter: &GrantFilter,
+    mut on_event: impl FnMut(&AchievementEvent),
+) -> eyre::Result<RepositoryRun> {
+    tracing::info!(
+        "Checking repository {:?}, reference {:?} for achievements ...",
+        repo.git_dir,
+        repo.reference
+    );
+    let mut git_repo = find_local_repository(repo.git_dir)?;
+
     let mut events = Vec::new();
     let stats = grant(
-        config,
-        reference,
-        &mut repo,
+        repo.config,
+        repo.reference,
+        &mut git_repo,
         depth,
-        data_dir,
-        name,
-        repo_config,
+        repo.data_dir,
+        repo.name,
+        repo.repo_config,
+        filter,
         |e| {
-            process_event(&e);
+            on_event(&e);
             events.push(e);
         },
     )?;
+    Ok(RepositoryRun { events, stats })
+}
 
-    if let Some(data_dir) = data_dir
-        && stats.num_commits_processed > 0
-    {
-        let repo_config = config.and_then(|c| c.repositories.get(name));
-        let url = repo_config.map(|rc| rc.url.as_str()).unwrap_or("");
-        let commit_url_prefix = repo_config.and_then(|rc| rc.resolve_commit_url_prefix());
-        crate::achievement::upsert_repository_csv(
-            data_dir,
-            name,
-            url,
-            commit_url_prefix.as_deref(),
-            reference,
-            stats.num_commits_processed,
-        )?;
-    }
+fn finish_repository(
+    repo: &RepositoryContext,
+    run: RepositoryRun,
+    format: OutputFormat,
+    integrations: &mut Integrations,
+) -> eyre::Result<CheckStat> {
+    let RepositoryRun { events, stats } = run;
+    process_stats(repo.name, &stats, format);
+    integrations.on_repository_complete(repo, &stats)?;
 
     let counts = tally_achievements(&events);
     for (pretty_id, count) in &counts {
@@ -108,7 +166,7 @@ fn check_impl(
     }
 
     Ok(CheckStat {
-        name: name.to_string(),
+        name: repo.name.to_string(),
         num_commits_checked: stats.num_commits_processed,
         num_achievements_granted: stats.num_achievements_generated,
         elapsed: stats.elapsed,
@@ -116,6 +174,23 @@ fn check_impl(
     })
 }
 
+/// The context for a repository from `config.toml`
+fn configured_repository<'a>(
+    name: &'a str,
+    repo_config: &'a RepositoryConfig,
+    config: &'a Config,
+    data_dir: &'a Path,
+) -> RepositoryContext<'a> {
+    RepositoryContext {
+        name,
+        reference: repo_config.reference.as_deref().unwrap_or("HEAD"),
+        git_dir: &repo_config.path,
+        data_dir: Some(data_dir),
+        config: Some(config),
+        repo_config: Some(repo_config),
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CheckAllStat {
     pub name: String,
@@ -149,6 +224,45 @@ pub fn print_check_all_summary(stats: &[CheckAllStat]) {
     }
 }
 
+/// The number of users to include in the top scorers summary
+const NUM_TOP_SCORERS: usize = 10;
+
+/// Print the overall leaderboard across every configured repository
+pub fn print_top_scorers(config: &Config, data_dir: &Path) -> eyre::Result<()> {
+    let default_rc = crate::config::RulesConfig::default();
+    let rules_config = config.rules.as_ref().unwrap_or(&default_rc);
+    let catalog = achievement_rows(&crate::rules::all_rules(rules_config), rules_config);
+
+    let mut events = BTreeMap::new();
+    for name in config.repositories.keys() {
+        let log = AchievementLog::load(Some(&AchievementLog::path_for(data_dir, name)))?;
+        events.insert(name.clone(), log.events().to_vec());
+    }
+    let leaderboards = scoring::score(&catalog, &events);
+
+    println!("## Top scorers");
+    println!("| Rank | Name | Points | # Achievements |");
+    println!("| ---- | ---- | ------ | -------------- |");
+    let mut rank = 0;
+    let mut prev_points = None;
+    for (i, entry) in leaderboards
+        .overall
+        .iter()
+        .take(NUM_TOP_SCORERS)
+        .enumerate()
+    {
+        if prev_points != Some(entry.points) {
+            rank = i + 1;
+            prev_points = Some(entry.points);
+        }
+        println!(
+            "| {} | {} | {} | {} |",
+            rank, entry.user_name, entry.points, entry.achievements
+        );
+    }
+    Ok(())
+}
+
 fn merge_stats(fetch: Vec<FetchStat>, check: Vec<CheckStat>) -> Vec<CheckAllStat> {
     let fetch: HashMap<String, FetchStat> =
         HashMap::from_iter(fetch.into_iter().map(|f| (f.name.clone(), f)));
@@ -185,33 +299,71 @@ pub fn check_all(
         fetch_stats = crate::commands::fetch_all(&args.into(), config, data_dir)?;
     }
 
+    let mut integrations = Integrations::new(&args.integrations, Some(config))?;
+
     tracing::info!("Checking repositories ...");
     let start = Instant::now();
-    for (name, repo_config) in config.repositories.iter() {
-        let reference = repo_config
-            .reference
-            .clone()
-            .unwrap_or_else(|| String::from("HEAD"));
-        let check_stat = check_impl(
-            Some(config),
-            name,
-            &repo_config.path,
-            &reference,
-            args.depth,
-            Some(data_dir),
-            Some(repo_config),
-        )?;
-        check_stats.push(check_stat);
+    let filter = GrantFilter::default();
+    if args.jobs.get() == 1 {
+        // Stream each event as it's generated, like check-one
+        for (name, repo_config) in &config.repositories {
+            let repo = configured_repository(name, repo_config, config, data_dir);
+            check_stats.push(check_impl(
+                &repo,
+                args.depth,
+                &filter,
+                args.format,
+                &mut integrations,
+            )?);
+        }
+    } else {
+        check_parallel(args, config, data_dir, &mut integrations, &mut check_stats)?;
     }
     tracing::info!(
         "... checked {} repositories after {:.2?}",
         config.repositories.len(),
         start.elapsed()
     );
+    integrations.on_check_all_complete(config, data_dir)?;
 
     Ok(merge_stats(fetch_stats, check_stats))
 }
 
+/// Check every repository on `args.jobs` threads.
+///
+/// Workers only run the pipeline. Everything that writes to stdout or to files shared between
+/// repositories happens on this thread, in config order, once a repository's pipeline has
+/// finished. So the integration hooks for a repository all run after its pipeline did.
+fn check_parallel(
+    args: &CheckAllArgs,
+    config: &Config,
+    data_dir: &Path,
+    integrations: &mut Integrations,
+    check_stats: &mut Vec<CheckStat>,
+) -> eyre::Result<()> {
+    let filter = GrantFilter::default();
+    crate::commands::parallel::for_each_repository(
+        config,
+        args.jobs,
+        |name, repo_config| {
+            let repo = configured_repository(name, repo_config, config, data_dir);
+            let prior = load_prior(&repo)?;
+            let run = run_repository(&repo, args.depth, &filter, |_| {})?;
+            Ok((prior, run))
+        },
+        |name, repo_config, (prior, run)| {
+            let repo = configured_repository(name, repo_config, config, data_dir);
+            integrations.on_repository_start(&repo, &prior)?;
+            for e in &run.events {
+                process_event(name, e, args.format);
+                integrations.on_event(&repo, e)?;
+            }
+            check_stats.push(finish_repository(&repo, run, args.format, integrations)?);
+            Ok(())
+        },
+    )
+}
+
 /// Look up a repository by name or remote URL
 fn find_repository<'a>(
     config: &'a Config,
@@ -250,27 +402,98 @@ pub fn check_one(
         fetch_stats.push(fetch_one(name, repo_config, data_dir, &current_rules)?);
     }
 
-    let reference = repo_config
-        .reference
-        .clone()
-        .unwrap_or_else(|| String::from("HEAD"));
+    let mut integrations = Integrations::new(&args.integrations, Some(config))?;
+    let repo = configured_repository(name, repo_config, config, data_dir);
     let check_stat = check_impl(
-        Some(config),
-        name,
-        &repo_config.path,
-        &reference,
+        &repo,
         args.depth,
-        Some(data_dir),
-        Some(repo_config),
+        &GrantFilter::default(),
+        args.format,
+        &mut integrations,
     )?;
 
     Ok(merge_stats(fetch_stats, vec![check_stat]))
 }
 
+/// A single line of `--format=jsonl` output
+#[derive(serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonRecord<'a> {
+    Grant(JsonAchievement<'a>),
+    Revoke(JsonAchievement<'a>),
+    Stats {
+        repository: &'a str,
+        commits_processed: u64,
+        achievements_granted: u64,
+        elapsed_ms: u128,
+    },
+}
+
+#[derive(serde::Serialize)]
+struct JsonAchievement<'a> {
+    repository: &'a str,
+    descriptor_id: usize,
+    achievement_id: &'a str,
+    name: &'a str,
+    description: &'a str,
+    commit: String,
+    user_name: &'a str,
+    user_email: &'a str,
+    timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+impl<'a> JsonAchievement<'a> {
+    fn new(repository: &'a str, a: &'a Achievement) -> Self {
+        Self {
+            repository,
+            descriptor_id: a.descriptor_id,
+            achievement_id: a.human_id,
+            name: &a.name,
+            description: &a.description,
+            commit: a.commit.to_string(),
+            user_name: &a.user_name,
+            user_email: &a.user_email,
+            timestamp: a.timestamp,
+        }
+    }
+}
+
+fn print_json(record: &JsonRecord) {
+    match serde_json::to_string(record) {
+        Ok(line) => println!("{line}"),
+        Err(e) => tracing::error!("failed to serialize event because: '{e:?}'"),
+    }
+}
+
 /// A common event sink that both check and check_all can use
-fn process_event(event: &AchievementEvent) {
-    // TODO: Support different output formats
-    println!("{event:?}");
+fn process_event(repository: &str, event: &AchievementEvent, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => println!("{event:?}"),
+        OutputFormat::Jsonl => {
+            let record = match event {
+                AchievementEvent::Grant(a) => {
+                    JsonRecord::Grant(JsonAchievement::new(repository, a))
+                }
+                AchievementEvent::Revoke(a) => {
+                    JsonRecord::Revoke(JsonAchievement::new(repository, a))
+                }
+            };
+            print_json(&record);
+        }
+    }
+}
+
+/// Print the final per-repository stats record, if the output format has one
+fn process_stats(repository: &str, stats: &GrantStats, format: OutputFormat) {
+    match format {
+        OutputFormat::Text => {}
+        OutputFormat::Jsonl => print_json(&JsonRecord::Stats {
+            repository,
+            commits_processed: stats.num_commits_processed,
+            achievements_granted: stats.num_achievements_generated,
+            elapsed_ms: stats.elapsed.as_millis(),
+        }),
+    }
 }
 
 /// Count grants per achievement, sorted by descriptor ID.
@@ -0,0 +1,277 @@
+use std::mem::discriminant;
+use std::path::{Path, PathBuf};
+
+use eyre::WrapErr;
+
+use crate::achievement::{Grant, GrantFilter};
+use crate::cli::ExplainArgs;
+use crate::config::{Config, RepositoryConfig, RulesConfig};
+use crate::git::clone::find_local_repository;
+use crate::git::mailmap::MailmapResolver;
+use crate::observer::{
+    CommitContext, Observation, ObserverData, ObserverEngine, builtin_observers,
+};
+use crate::rules::RulePlugin;
+
+/// Everything the observers and rules made of a single commit
+struct Explanation {
+    /// The author as recorded in the commit, before mailmap resolution
+    raw_author: String,
+    context: CommitContext,
+    observations: Vec<ExplainedObservation>,
+    rules: Vec<RuleDecision>,
+}
+
+struct ExplainedObservation {
+    observation: Observation,
+    /// The pretty IDs of the rules that consume this kind of observation
+    consumers: Vec<String>,
+}
+
+struct RuleDecision {
+    pretty_id: String,
+    /// How many of the commit's observations this rule consumes
+    consumed: usize,
+    /// Grants returned by `process` and `commit_complete`
+    grants: Vec<Grant>,
+    /// Grants returned by `finalize`, for rules that accumulate state across commits
+    finalized: Option<Vec<Grant>>,
+}
+
+// Stateless, like check; only reads the config if --data-dir was explicitly passed
+pub fn explain(args: &ExplainArgs, config: Option<&Config>) -> eyre::Result<()> {
+    let explanation = explain_commit(args, config)?;
+    print!("{}", format_explanation(&explanation));
+    Ok(())
+}
+
+fn explain_commit(args: &ExplainArgs, config: Option<&Config>) -> eyre::Result<Explanation> {
+    let repo = find_local_repository(&args.path)?;
+    let oid = crate::git::rev::parse(&args.commit, &repo)
+        .wrap_err_with(|| format!("Failed to rev-parse: {:?}", args.commit))?;
+
+    let default_rc = RulesConfig::default();
+    let rules_config = config.and_then(|c| c.rules.as_ref()).unwrap_or(&default_rc);
+    let filter = GrantFilter {
+        rules: args.rules.clone(),
+        ..Default::default()
+    };
+    let mut rules = filter.build_rules(rules_config)?;
+
+    let global_mailmap = config.and_then(|c| c.mailmap_file.as_deref());
+    let repo_mailmap = config
+        .and_then(|c| configured_repository(c, &args.path, &repo))
+        .and_then(|rc| rc.mailmap_file.as_deref());
+    let mailmap = MailmapResolver::new(repo.open_mailmap(), global_mailmap, repo_mailmap)?;
+
+    let raw_author = {
+        let commit = repo.find_commit(oid)?;
+        let author = commit.author()?;
+        format!("{} <{}>", author.name, author.email)
+    };
+
+    // Unlike the pipeline, run every observer so that observations no rule consumes are shown too
+    let mut engine = ObserverEngine::new(&repo, builtin_observers(), mailmap)?;
+    let mut context = None;
+    let mut observations = Vec::new();
+    for data in engine.process_commit(oid)? {
+        match data {
+            ObserverData::CommitStart(ctx) => context = Some(ctx),
+            ObserverData::Observation(obs) => observations.push(obs),
+            ObserverData::CommitComplete => {}
+        }
+    }
+    let context = context.ok_or_else(|| eyre::eyre!("No commit context for {oid}"))?;
+
+    let co_authors = rules_config.credits_co_authors();
+    let decisions = rules
+        .iter_mut()
+        .map(|rule| decide(rule.as_mut(), &context, &observations, co_authors))
+        .collect::<eyre::Result<Vec<_>>>()?;
+
+    let observations = observations
+        .into_iter()
+        .map(|observation| {
+            let consumers = rules
+                .iter()
+                .filter(|r| r.consumes().contains(&discriminant(&observation)))
+                .map(|r| pretty_id(r.as_ref()))
+                .collect();
+            ExplainedObservation {
+                observation,
+                consumers,
+            }
+        })
+        .collect();
+
+    Ok(Explanation {
+        raw_author,
+        context,
+        observations,
+        rules: decisions,
+    })
+}
+
+/// Find the repository in `config.toml` that was cloned to `path`, whatever it's named
+fn configured_repository<'a>(
+    config: &'a Config,
+    path: &Path,
+    repo: &gix::Repository,
+) -> Option<&'a RepositoryConfig> {
+    let candidates: Vec<PathBuf> = [path, repo.git_dir()]
+        .into_iter()
+        .filter_map(|p| p.canonicalize().ok())
+        .collect();
+    config.repositories.values().find(|rc| {
+        rc.path
+            .canonicalize()
+            .is_ok_and(|p| candidates.contains(&p))
+    })
+}
+
+/// Feed the commit to a single rule, the same way the [RuleEngine](crate::rules::RuleEngine) does
+fn decide(
+    rule: &mut dyn RulePlugin,
+    ctx: &CommitContext,
+    observations: &[Observation],
+    co_authors: bool,
+) -> eyre::Result<RuleDecision> {
+    let pretty_id = pretty_id(rule);
+    let stateful = rule.has_cache();
+    if stateful {
+        // Start from an empty cache, as if no earlier commits had been processed
+        rule.init_cache(serde_json::Value::Null)?;
+    }
+
+    let mut grants = Vec::new();
+    rule.commit_start(ctx)?;
+    for obs in observations {
+        if let Some(grant) = rule
+            .process(ctx, obs)
+            .wrap_err_with(|| format!("{pretty_id} failed to process {obs:?}"))?
+        {
+            grants.push(grant);
+        }
+    }
+    if let Some(grant) = rule.commit_complete(ctx)? {
+        grants.push(grant);
+    }
+    if co_authors && rule.credits_co_authors() {
+        let shared: Vec<Grant> = grants
+            .iter()
+            .flat_map(|g| g.shared_with_co_authors(ctx))
+            .collect();
+        grants.extend(shared);
+    }
+    let finalized = if stateful {
+        Some(rule.finalize()?)
+    } else {
+        None
+    };
+
+    let consumed = observations
+        .iter()
+        .filter(|obs| rule.consumes().contains(&discriminant(*obs)))
+        .count();
+    Ok(RuleDecision {
+        pretty_id,
+        consumed,
+        grants,
+        finalized,
+    })
+}
+
+fn pretty_id(rule: &dyn RulePlugin) -> String {
+    let meta = rule.meta();
+    format!("H{}-{}", meta.id, meta.human_id)
+}
+
+fn format_grant(grant: &Grant) -> String {
+    match &grant.name_override {
+        Some(name) => format!("{name:?} to {} <{}>", grant.user_name, grant.user_email),
+        None => format!("to {} <{}>", grant.user_name, grant.user_email),
+    }
+}
+
+fn format_explanation(explanation: &Explanation) -> String {
+    let ctx = &explanation.context;
+    let resolved = format!("{} <{}>", ctx.author_name, ctx.author_email);
+    let mut out = String::new();
+    out.push_str(&format!("commit {}\n", ctx.oid));
+    out.push_str(&format!("Author:    {}\n", explanation.raw_author));
+    if resolved == explanation.raw_author {
+        out.push_str("Mailmap:   (unchanged)\n");
+    } else {
+        out.push_str(&format!("Mailmap:   {resolved}\n"));
+    }
+    if !ctx.co_authors.is_empty() {
+        let co_authors: Vec<String> = ctx
+            .co_authors
+            .iter()
+            .map(|c| format!("{} <{}>", c.name, c.email))
+            .collect();
+        out.push_str(&format!("Co-authors: {}\n", co_authors.join(", ")));
+    }
+    out.push_str(&format!(
+        "Authored:  {}\n",
+        ctx.author_timestamp.to_rfc3339()
+    ));
+    out.push_str(&format!(
+        "Committed: {}\n",
+        ctx.commit_timestamp.to_rfc3339()
+    ));
+
+    out.push_str("\nObservations:\n");
+    if explanation.observations.is_empty() {
+        out.push_str("  (none)\n");
+    }
+    for explained in &explanation.observations {
+        out.push_str(&format!("  {:?}\n", explained.observation));
+        if explained.consumers.is_empty() {
+            out.push_str("      not consumed by any rule\n");
+        } else {
+            out.push_str(&format!(
+                "      consumed by {}\n",
+                explained.consumers.join(", ")
+            ));
+        }
+    }
+
+    out.push_str("\nRules:\n");
+    if explanation.rules.is_empty() {
+        out.push_str("  (none)\n");
+    }
+    for decision in &explanation.rules {
+        if decision.grants.is_empty() {
+            let reason = if decision.consumed == 0 {
+                " (consumed no observations)"
+            } else {
+                ""
+            };
+            out.push_str(&format!("  {}: no grant{reason}\n", decision.pretty_id));
+        }
+        for grant in &decision.grants {
+            out.push_str(&format!(
+                "  {}: granted {}\n",
+                decision.pretty_id,
+                format_grant(grant)
+            ));
+        }
+        if let Some(finalized) = &decision.finalized {
+            out.push_str(
+                "      stateful: the decision also depends on the rule's cache of earlier commits, \
+                 and on finalize\n",
+            );
+            if finalized.is_empty() {
+                out.push_str("      finalize, if this were the only commit: no grant\n");
+            }
+            for grant in finalized {
+                out.push_str(&format!(
+                    "      finalize, if this were the only commit: granted {}\n",
+                    format_grant(grant)
+                ));
+            }
+        }
+    }
+    out
+}
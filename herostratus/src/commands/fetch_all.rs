@@ -8,7 +8,7 @@ use crate::git::clone::{clone_repository, find_local_repository, pull_branch};
 pub(crate) fn current_rule_versions(config: &Config) -> Vec<(usize, u32)> {
     let default_config = crate::config::RulesConfig::default();
     let config = config.rules.as_ref().unwrap_or(&default_config);
-    crate::rules::builtin_rules(config)
+    crate::rules::all_rules(config)
         .iter()
         .map(|rule| (rule.meta().id, rule.version()))
         .collect()
@@ -51,8 +51,10 @@ pub fn fetch_one(
             // For performance, we only shallow clone and deepen if we have a valid checkpoint that
             // we expect to reach. Otherwise (if there were rules versions that were updated, or new
             // rules added) we do a full clone, because it's *far* faster to do a full clone than an
-            // iterative shallow deepend as-needed.
-            let shallow = if checkpoint.data.commit.is_some()
+            // iterative shallow deepend as-needed. Repositories tracking several branches always
+            // get a full clone, since each branch's history is walked separately.
+            let shallow = if !config.tracks_several_references()
+                && checkpoint.data.commit.is_some()
                 && checkpoint.data.has_processed_all(current_rules)
             {
                 let depth = crate::git::clone::DEFAULT_SHALLOW_DEPTH;
@@ -78,7 +80,7 @@ pub fn fetch_one(
 }
 
 pub fn fetch_all(
-    _args: &FetchAllArgs,
+    args: &FetchAllArgs,
     config: &Config,
     data_dir: &Path,
 ) -> eyre::Result<Vec<FetchStat>> {
@@ -89,9 +91,15 @@ pub fn fetch_all(
     // *expect* diverging calculations, so I thought it was easier just to recalculate it here. Time
     // will tell if I'm right :)
     let current_rules = current_rule_versions(config);
-    for (name, repo_config) in config.repositories.iter() {
-        stats.push(fetch_one(name, repo_config, data_dir, &current_rules)?);
-    }
+    crate::commands::parallel::for_each_repository(
+        config,
+        args.jobs,
+        |name, repo_config| fetch_one(name, repo_config, data_dir, &current_rules),
+        |_, _, stat| {
+            stats.push(stat);
+            Ok(())
+        },
+    )?;
     tracing::info!(
         "... fetched {} repositories after {:.2?}",
         config.repositories.len(),
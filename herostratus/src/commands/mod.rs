@@ -1,9 +1,19 @@
 mod add;
 mod check;
+mod explain;
 mod fetch_all;
+mod parallel;
+mod query;
+mod remove;
 mod render;
 
 pub use add::add;
-pub use check::{CheckAllStat, CheckStat, check, check_all, check_one, print_check_all_summary};
+pub use check::{
+    CheckAllStat, CheckStat, check, check_all, check_one, print_check_all_summary,
+    print_top_scorers,
+};
+pub use explain::explain;
 pub use fetch_all::fetch_all;
+pub use query::query;
+pub use remove::remove;
 pub use render::render;
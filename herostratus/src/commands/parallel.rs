@@ -0,0 +1,207 @@
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+
+use crate::config::{Config, RepositoryConfig};
+
+/// Run `work` for every configured repository on up to `jobs` threads, and `publish` each result
+/// on the calling thread.
+///
+/// Results are published in config order regardless of which finishes first, so that anything
+/// `publish` writes (stdout, shared CSV files) is deterministic. Repositories that share a clone
+/// are worked on one at a time by the same thread, because fetching and deepening both modify the
+/// clone.
+///
+/// Stops handing out work after the first error, and returns the first error in config order.
+pub(crate) fn for_each_repository<T: Send>(
+    config: &Config,
+    jobs: NonZeroUsize,
+    work: impl Fn(&str, &RepositoryConfig) -> eyre::Result<T> + Sync,
+    mut publish: impl FnMut(&str, &RepositoryConfig, T) -> eyre::Result<()>,
+) -> eyre::Result<()> {
+    let repos: Vec<_> = config.repositories.iter().collect();
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for (index, (_, repo_config)) in repos.iter().enumerate() {
+        match groups
+            .iter_mut()
+            .find(|g| repos[g[0]].1.path == repo_config.path)
+        {
+            Some(group) => group.push(index),
+            None => groups.push(vec![index]),
+        }
+    }
+
+    let next_group = AtomicUsize::new(0);
+    let abort = AtomicBool::new(false);
+    let num_workers = jobs.get().min(groups.len());
+    let (tx, rx) = mpsc::channel();
+
+    std::thread::scope(|s| {
+        for _ in 0..num_workers {
+            let tx = tx.clone();
+            let (repos, groups, next_group, abort, work) =
+                (&repos, &groups, &next_group, &abort, &work);
+            s.spawn(move || {
+                loop {
+                    let group = next_group.fetch_add(1, Ordering::Relaxed);
+                    if group >= groups.len() {
+                        break;
+                    }
+                    for &index in &groups[group] {
+                        if abort.load(Ordering::Relaxed) {
+                            return;
+                        }
+                        let (name, repo_config) = repos[index];
+                        let result = work(name, repo_config);
+                        if result.is_err() {
+                            abort.store(true, Ordering::Relaxed);
+                        }
+                        // The receiver outlives every worker
+                        let _ = tx.send((index, result));
+                    }
+                }
+            });
+        }
+        // Otherwise the loop below would never end
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        let mut error = None;
+        for (index, result) in rx {
+            pending.insert(index, result);
+            while error.is_none()
+                && let Some(result) = pending.remove(&next)
+            {
+                let (name, repo_config) = repos[next];
+                next += 1;
+                if let Err(e) = result.and_then(|t| publish(name, repo_config, t)) {
+                    abort.store(true, Ordering::Relaxed);
+                    error = Some(e);
+                }
+            }
+        }
+        // Workers stop partway through a group on abort, so an error can be left behind a
+        // repository that was never sent
+        if error.is_none() {
+            error = pending.into_values().find_map(Result::err);
+        }
+        match error {
+            Some(e) => Err(e),
+            None if next < repos.len() => {
+                eyre::bail!("Stopped before processing {:?}", repos[next].0)
+            }
+            None => Ok(()),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+    use std::time::Duration;
+
+    use super::*;
+
+    fn config(paths: &[&str]) -> Config {
+        let mut config = Config::default();
+        for (i, path) in paths.iter().enumerate() {
+            config.repositories.insert(
+                format!("repo-{i}"),
+                RepositoryConfig {
+                    path: PathBuf::from(path),
+                    ..Default::default()
+                },
+            );
+        }
+        config
+    }
+
+    #[test]
+    fn publishes_in_config_order() {
+        let config = config(&["a", "b", "c", "d", "e"]);
+        let mut published = Vec::new();
+        for_each_repository(
+            &config,
+            NonZeroUsize::new(4).unwrap(),
+            |name, _| {
+                // Make the first repositories finish last
+                let index: u64 = name.trim_start_matches("repo-").parse().unwrap();
+                std::thread::sleep(Duration::from_millis(50 - 10 * index));
+                Ok(name.to_string())
+            },
+            |name, _, result| {
+                assert_eq!(name, result);
+                published.push(result);
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            published,
+            ["repo-0", "repo-1", "repo-2", "repo-3", "repo-4"]
+        );
+    }
+
+    #[test]
+    fn shared_clones_are_not_concurrent() {
+        let config = config(&["shared", "other", "shared", "shared"]);
+        let busy = AtomicBool::new(false);
+        for_each_repository(
+            &config,
+            NonZeroUsize::new(4).unwrap(),
+            |_, rc| {
+                if rc.path == Path::new("shared") {
+                    assert!(!busy.swap(true, Ordering::SeqCst));
+                    std::thread::sleep(Duration::from_millis(10));
+                    busy.store(false, Ordering::SeqCst);
+                }
+                Ok(())
+            },
+            |_, _, _| Ok(()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn returns_first_error_in_config_order() {
+        let config = config(&["a", "b", "c"]);
+        let mut published = Vec::new();
+        let result = for_each_repository(
+            &config,
+            NonZeroUsize::new(3).unwrap(),
+            |name, _| match name {
+                "repo-1" => eyre::bail!("failed {name}"),
+                _ => Ok(name.to_string()),
+            },
+            |_, _, result| {
+                published.push(result);
+                Ok(())
+            },
+        );
+        assert_eq!(result.unwrap_err().to_string(), "failed repo-1");
+        assert_eq!(published, ["repo-0"]);
+    }
+
+    #[test]
+    fn returns_error_from_later_repository_in_shared_clone() {
+        // The shared clone is worked on first, so repo-1 is never processed after repo-2 fails
+        let config = config(&["shared", "other", "shared"]);
+        let mut published = Vec::new();
+        let result = for_each_repository(
+            &config,
+            NonZeroUsize::new(1).unwrap(),
+            |name, _| match name {
+                "repo-2" => eyre::bail!("failed {name}"),
+                _ => Ok(name.to_string()),
+            },
+            |_, _, result| {
+                published.push(result);
+                Ok(())
+            },
+        );
+        assert_eq!(result.unwrap_err().to_string(), "failed repo-2");
+        assert_eq!(published, ["repo-0"]);
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use chrono::{DateTime, Utc};
+
+use super::render::data::{AchievementLogEvent, AchievementRow, load_achievements, load_events};
+use crate::achievement::{AchievementEventKind, AchievementLog};
+use crate::cli::{QueryArgs, QueryFormat};
+
+/// A single event matched by a query
+#[derive(Debug, serde::Serialize)]
+struct QueryResult<'a> {
+    timestamp: DateTime<Utc>,
+    repository: &'a str,
+    event: &'a AchievementEventKind,
+    achievement_id: &'a str,
+    /// The achievement's name, or the name this grant was given by its rule
+    achievement_name: &'a str,
+    user_name: &'a str,
+    user_email: &'a str,
+    commit: String,
+    /// Whether a grant is still held. Always false for revocations.
+    active: bool,
+    /// The pretty ID, like "H1-fixup", for the table
+    #[serde(skip)]
+    pretty_id: String,
+}
+
+pub fn query(args: &QueryArgs) -> eyre::Result<()> {
+    let export_dir = args
+        .export_dir
+        .as_deref()
+        .ok_or_else(|| eyre::eyre!("query requires --export-dir"))?;
+    let achievements = load_achievements(export_dir)?;
+    let events = load_events(export_dir)?;
+    let logs: Vec<(String, AchievementLog)> = events
+        .into_iter()
+        .map(|(repo, events)| (repo, AchievementLog::from_events(events)))
+        .collect();
+
+    let results = run_query(args, &achievements, &logs)?;
+    match args.format {
+        QueryFormat::Table => print_table(&results),
+        QueryFormat::Json => println!("{}", serde_json::to_string_pretty(&results)?),
+    }
+    Ok(())
+}
+
+fn run_query<'a>(
+    args: &QueryArgs,
+    achievements: &'a [AchievementRow],
+    logs: &'a [(String, AchievementLog)],
+) -> eyre::Result<Vec<QueryResult<'a>>> {
+    // Resolve the achievement up front, so that a typo is an error rather than an empty result
+    let achievement = match &args.achievement {
+        Some(id) => match achievements.iter().find(|a| a.id_matches(id)) {
+            Some(row) => Some(row.human_id.as_str()),
+            None if logs
+                .iter()
+                .any(|(_, log)| log.events().iter().any(|e| e.achievement_id == *id)) =>
+            {
+                Some(id.as_str())
+            }
+            None => eyre::bail!(
+                "Achievement {id:?} not found. Use `herostratus --list-rules` to see the available rules"
+            ),
+        },
+        None => None,
+    };
+    let user = args.user.as_deref().map(str::to_lowercase);
+
+    let mut results = Vec::new();
+    for (repo, log) in logs {
+        if args.repository.as_ref().is_some_and(|r| r != repo) {
+            continue;
+        }
+        // The log's own active-grant logic decides which grants have been revoked
+        let active: HashSet<usize> = log.active_grant_indices().collect();
+
+        for (index, event) in log.events().iter().enumerate() {
+            let is_active = active.contains(&index);
+            if achievement.is_some_and(|a| a != event.achievement_id)
+                || user.as_ref().is_some_and(|u| {
+                    *u != event.user_email.to_lowercase() && *u != event.user_name.to_lowercase()
+                })
+                || args.event.as_ref().is_some_and(|e| *e != event.event)
+                || (args.active && !is_active)
+                || args.since.is_some_and(|since| event.timestamp < since)
+                || args.until.is_some_and(|until| event.timestamp >= until)
+            {
+                continue;
+            }
+            results.push(to_result(repo, event, is_active, achievements));
+        }
+    }
+    results.sort_by(|a, b| {
+        a.timestamp
+            .cmp(&b.timestamp)
+            .then_with(|| a.repository.cmp(b.repository))
+    });
+    Ok(results)
+}
+
+fn to_result<'a>(
+    repo: &'a str,
+    event: &'a AchievementLogEvent,
+    active: bool,
+    achievements: &'a [AchievementRow],
+) -> QueryResult<'a> {
+    let row = achievements
+        .iter()
+        .find(|a| a.human_id == event.achievement_id);
+    let achievement_name = match (&event.name_override, row) {
+        (Some(name), _) => name.as_str(),
+        (None, Some(row)) => row.name.as_str(),
+        (None, None) => event.achievement_id.as_str(),
+    };
+    let pretty_id = match row {
+        Some(row) => format!("H{}-{}", row.id, row.human_id),
+        None => event.achievement_id.clone(),
+    };
+    QueryResult {
+        timestamp: event.timestamp,
+        repository: repo,
+        event: &event.event,
+        achievement_id: &event.achievement_id,
+        achievement_name,
+        user_name: &event.user_name,
+        user_email: &event.user_email,
+        commit: event.commit.to_string(),
+        active,
+        pretty_id,
+    }
+}
+
+fn print_table(results: &[QueryResult]) {
+    println!("| Date | Repository | Event | Achievement | User | Commit | Status |");
+    println!("| ---- | ---------- | ----- | ----------- | ---- | ------ | ------ |");
+    for result in results {
+        let (event, status) = match result.event {
+            AchievementEventKind::Grant if result.active => ("grant", "active"),
+            AchievementEventKind::Grant => ("grant", "revoked"),
+            AchievementEventKind::Revoke => ("revoke", ""),
+        };
+        println!(
+            "| {} | {} | {event} | {} ({}) | {} <{}> | {} | {status} |",
+            result.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            result.repository,
+            result.achievement_name,
+            result.pretty_id,
+            result.user_name,
+            result.user_email,
+            &result.commit[..result.commit.len().min(10)],
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate::achievement::{AchievementLog, remove_repository_csv};
+use crate::cli::RemoveArgs;
+use crate::config::Config;
+
+/// Find the name of the repository given by name or clone URL
+fn resolve_name(repository: &str, config: &Config) -> eyre::Result<String> {
+    if config.repositories.contains_key(repository) {
+        return Ok(repository.to_string());
+    }
+
+    let matches: Vec<&String> = config
+        .repositories
+        .iter()
+        .filter(|(_, rc)| rc.url == repository)
+        .map(|(name, _)| name)
+        .collect();
+    match matches.as_slice() {
+        [] => eyre::bail!("No configured repository with name or URL {repository:?}"),
+        [name] => Ok(name.to_string()),
+        names => eyre::bail!(
+            "URL {repository:?} matches multiple repositories {names:?}; remove one by name instead"
+        ),
+    }
+}
+
+fn remove_path(path: &Path) -> eyre::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)?;
+    } else if path.exists() {
+        std::fs::remove_file(path)?;
+    } else {
+        return Ok(());
+    }
+    tracing::info!("Removed {path:?}");
+    Ok(())
+}
+
+pub fn remove(args: &RemoveArgs, config: &mut Config, data_dir: &Path) -> eyre::Result<()> {
+    let name = resolve_name(&args.repository, config)?;
+    let repo_config = config
+        .repositories
+        .remove(&name)
+        .expect("name was resolved from the config");
+    tracing::info!("Removed repository {name:?} from the config");
+
+    if !args.purge {
+        return Ok(());
+    }
+
+    // Never delete a user's own checkout, or a bare repository that another branch still uses
+    let shared = config
+        .repositories
+        .values()
+        .any(|rc| rc.path == repo_config.path);
+    if !repo_config.path.starts_with(data_dir) {
+        tracing::warn!(
+            "Not deleting {:?} because it's outside of the data directory",
+            repo_config.path
+        );
+    } else if shared {
+        tracing::info!(
+            "Not deleting {:?} because it's shared with another repository",
+            repo_config.path
+        );
+    } else {
+        remove_path(&repo_config.path)?;
+    }
+
+    remove_path(&data_dir.join("cache").join(&name))?;
+
+    if !args.keep_events {
+        remove_path(&AchievementLog::path_for(data_dir, &name))?;
+        remove_repository_csv(data_dir, &name)?;
+    }
+    Ok(())
+}
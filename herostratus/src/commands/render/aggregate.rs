@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use chrono::{DateTime, Utc};
 
 use super::users::User;
+use crate::achievement::scoring::{self, ScoreEntry};
 use crate::achievement::{
     AchievementEventKind, AchievementLogEvent, AchievementRow, RepositoryRow,
 };
@@ -12,7 +13,7 @@ use crate::achievement::{
 /// Chrono's default serialization preserves sub-second precision, which produces inconsistent
 /// output depending on the source (e.g. `Utc::now()` has nanoseconds, git timestamps do not).
 /// This module normalizes all timestamps to second precision.
-mod timestamp_serde {
+pub(super) mod timestamp_serde {
     use chrono::{DateTime, Utc};
     use serde::Serializer;
 
@@ -30,6 +31,7 @@ pub struct SiteData {
     pub repositories: Vec<RepoContext>,
     pub users: Vec<UserContext>,
     pub recent_activity: Vec<ActivityEntry>,
+    pub leaderboards: LeaderboardsContext,
 }
 
 /// Per-achievement aggregated data.
@@ -83,9 +85,11 @@ pub struct RepoContext {
 /// Per-user aggregated data.
 #[derive(Debug, serde::Serialize)]
 pub struct UserContext {
+    /// The display name. Email addresses are deliberately omitted so they aren't published.
     pub name: String,
-    pub email: String,
     pub slug: String,
+    /// The path of the user's avatar image, relative to the site root
+    pub avatar: String,
     pub total_achievements: usize,
     pub active_achievements: usize,
     pub repos_contributed_to: usize,
@@ -110,6 +114,15 @@ pub struct UserAchievementEntry {
     pub commit: String,
     #[serde(serialize_with = "timestamp_serde::serialize")]
     pub timestamp: DateTime<Utc>,
+    /// The other users holding the same achievement for the same commit, like co-authors
+    pub shared_with: Vec<SharedCredit>,
+}
+
+/// Another user who shares the credit for an achievement.
+#[derive(Debug, serde::Serialize)]
+pub struct SharedCredit {
+    pub user_name: String,
+    pub user_slug: String,
 }
 
 /// An entry in an achievement summary table.
@@ -120,6 +133,34 @@ pub struct AchievementSummaryEntry {
     pub grant_count: usize,
 }
 
+/// The overall, per-repository, and per-achievement leaderboards.
+#[derive(Debug, serde::Serialize)]
+pub struct LeaderboardsContext {
+    pub overall: Vec<LeaderboardEntry>,
+    pub by_repository: Vec<NamedLeaderboard>,
+    pub by_achievement: Vec<NamedLeaderboard>,
+}
+
+/// The leaderboard for a single repository or achievement.
+#[derive(Debug, serde::Serialize)]
+pub struct NamedLeaderboard {
+    pub name: String,
+    /// The repository name or achievement human ID, for linking to its page
+    pub slug: String,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+/// A user's place on a leaderboard. Tied users share the same rank.
+#[derive(Debug, serde::Serialize)]
+pub struct LeaderboardEntry {
+    pub rank: usize,
+    pub user_name: String,
+    pub user_slug: String,
+    pub user_avatar: String,
+    pub points: u64,
+    pub achievements: usize,
+}
+
 /// A single event for display in timelines and recent activity.
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ActivityEntry {
@@ -219,11 +260,71 @@ pub fn aggregate(
     );
     user_contexts.sort_by_key(|c| c.name.to_lowercase());
 
+    let leaderboards = build_leaderboards(achievements, events, &user_by_email);
+
     SiteData {
         achievements: achievement_contexts,
         repositories: repo_contexts,
         users: user_contexts,
         recent_activity,
+        leaderboards,
+    }
+}
+
+fn build_leaderboards(
+    achievements: &[AchievementRow],
+    events: &BTreeMap<String, Vec<AchievementLogEvent>>,
+    user_by_email: &HashMap<&str, &User>,
+) -> LeaderboardsContext {
+    let boards = scoring::score(achievements, events);
+    let entries = |board: &[ScoreEntry]| -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = Vec::with_capacity(board.len());
+        for (i, score) in board.iter().enumerate() {
+            let rank = match entries.last() {
+                Some(prev) if prev.points == score.points => prev.rank,
+                _ => i + 1,
+            };
+            let user = user_by_email.get(score.user_email.as_str());
+            entries.push(LeaderboardEntry {
+                rank,
+                user_name: user
+                    .map(|u| u.name.clone())
+                    .unwrap_or_else(|| score.user_name.clone()),
+                user_slug: user.map(|u| u.slug.clone()).unwrap_or_default(),
+                user_avatar: user.map(|u| u.avatar.clone()).unwrap_or_default(),
+                points: score.points,
+                achievements: score.achievements,
+            });
+        }
+        entries
+    };
+
+    // Order the per-achievement leaderboards like the achievements page
+    let by_achievement = achievements
+        .iter()
+        .filter_map(|a| {
+            let board = boards.by_achievement.get(&a.human_id)?;
+            Some(NamedLeaderboard {
+                name: a.name.clone(),
+                slug: a.human_id.clone(),
+                entries: entries(board),
+            })
+        })
+        .collect();
+
+    LeaderboardsContext {
+        overall: entries(&boards.overall),
+        by_repository: boards
+            .by_repository
+            .iter()
+            .filter(|(_, board)| !board.is_empty())
+            .map(|(repo, board)| NamedLeaderboard {
+                name: repo.clone(),
+                slug: repo.clone(),
+                entries: entries(board),
+            })
+            .collect(),
+        by_achievement,
     }
 }
 
@@ -444,6 +545,7 @@ fn build_user_contexts(
                         ),
                         commit: grant.event.commit.to_string(),
                         timestamp: grant.event.timestamp,
+                        shared_with: shared_credit(grant, active_grants, users),
                     });
             }
             let mut achievements_by_repo: Vec<UserRepoAchievements> = by_repo
@@ -473,8 +575,8 @@ fn build_user_contexts(
 
             UserContext {
                 name: user.name.clone(),
-                email: user.email.clone(),
                 slug: user.slug.clone(),
+                avatar: user.avatar.clone(),
                 total_achievements,
                 active_achievements: user_active.len(),
                 repos_contributed_to,
@@ -485,6 +587,32 @@ fn build_user_contexts(
         .collect()
 }
 
+/// Find the other users holding the same achievement as `grant`, for the same commit
+fn shared_credit(
+    grant: &ActiveGrant<'_>,
+    active_grants: &[ActiveGrant<'_>],
+    users: &[User],
+) -> Vec<SharedCredit> {
+    // Meta achievements aren't tied to a commit
+    if grant.event.commit.is_null() {
+        return Vec::new();
+    }
+    active_grants
+        .iter()
+        .filter(|other| {
+            other.repo_name == grant.repo_name
+                && other.event.achievement_id == grant.event.achievement_id
+                && other.event.commit == grant.event.commit
+                && other.event.user_email != grant.event.user_email
+        })
+        .filter_map(|other| users.iter().find(|u| u.email == other.event.user_email))
+        .map(|user| SharedCredit {
+            user_name: user.name.clone(),
+            user_slug: user.slug.clone(),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use chrono::TimeZone;
@@ -520,6 +648,7 @@ mod tests {
             name: name.to_string(),
             description: "test".to_string(),
             kind: "per-user".to_string(),
+            points: 10,
         }
     }
 
@@ -534,11 +663,7 @@ mod tests {
     }
 
     fn test_user(email: &str, name: &str, slug: &str) -> User {
-        User {
-            email: email.to_string(),
-            name: name.to_string(),
-            slug: slug.to_string(),
-        }
+        User::new(email, name, slug)
     }
 
     #[test]
@@ -683,6 +808,69 @@ mod tests {
         assert_eq!(alice.repos_contributed_to, 2);
     }
 
+    #[test]
+    fn aggregate_user_achievements_show_shared_credit() {
+        let achievements = vec![
+            test_achievement("fixup", "Leftovers"),
+            test_achievement("empty-commit", "Nothing to See Here"),
+        ];
+        let repositories = vec![test_repo("repo")];
+        let mut other_commit = make_event(
+            "carol@example.com",
+            "Carol",
+            "fixup",
+            AchievementEventKind::Grant,
+            100,
+        );
+        other_commit.commit = gix::ObjectId::from_bytes_or_panic(&[0xBB; 20]);
+        let events = BTreeMap::from([(
+            "repo".to_string(),
+            vec![
+                make_event(
+                    "alice@example.com",
+                    "Alice",
+                    "fixup",
+                    AchievementEventKind::Grant,
+                    100,
+                ),
+                make_event(
+                    "bob@example.com",
+                    "Bob",
+                    "fixup",
+                    AchievementEventKind::Grant,
+                    100,
+                ),
+                make_event(
+                    "carol@example.com",
+                    "Carol",
+                    "empty-commit",
+                    AchievementEventKind::Grant,
+                    100,
+                ),
+                other_commit,
+            ],
+        )]);
+        let users = vec![
+            test_user("alice@example.com", "Alice", "alice"),
+            test_user("bob@example.com", "Bob", "bob"),
+            test_user("carol@example.com", "Carol", "carol"),
+        ];
+
+        let site = aggregate(&achievements, &repositories, &events, &users);
+
+        let alice = &site.users[0];
+        let shared = &alice.achievements_by_repo[0].achievements[0].shared_with;
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].user_name, "Bob");
+        assert_eq!(shared[0].user_slug, "bob");
+
+        // Same commit but a different achievement, or same achievement but a different commit
+        let carol = &site.users[2];
+        for entry in &carol.achievements_by_repo[0].achievements {
+            assert!(entry.shared_with.is_empty(), "{entry:?}");
+        }
+    }
+
     #[test]
     fn aggregate_recent_activity_sorted_reverse_chronological() {
         let achievements = vec![test_achievement("fixup", "Leftovers")];
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use eyre::WrapErr;
+
+use super::site_writer::SiteWriter;
+use super::users::User;
+
+/// The identicons are a square grid of this many cells, mirrored left to right
+const GRID: usize = 5;
+
+/// Write an avatar for each user to `avatar/` in the output directory, and point
+/// [User::avatar] at it
+///
+/// Users with an image in `overrides`, keyed by their mailmap-resolved email, get a copy of that
+/// image. Everyone else gets an [identicon].
+pub fn write_avatars(
+    users: &mut [User],
+    overrides: &BTreeMap<String, PathBuf>,
+    writer: &mut SiteWriter,
+) -> eyre::Result<()> {
+    for user in users {
+        if let Some(image) = overrides.get(&user.email) {
+            let extension = image
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_else(|| "png".to_string());
+            user.avatar = format!("avatar/{}.{extension}", user.slug);
+            let contents = std::fs::read(image)
+                .wrap_err_with(|| format!("Failed to read avatar {image:?} for {}", user.email))?;
+            writer.write(&user.avatar, contents)?;
+        } else {
+            writer.write(&user.avatar, identicon(&user.email)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generate a GitHub style identicon SVG from the SHA1 of an email address
+///
+/// The same email always gives the same identicon, regardless of case or surrounding whitespace.
+pub fn identicon(email: &str) -> eyre::Result<String> {
+    let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+    hasher.update(email.trim().to_lowercase().as_bytes());
+    let digest = hasher.try_finalize()?;
+    let bytes = digest.as_bytes();
+
+    let hue = u16::from_be_bytes([bytes[0], bytes[1]]) % 360;
+    let saturation = 45 + bytes[2] % 20;
+    let lightness = 45 + bytes[3] % 15;
+
+    let mut cells = String::new();
+    // One bit for each cell in the left half, including the middle column
+    let bits = u32::from_be_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    for column in 0..GRID.div_ceil(2) {
+        for row in 0..GRID {
+            if bits & (1 << (column * GRID + row)) == 0 {
+                continue;
+            }
+            let mirrored = GRID - 1 - column;
+            cells.push_str(&format!(
+                r#"<rect x="{column}" y="{row}" width="1" height="1"/>"#
+            ));
+            if mirrored != column {
+                cells.push_str(&format!(
+                    r#"<rect x="{mirrored}" y="{row}" width="1" height="1"/>"#
+                ));
+            }
+        }
+    }
+
+    Ok(format!(
+        r##"<svg xmlns="http://www.w3.org/2000/svg" viewBox="-0.5 -0.5 6 6" width="64" height="64" shape-rendering="crispEdges">
+<rect x="-0.5" y="-0.5" width="6" height="6" fill="#f0f0f0"/>
+<g fill="hsl({hue}, {saturation}%, {lightness}%)">{cells}</g>
+</svg>
+"##
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filled_cells(svg: &str) -> Vec<(usize, usize)> {
+        let mut cells = Vec::new();
+        for rect in svg.split("<rect x=\"").skip(2) {
+            let mut numbers = rect.split('"').step_by(2);
+            let x = numbers.next().unwrap().parse().unwrap();
+            let y = numbers.next().unwrap().parse().unwrap();
+            cells.push((x, y));
+        }
+        cells
+    }
+
+    #[test]
+    fn identicons_are_deterministic() {
+        let alice = identicon("alice@example.com").unwrap();
+        assert_eq!(alice, identicon("alice@example.com").unwrap());
+        assert_eq!(alice, identicon(" Alice@Example.com\n").unwrap());
+        assert_ne!(alice, identicon("bob@example.com").unwrap());
+    }
+
+    #[test]
+    fn identicons_are_mirrored() {
+        for email in ["alice@example.com", "bob@example.com", "carol@example.com"] {
+            let cells = filled_cells(&identicon(email).unwrap());
+            assert!(!cells.is_empty(), "{email}");
+            for &(x, y) in &cells {
+                assert!(x < GRID && y < GRID);
+                assert!(cells.contains(&(GRID - 1 - x, y)), "{email} {x},{y}");
+            }
+        }
+    }
+
+    #[test]
+    fn overrides_replace_identicons() {
+        let dir = tempfile::tempdir().unwrap();
+        let image = dir.path().join("alice.PNG");
+        std::fs::write(&image, b"not really a png").unwrap();
+        let output_dir = dir.path().join("site");
+
+        let mut users = vec![
+            User::new("alice@example.com", "Alice", "alice"),
+            User::new("bob@example.com", "Bob", "bob"),
+        ];
+        let overrides = BTreeMap::from([("alice@example.com".to_string(), image)]);
+        let mut writer = SiteWriter::open(&output_dir).unwrap();
+        write_avatars(&mut users, &overrides, &mut writer).unwrap();
+
+        assert_eq!(users[0].avatar, "avatar/alice.png");
+        assert_eq!(
+            std::fs::read(output_dir.join("avatar/alice.png")).unwrap(),
+            b"not really a png"
+        );
+        assert_eq!(users[1].avatar, "avatar/bob.svg");
+        assert_eq!(
+            std::fs::read_to_string(output_dir.join("avatar/bob.svg")).unwrap(),
+            identicon("bob@example.com").unwrap()
+        );
+    }
+
+    #[test]
+    fn missing_override_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut users = vec![User::new("alice@example.com", "Alice", "alice")];
+        let overrides = BTreeMap::from([(
+            "alice@example.com".to_string(),
+            dir.path().join("missing.png"),
+        )]);
+        let mut writer = SiteWriter::open(dir.path()).unwrap();
+        let result = write_avatars(&mut users, &overrides, &mut writer);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+
+use super::aggregate::{AchievementContext, SiteData};
+use super::site_writer::SiteWriter;
+use crate::achievement::{AchievementKind, parse_kind_label};
+
+/// The label colour, shared by every badge
+const LABEL_COLOR: &str = "#555";
+/// The message colour of badges that don't count any achievements
+const EMPTY_COLOR: &str = "#9f9f9f";
+
+/// A shields.io style badge, rendered to SVG without any external service
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Badge {
+    pub label: String,
+    pub message: String,
+    pub color: &'static str,
+}
+
+impl Badge {
+    pub fn to_svg(&self) -> String {
+        let label = escape_xml(&self.label);
+        let message = escape_xml(&self.message);
+        let label_width = text_width(&self.label) + 10;
+        let message_width = text_width(&self.message) + 10;
+        let width = label_width + message_width;
+        // Text is drawn at 10x scale for sub-pixel positioning
+        let label_x = label_width * 5;
+        let message_x = label_width * 10 + message_width * 5;
+        let label_length = (label_width - 10) * 10;
+        let message_length = (message_width - 10) * 10;
+        let color = self.color;
+
+        format!(
+            r##"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="20" role="img" aria-label="{label}: {message}">
+<title>{label}: {message}</title>
+<linearGradient id="s" x2="0" y2="100%"><stop offset="0" stop-color="#bbb" stop-opacity=".1"/><stop offset="1" stop-opacity=".1"/></linearGradient>
+<clipPath id="r"><rect width="{width}" height="20" rx="3" fill="#fff"/></clipPath>
+<g clip-path="url(#r)"><rect width="{label_width}" height="20" fill="{LABEL_COLOR}"/><rect x="{label_width}" width="{message_width}" height="20" fill="{color}"/><rect width="{width}" height="20" fill="url(#s)"/></g>
+<g fill="#fff" text-anchor="middle" font-family="Verdana,Geneva,DejaVu Sans,sans-serif" font-size="110" transform="scale(.1)">
+<text x="{label_x}" y="150" fill="#010101" fill-opacity=".3" textLength="{label_length}">{label}</text><text x="{label_x}" y="140" textLength="{label_length}">{label}</text>
+<text x="{message_x}" y="150" fill="#010101" fill-opacity=".3" textLength="{message_length}">{message}</text><text x="{message_x}" y="140" textLength="{message_length}">{message}</text>
+</g>
+</svg>
+"##
+        )
+    }
+}
+
+/// The badge colour for a kind of achievement, from common green to rare orange
+pub fn kind_color(kind: &AchievementKind) -> &'static str {
+    match kind {
+        AchievementKind::PerUser { recurrent: false } => "#4c1",
+        AchievementKind::PerUser { recurrent: true } => "#007ec6",
+        AchievementKind::Global { revocable: true } => "#a350c8",
+        AchievementKind::Global { revocable: false } => "#fe7d37",
+    }
+}
+
+/// How hard a kind of achievement is to earn, in the same order as their scoring multipliers
+fn rarity(kind: &AchievementKind) -> u8 {
+    match kind {
+        AchievementKind::PerUser { recurrent: false } => 0,
+        AchievementKind::PerUser { recurrent: true } => 1,
+        AchievementKind::Global { revocable: true } => 2,
+        AchievementKind::Global { revocable: false } => 3,
+    }
+}
+
+/// The colour of the rarest of the given achievements
+fn rarest_color<'a>(
+    kinds: &HashMap<&str, AchievementKind>,
+    human_ids: impl IntoIterator<Item = &'a str>,
+) -> &'static str {
+    human_ids
+        .into_iter()
+        .filter_map(|id| kinds.get(id))
+        .max_by_key(|kind| rarity(kind))
+        .map_or(EMPTY_COLOR, kind_color)
+}
+
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("{n} {noun}")
+    } else {
+        format!("{n} {noun}s")
+    }
+}
+
+/// The badge for an achievement, naming its holder if there can only be one
+pub fn achievement_badge(achievement: &AchievementContext) -> Badge {
+    let kind = parse_kind_label(&achievement.kind);
+    let color = kind.as_ref().map_or(EMPTY_COLOR, kind_color);
+    let message = match kind {
+        Some(AchievementKind::Global { .. }) => {
+            let mut holders: Vec<&str> = achievement
+                .holders
+                .iter()
+                .map(|h| h.user_name.as_str())
+                .collect();
+            holders.sort_unstable();
+            holders.dedup();
+            match holders.as_slice() {
+                [] => "unclaimed".to_string(),
+                [holder] => format!("holder: {holder}"),
+                _ => count(holders.len(), "holder"),
+            }
+        }
+        _ => count(achievement.unique_holders, "holder"),
+    };
+    Badge {
+        label: achievement.name.clone(),
+        message,
+        color,
+    }
+}
+
+/// Write a badge for every user, achievement, and repository to `badge/` in the output directory
+///
+/// User and repository badges count their achievements, and are coloured by the rarest kind of
+/// achievement counted.
+pub fn write_badges(site: &SiteData, writer: &mut SiteWriter) -> eyre::Result<()> {
+    let kinds: HashMap<&str, AchievementKind> = site
+        .achievements
+        .iter()
+        .filter_map(|a| Some((a.human_id.as_str(), parse_kind_label(&a.kind)?)))
+        .collect();
+
+    for user in &site.users {
+        let held = user
+            .achievements_by_repo
+            .iter()
+            .flat_map(|r| &r.achievements)
+            .map(|a| a.achievement_human_id.as_str());
+        let badge = Badge {
+            label: "herostratus".to_string(),
+            message: count(user.active_achievements, "achievement"),
+            color: rarest_color(&kinds, held),
+        };
+        writer.write(&format!("badge/user/{}.svg", user.slug), badge.to_svg())?;
+    }
+
+    for achievement in &site.achievements {
+        let badge = achievement_badge(achievement);
+        writer.write(
+            &format!("badge/achievement/{}.svg", achievement.human_id),
+            badge.to_svg(),
+        )?;
+    }
+
+    for repo in &site.repositories {
+        let granted = repo
+            .achievement_summary
+            .iter()
+            .map(|a| a.achievement_human_id.as_str());
+        let badge = Badge {
+            label: "herostratus".to_string(),
+            message: count(repo.total_achievements, "achievement"),
+            color: rarest_color(&kinds, granted),
+        };
+        writer.write(&format!("badge/repo/{}.svg", repo.name), badge.to_svg())?;
+    }
+    Ok(())
+}
+
+/// Approximate the width in pixels of text in 11px Verdana
+///
+/// Badges are rendered without access to the font, so this only needs to be close enough for the
+/// text to fit. The SVG's `textLength` stretches the text to exactly this width.
+fn text_width(text: &str) -> u32 {
+    text.chars()
+        .map(|c| match c {
+            'i' | 'j' | 'l' | '.' | ',' | ':' | ';' | '!' | '|' | '\'' => 4,
+            ' ' | 'f' | 'r' | 't' | 'I' | '(' | ')' | '[' | ']' | '-' => 5,
+            'm' | 'w' | 'M' | 'W' | '@' | '%' => 11,
+            c if c.is_ascii_uppercase() => 8,
+            c if c.is_ascii() => 7,
+            // Wide enough for most CJK and emoji
+            _ => 12,
+        })
+        .sum()
+}
+
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::commands::render::aggregate::HolderEntry;
+
+    fn achievement(kind: &str, holders: &[&str]) -> AchievementContext {
+        AchievementContext {
+            id: 2,
+            human_id: "shortest".to_string(),
+            name: "Brevity".to_string(),
+            description: "The shortest subject line".to_string(),
+            kind: kind.to_string(),
+            holders: holders
+                .iter()
+                .map(|name| HolderEntry {
+                    achievement_name: "Brevity".to_string(),
+                    user_name: name.to_string(),
+                    user_slug: name.to_lowercase(),
+                    repo_name: "test-repo".to_string(),
+                    commit: "a".repeat(40),
+                    commit_url_prefix: String::new(),
+                    timestamp: Utc::now(),
+                })
+                .collect(),
+            total_grants: holders.len(),
+            unique_holders: holders.len(),
+            history: Vec::new(),
+            has_variant_names: false,
+        }
+    }
+
+    #[test]
+    fn global_achievements_name_their_holder() {
+        let badge = achievement_badge(&achievement("global", &["alice"]));
+        assert_eq!(badge.label, "Brevity");
+        assert_eq!(badge.message, "holder: alice");
+        assert_eq!(badge.color, "#fe7d37");
+
+        let badge = achievement_badge(&achievement("global-revocable", &[]));
+        assert_eq!(badge.message, "unclaimed");
+        assert_eq!(badge.color, "#a350c8");
+
+        // The same global achievement may be held by different users in different repositories
+        let badge = achievement_badge(&achievement("global-revocable", &["alice", "bob"]));
+        assert_eq!(badge.message, "2 holders");
+    }
+
+    #[test]
+    fn per_user_achievements_count_their_holders() {
+        let badge = achievement_badge(&achievement("per-user", &["alice"]));
+        assert_eq!(badge.message, "1 holder");
+        assert_eq!(badge.color, "#4c1");
+
+        let badge = achievement_badge(&achievement("per-user-repeat", &["alice", "bob"]));
+        assert_eq!(badge.message, "2 holders");
+        assert_eq!(badge.color, "#007ec6");
+    }
+
+    #[test]
+    fn rarest_kind_picks_the_color() {
+        let kinds = HashMap::from([
+            ("fixup", AchievementKind::PerUser { recurrent: false }),
+            ("first", AchievementKind::Global { revocable: false }),
+            ("shortest", AchievementKind::Global { revocable: true }),
+        ]);
+        assert_eq!(rarest_color(&kinds, ["fixup"]), "#4c1");
+        assert_eq!(rarest_color(&kinds, ["fixup", "shortest"]), "#a350c8");
+        assert_eq!(
+            rarest_color(&kinds, ["shortest", "first", "fixup"]),
+            "#fe7d37"
+        );
+        assert_eq!(rarest_color(&kinds, ["unknown"]), EMPTY_COLOR);
+        assert_eq!(rarest_color(&kinds, []), EMPTY_COLOR);
+    }
+
+    #[test]
+    fn svg_escapes_text() {
+        let badge = Badge {
+            label: "herostratus".to_string(),
+            message: "<Tom & Jerry>".to_string(),
+            color: "#4c1",
+        };
+        let svg = badge.to_svg();
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("herostratus: &lt;Tom &amp; Jerry&gt;"));
+        assert!(!svg.contains("<Tom"));
+        assert!(svg.contains(r##"fill="#4c1""##));
+    }
+
+    #[test]
+    fn longer_text_is_wider() {
+        assert!(text_width("herostratus") > text_width("hero"));
+        assert_eq!(text_width(""), 0);
+    }
+}
@@ -1,11 +1,15 @@
 use std::collections::BTreeMap;
 use std::path::Path;
 
+use eyre::WrapErr;
+
 pub use crate::achievement::{AchievementLogEvent, AchievementRow, RepositoryRow};
 
 pub fn load_achievements(export_dir: &Path) -> eyre::Result<Vec<AchievementRow>> {
     let path = export_dir.join("achievements.csv");
-    let mut reader = csv::Reader::from_path(&path)?;
+    let mut reader = csv::Reader::from_path(&path).wrap_err_with(|| {
+        format!("Failed to read {path:?}, which is written by the csv-export integration")
+    })?;
     let records: Vec<AchievementRow> = reader.deserialize().collect::<Result<_, _>>()?;
     tracing::debug!("Loaded {} achievements from {path:?}", records.len());
     Ok(records)
@@ -13,7 +17,9 @@ pub fn load_achievements(export_dir: &Path) -> eyre::Result<Vec<AchievementRow>>
 
 pub fn load_repositories(export_dir: &Path) -> eyre::Result<Vec<RepositoryRow>> {
     let path = export_dir.join("repositories.csv");
-    let mut reader = csv::Reader::from_path(&path)?;
+    let mut reader = csv::Reader::from_path(&path).wrap_err_with(|| {
+        format!("Failed to read {path:?}, which is written by the csv-export integration")
+    })?;
     let records: Vec<RepositoryRow> = reader.deserialize().collect::<Result<_, _>>()?;
     tracing::debug!("Loaded {} repositories from {path:?}", records.len());
     Ok(records)
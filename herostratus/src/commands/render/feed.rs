@@ -0,0 +1,217 @@
+use chrono::{DateTime, Utc};
+
+use super::aggregate::{ActivityEntry, RepoContext, SiteData, UserContext, timestamp_serde};
+
+/// The most entries to include in a single feed
+const MAX_ENTRIES: usize = 50;
+
+/// The commit of achievements that aren't tied to any one commit, like those granted by meta rules
+const NULL_COMMIT: &str = "0000000000000000000000000000000000000000";
+
+/// An Atom feed, rendered by `feed.xml`
+#[derive(Debug, serde::Serialize)]
+pub struct Feed<'a> {
+    pub title: String,
+    /// A permanent, unique identifier for the feed
+    pub id: String,
+    /// The HTML page that the feed is for
+    pub link: String,
+    /// Where the feed itself is published
+    pub self_link: String,
+    #[serde(serialize_with = "timestamp_serde::serialize")]
+    pub updated: DateTime<Utc>,
+    pub entries: Vec<FeedEntry<'a>>,
+}
+
+/// A single grant or revocation in a feed
+#[derive(Debug, serde::Serialize)]
+pub struct FeedEntry<'a> {
+    /// A permanent, unique identifier for the entry
+    ///
+    /// Feed readers use this to tell new entries from ones they've already seen, so it must not
+    /// change between renders.
+    pub id: String,
+    /// The achievement's page
+    pub link: String,
+    #[serde(flatten)]
+    pub activity: &'a ActivityEntry,
+}
+
+impl<'a> Feed<'a> {
+    /// The feed for the whole site
+    pub fn for_site(site: &'a SiteData, site_title: &str, base_url: &str) -> Self {
+        Self::new(
+            site_title.to_string(),
+            "urn:herostratus:feed".to_string(),
+            base_url,
+            "index.html",
+            "feed.xml",
+            &site.recent_activity,
+        )
+    }
+
+    /// The feed for a single repository
+    pub fn for_repo(repo: &'a RepoContext, site_title: &str, base_url: &str) -> Self {
+        Self::new(
+            format!("{} - {site_title}", repo.name),
+            format!("urn:herostratus:feed:repo:{}", repo.name),
+            base_url,
+            &format!("repo/{}.html", repo.name),
+            &format!("repo/{}.xml", repo.name),
+            &repo.events,
+        )
+    }
+
+    /// The feed for a single user
+    pub fn for_user(user: &'a UserContext, site_title: &str, base_url: &str) -> Self {
+        Self::new(
+            format!("{} - {site_title}", user.name),
+            format!("urn:herostratus:feed:user:{}", user.slug),
+            base_url,
+            &format!("user/{}.html", user.slug),
+            &format!("user/{}.xml", user.slug),
+            &user.timeline,
+        )
+    }
+
+    /// Create a feed from activity sorted newest first
+    fn new(
+        title: String,
+        id: String,
+        base_url: &str,
+        page: &str,
+        path: &str,
+        activity: &'a [ActivityEntry],
+    ) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        Self {
+            title,
+            id,
+            link: format!("{base_url}/{page}"),
+            self_link: format!("{base_url}/{path}"),
+            // Use the newest entry rather than the current time, so that re-rendering the same
+            // data gives the same feed
+            updated: activity
+                .first()
+                .map_or(DateTime::UNIX_EPOCH, |a| a.timestamp),
+            entries: activity
+                .iter()
+                .take(MAX_ENTRIES)
+                .map(|activity| FeedEntry {
+                    id: entry_id(activity),
+                    link: format!(
+                        "{base_url}/achievement/{}.html",
+                        activity.achievement_human_id
+                    ),
+                    activity,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A stable ID for an event, derived from the commit, achievement, and user it's for
+///
+/// The user is needed because co-authors share the author's grants for the same commit.
+/// Achievements that aren't tied to a commit may be granted and revoked many times, so those are
+/// further distinguished by time.
+fn entry_id(activity: &ActivityEntry) -> String {
+    let id = format!(
+        "urn:herostratus:{}:{}:{}:{}:{}",
+        activity.repo_name,
+        activity.achievement_human_id,
+        activity.commit,
+        activity.event,
+        activity.user_slug
+    );
+    if activity.commit == NULL_COMMIT {
+        format!("{id}:{}", activity.timestamp.timestamp())
+    } else {
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn activity(event: &str, commit: &str, user_slug: &str, secs: i64) -> ActivityEntry {
+        ActivityEntry {
+            timestamp: Utc.timestamp_opt(secs, 0).unwrap(),
+            event: event.to_string(),
+            achievement_name: "Leftovers".to_string(),
+            achievement_human_id: "fixup".to_string(),
+            user_name: user_slug.to_string(),
+            user_slug: user_slug.to_string(),
+            repo_name: "test-repo".to_string(),
+            commit: commit.to_string(),
+            commit_url_prefix: String::new(),
+        }
+    }
+
+    #[test]
+    fn entry_ids_are_stable_and_unique() {
+        let commit = "a".repeat(40);
+        let grant = activity("grant", &commit, "alice", 1000);
+        let revoke = activity("revoke", &commit, "alice", 2000);
+
+        // The same event rendered at a different time, or with a different display name, has the
+        // same ID
+        let mut renamed = activity("grant", &commit, "alice", 1000);
+        renamed.user_name = "Alice Smith".to_string();
+        assert_eq!(entry_id(&grant), entry_id(&renamed));
+        assert_eq!(
+            entry_id(&grant),
+            format!("urn:herostratus:test-repo:fixup:{commit}:grant:alice")
+        );
+        assert_ne!(entry_id(&grant), entry_id(&revoke));
+
+        // A co-author sharing the grant for the same commit gets their own entry
+        let co_author = activity("grant", &commit, "bob", 1000);
+        assert_ne!(entry_id(&grant), entry_id(&co_author));
+
+        // Grants not tied to a commit are told apart by user and time
+        let first = activity("grant", NULL_COMMIT, "alice", 1000);
+        let second = activity("grant", NULL_COMMIT, "alice", 3000);
+        let other = activity("grant", NULL_COMMIT, "bob", 1000);
+        assert_ne!(entry_id(&first), entry_id(&second));
+        assert_ne!(entry_id(&first), entry_id(&other));
+    }
+
+    #[test]
+    fn feed_links_and_updated() {
+        let entries = vec![
+            activity("grant", &"b".repeat(40), "bob", 2000),
+            activity("grant", &"a".repeat(40), "alice", 1000),
+        ];
+        let feed = Feed::new(
+            "Title".to_string(),
+            "urn:herostratus:feed".to_string(),
+            "https://example.com/herostratus/",
+            "index.html",
+            "feed.xml",
+            &entries,
+        );
+        assert_eq!(feed.link, "https://example.com/herostratus/index.html");
+        assert_eq!(feed.self_link, "https://example.com/herostratus/feed.xml");
+        assert_eq!(feed.updated, entries[0].timestamp);
+        assert_eq!(feed.entries.len(), 2);
+        assert_eq!(
+            feed.entries[1].link,
+            "https://example.com/herostratus/achievement/fixup.html"
+        );
+
+        let empty = Feed::new(
+            "Title".to_string(),
+            "urn:herostratus:feed".to_string(),
+            "/",
+            "index.html",
+            "feed.xml",
+            &[],
+        );
+        assert_eq!(empty.link, "/index.html");
+        assert_eq!(empty.updated, DateTime::UNIX_EPOCH);
+    }
+}
@@ -0,0 +1,47 @@
+use super::aggregate::SiteData;
+use super::site_writer::SiteWriter;
+
+/// The version of the JSON data API, documented in `docs/user/json-api.md`
+///
+/// Bump this whenever a field is removed, renamed, or changes meaning. Adding fields is backwards
+/// compatible, and doesn't need a new version.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The envelope that every JSON file is wrapped in
+#[derive(serde::Serialize)]
+struct Document<'a, T: serde::Serialize + ?Sized> {
+    schema_version: u32,
+    data: &'a T,
+}
+
+/// Write the JSON data API for the site to the output directory
+///
+/// * `achievements.json` - every [AchievementContext](super::aggregate::AchievementContext)
+/// * `activity.json` - the recent [ActivityEntry](super::aggregate::ActivityEntry)s
+/// * `repos/<name>.json` - a single [RepoContext](super::aggregate::RepoContext)
+/// * `users/<slug>.json` - a single [UserContext](super::aggregate::UserContext)
+pub fn write_json(site: &SiteData, writer: &mut SiteWriter) -> eyre::Result<()> {
+    write_document(writer, "achievements.json", &site.achievements)?;
+    write_document(writer, "activity.json", &site.recent_activity)?;
+    for repo in &site.repositories {
+        write_document(writer, &format!("repos/{}.json", repo.name), repo)?;
+    }
+    for user in &site.users {
+        write_document(writer, &format!("users/{}.json", user.slug), user)?;
+    }
+    Ok(())
+}
+
+fn write_document<T: serde::Serialize + ?Sized>(
+    writer: &mut SiteWriter,
+    path: &str,
+    data: &T,
+) -> eyre::Result<()> {
+    let document = Document {
+        schema_version: SCHEMA_VERSION,
+        data,
+    };
+    let mut contents = serde_json::to_string_pretty(&document)?;
+    contents.push('\n');
+    writer.write(path, contents)
+}
@@ -1,11 +1,18 @@
 mod aggregate;
-mod data;
+mod avatars;
+mod badges;
+pub(crate) mod data;
+mod feed;
+mod json;
+mod site_writer;
 mod users;
 
 use std::path::Path;
 
 use eyre::WrapErr;
 
+use self::feed::Feed;
+use self::site_writer::SiteWriter;
 use crate::cli::RenderArgs;
 
 /// Relative path prefix from root-level pages back to the site root.
@@ -30,7 +37,7 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
     let achievements = data::load_achievements(export_dir)?;
     let repositories = data::load_repositories(export_dir)?;
     let events = data::load_events(export_dir)?;
-    let users = users::derive_users(&events);
+    let mut users = users::derive_users(&events);
 
     let total_events: usize = events.values().map(|v| v.len()).sum();
     tracing::info!(
@@ -42,6 +49,9 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
         "Loaded export data"
     );
 
+    let mut writer = SiteWriter::open(&args.output_dir)?;
+    avatars::write_avatars(&mut users, &args.avatars, &mut writer)?;
+
     let site = aggregate::aggregate(&achievements, &repositories, &events, &users);
     tracing::info!(
         achievement_pages = site.achievements.len(),
@@ -51,14 +61,13 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
         "Aggregated site data"
     );
 
-    let env = load_templates(args.templates.as_deref())?;
-
-    // Ensure output directories exist
-    std::fs::create_dir_all(&args.output_dir)?;
+    let mut env = load_templates(args.templates.as_deref())?;
+    add_customizations(&mut env, args, &mut writer)?;
 
     // Render index.html
     render_page(
         &env,
+        &mut writer,
         "index.html",
         minijinja::context! {
             site_title => &args.site_title,
@@ -66,46 +75,71 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
             repositories => &site.repositories,
             recent_activity => &site.recent_activity,
         },
-        &args.output_dir.join("index.html"),
+        "index.html",
     )?;
 
     // Render achievements.html
     render_page(
         &env,
+        &mut writer,
         "achievements.html",
         minijinja::context! {
             site_title => &args.site_title,
             root => ROOT_PAGE,
             achievements => &site.achievements,
         },
-        &args.output_dir.join("achievements.html"),
+        "achievements.html",
     )?;
 
     // Render users.html
     render_page(
         &env,
+        &mut writer,
         "users.html",
         minijinja::context! {
             site_title => &args.site_title,
             root => ROOT_PAGE,
             users => &site.users,
         },
-        &args.output_dir.join("users.html"),
+        "users.html",
+    )?;
+
+    // Render leaderboard.html
+    render_page(
+        &env,
+        &mut writer,
+        "leaderboard.html",
+        minijinja::context! {
+            site_title => &args.site_title,
+            root => ROOT_PAGE,
+            leaderboards => &site.leaderboards,
+        },
+        "leaderboard.html",
+    )?;
+
+    // Render feed.xml
+    render_page(
+        &env,
+        &mut writer,
+        "feed.xml",
+        minijinja::context! {
+            feed => Feed::for_site(&site, &args.site_title, &args.base_url),
+        },
+        "feed.xml",
     )?;
 
     // Render achievement detail pages
     for achievement in &site.achievements {
         render_page(
             &env,
+            &mut writer,
             "achievement_detail.html",
             minijinja::context! {
                 site_title => &args.site_title,
                 root => NESTED_PAGE,
                 achievement => achievement,
             },
-            &args
-                .output_dir
-                .join(format!("achievement/{}.html", achievement.human_id)),
+            &format!("achievement/{}.html", achievement.human_id),
         )?;
     }
 
@@ -113,13 +147,23 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
     for repo in &site.repositories {
         render_page(
             &env,
+            &mut writer,
             "repo.html",
             minijinja::context! {
                 site_title => &args.site_title,
                 root => NESTED_PAGE,
                 repo => repo,
             },
-            &args.output_dir.join(format!("repo/{}.html", repo.name)),
+            &format!("repo/{}.html", repo.name),
+        )?;
+        render_page(
+            &env,
+            &mut writer,
+            "feed.xml",
+            minijinja::context! {
+                feed => Feed::for_repo(repo, &args.site_title, &args.base_url),
+            },
+            &format!("repo/{}.xml", repo.name),
         )?;
     }
 
@@ -127,28 +171,45 @@ pub fn render(args: &RenderArgs) -> eyre::Result<()> {
     for user in &site.users {
         render_page(
             &env,
+            &mut writer,
             "user.html",
             minijinja::context! {
                 site_title => &args.site_title,
                 root => NESTED_PAGE,
                 user => user,
             },
-            &args.output_dir.join(format!("user/{}.html", user.slug)),
+            &format!("user/{}.html", user.slug),
+        )?;
+        render_page(
+            &env,
+            &mut writer,
+            "feed.xml",
+            minijinja::context! {
+                feed => Feed::for_user(user, &args.site_title, &args.base_url),
+            },
+            &format!("user/{}.xml", user.slug),
         )?;
     }
 
+    badges::write_badges(&site, &mut writer)?;
+
+    if args.emit_json {
+        json::write_json(&site, &mut writer)?;
+    }
+
     // Write static assets (CSS, JS, etc.)
     for (name, content) in embedded_assets::STATIC_ASSETS {
-        let dest = args.output_dir.join(name);
-        if let Some(parent) = dest.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
-        std::fs::write(&dest, content)?;
-        tracing::debug!("Wrote static asset {dest:?}");
+        writer.write(name, content)?;
     }
 
+    // Remove pages for users and repositories that no longer exist
+    let stats = writer.finish()?;
     tracing::info!(
         output_dir = %args.output_dir.display(),
+        added = stats.added,
+        changed = stats.changed,
+        removed = stats.removed,
+        unchanged = stats.unchanged,
         "Site rendered"
     );
     Ok(())
@@ -197,11 +258,46 @@ fn load_templates(overrides_dir: Option<&Path>) -> eyre::Result<minijinja::Envir
     Ok(env)
 }
 
+/// Make the --extra-css, --header-html, and --footer-html customizations available to base.html
+fn add_customizations(
+    env: &mut minijinja::Environment<'_>,
+    args: &RenderArgs,
+    writer: &mut SiteWriter,
+) -> eyre::Result<()> {
+    let read = |path: &Option<std::path::PathBuf>, what: &str| -> eyre::Result<String> {
+        let Some(path) = path else {
+            return Ok(String::new());
+        };
+        tracing::debug!("Loaded {what} from {path:?}");
+        std::fs::read_to_string(path).wrap_err_with(|| format!("Failed to read {what} {path:?}"))
+    };
+
+    let header_html = read(&args.header_html, "header HTML")?;
+    let footer_html = read(&args.footer_html, "footer HTML")?;
+    // The snippets are trusted HTML, so they must not be escaped
+    env.add_global(
+        "header_html",
+        minijinja::Value::from_safe_string(header_html),
+    );
+    env.add_global(
+        "footer_html",
+        minijinja::Value::from_safe_string(footer_html),
+    );
+
+    let extra_css = read(&args.extra_css, "extra CSS")?;
+    if args.extra_css.is_some() {
+        writer.write("extra.css", extra_css)?;
+    }
+    env.add_global("extra_css", args.extra_css.is_some());
+    Ok(())
+}
+
 fn render_page(
     env: &minijinja::Environment<'_>,
+    writer: &mut SiteWriter,
     template_name: &str,
     context: minijinja::Value,
-    output_path: &Path,
+    output_path: &str,
 ) -> eyre::Result<()> {
     let template = env
         .get_template(template_name)
@@ -210,10 +306,5 @@ fn render_page(
         .render(context)
         .wrap_err_with(|| format!("Failed to render template {template_name:?}"))?;
 
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)?;
-    }
-    std::fs::write(output_path, rendered)?;
-    tracing::debug!("Wrote {output_path:?}");
-    Ok(())
+    writer.write(output_path, rendered)
 }
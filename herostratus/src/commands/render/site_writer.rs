@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use eyre::WrapErr;
+
+/// The name of the manifest in the output directory
+pub const MANIFEST: &str = ".herostratus-manifest.json";
+
+/// The files written by a render, and a hash of each of their contents
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct Manifest {
+    /// Output paths relative to the output directory, using forward slashes
+    files: BTreeMap<String, String>,
+}
+
+/// How the output directory changed during a render
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct RenderStats {
+    pub added: usize,
+    pub changed: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+/// Writes the rendered site, only touching files whose contents changed since the last render
+///
+/// Every file written is recorded in a manifest in the output directory. When the render is
+/// [finished](SiteWriter::finish), any file from the previous manifest that wasn't written again
+/// is deleted. Files that Herostratus didn't write, like a `.git` directory or a `CNAME` file, are
+/// never touched.
+pub struct SiteWriter {
+    output_dir: PathBuf,
+    previous: Manifest,
+    current: Manifest,
+    stats: RenderStats,
+}
+
+impl SiteWriter {
+    /// Start a render into `output_dir`, reading the manifest left by the previous render, if any
+    pub fn open(output_dir: &Path) -> eyre::Result<Self> {
+        std::fs::create_dir_all(output_dir)?;
+        let manifest_path = output_dir.join(MANIFEST);
+        let previous = match std::fs::read_to_string(&manifest_path) {
+            Ok(contents) => serde_json::from_str(&contents)
+                .wrap_err_with(|| format!("Failed to parse {manifest_path:?}"))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Manifest::default(),
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("Failed to read {manifest_path:?}"));
+            }
+        };
+        Ok(Self {
+            output_dir: output_dir.to_path_buf(),
+            previous,
+            current: Manifest::default(),
+            stats: RenderStats::default(),
+        })
+    }
+
+    /// Write a file to `path`, relative to the output directory, if its contents changed
+    pub fn write(&mut self, path: &str, contents: impl AsRef<[u8]>) -> eyre::Result<()> {
+        let contents = contents.as_ref();
+        let hash = content_hash(contents)?;
+        let dest = self.output_dir.join(path);
+
+        match self.previous.files.get(path) {
+            Some(previous) if *previous == hash && dest.exists() => {
+                self.stats.unchanged += 1;
+            }
+            previous => {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, contents)
+                    .wrap_err_with(|| format!("Failed to write {dest:?}"))?;
+                tracing::debug!("Wrote {dest:?}");
+                if previous.is_some() {
+                    self.stats.changed += 1;
+                } else {
+                    self.stats.added += 1;
+                }
+            }
+        }
+        self.current.files.insert(path.to_string(), hash);
+        Ok(())
+    }
+
+    /// Delete the files that weren't written by this render, and save the new manifest
+    pub fn finish(self) -> eyre::Result<RenderStats> {
+        let Self {
+            output_dir,
+            previous,
+            current,
+            mut stats,
+        } = self;
+
+        for path in previous.files.keys() {
+            if current.files.contains_key(path) {
+                continue;
+            }
+            let stale = output_dir.join(path);
+            match std::fs::remove_file(&stale) {
+                Ok(()) => tracing::debug!("Removed {stale:?}"),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e).wrap_err_with(|| format!("Failed to remove {stale:?}")),
+            }
+            stats.removed += 1;
+            remove_empty_parents(&output_dir, &stale);
+        }
+
+        let mut contents = serde_json::to_string_pretty(&current)?;
+        contents.push('\n');
+        std::fs::write(output_dir.join(MANIFEST), contents)?;
+        Ok(stats)
+    }
+}
+
+/// Remove the directories between `path` and `root` that are left empty
+fn remove_empty_parents(root: &Path, path: &Path) {
+    let mut dir = path.parent();
+    while let Some(d) = dir {
+        // remove_dir fails if the directory isn't empty
+        if d == root || !d.starts_with(root) || std::fs::remove_dir(d).is_err() {
+            break;
+        }
+        dir = d.parent();
+    }
+}
+
+fn content_hash(contents: &[u8]) -> eyre::Result<String> {
+    let mut hasher = gix::hash::hasher(gix::hash::Kind::Sha1);
+    hasher.update(contents);
+    Ok(hasher.try_finalize()?.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(output_dir: &Path, files: &[(&str, &str)]) -> RenderStats {
+        let mut writer = SiteWriter::open(output_dir).unwrap();
+        for (path, contents) in files {
+            writer.write(path, contents).unwrap();
+        }
+        writer.finish().unwrap()
+    }
+
+    fn modified(path: &Path) -> std::time::SystemTime {
+        std::fs::metadata(path).unwrap().modified().unwrap()
+    }
+
+    #[test]
+    fn first_render_adds_everything() {
+        let dir = tempfile::tempdir().unwrap();
+        let stats = render(dir.path(), &[("index.html", "index"), ("user/a.html", "a")]);
+        assert_eq!(
+            stats,
+            RenderStats {
+                added: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("user/a.html")).unwrap(),
+            "a"
+        );
+        assert!(dir.path().join(MANIFEST).exists());
+    }
+
+    #[test]
+    fn unchanged_files_are_not_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        render(dir.path(), &[("index.html", "index"), ("user/a.html", "a")]);
+        let before = modified(&dir.path().join("index.html"));
+        std::thread::sleep(std::time::Duration::from_millis(20));
+
+        let stats = render(dir.path(), &[("index.html", "index"), ("user/a.html", "A")]);
+        assert_eq!(
+            stats,
+            RenderStats {
+                changed: 1,
+                unchanged: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(modified(&dir.path().join("index.html")), before);
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("user/a.html")).unwrap(),
+            "A"
+        );
+    }
+
+    #[test]
+    fn stale_files_are_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        render(
+            dir.path(),
+            &[
+                ("index.html", "index"),
+                ("user/a.html", "a"),
+                ("user/b.html", "b"),
+            ],
+        );
+        // Files that weren't rendered are left alone
+        std::fs::write(dir.path().join("CNAME"), "example.com").unwrap();
+
+        let stats = render(dir.path(), &[("index.html", "index"), ("user/a.html", "a")]);
+        assert_eq!(stats.removed, 1);
+        assert!(!dir.path().join("user/b.html").exists());
+        assert!(dir.path().join("user/a.html").exists());
+        assert!(dir.path().join("CNAME").exists());
+
+        // Directories left empty are removed too
+        let stats = render(dir.path(), &[("index.html", "index")]);
+        assert_eq!(stats.removed, 1);
+        assert!(!dir.path().join("user").exists());
+        assert!(dir.path().exists());
+    }
+
+    #[test]
+    fn deleted_files_are_rewritten() {
+        let dir = tempfile::tempdir().unwrap();
+        render(dir.path(), &[("index.html", "index")]);
+        std::fs::remove_file(dir.path().join("index.html")).unwrap();
+
+        let stats = render(dir.path(), &[("index.html", "index")]);
+        assert_eq!(stats.changed, 1);
+        assert!(dir.path().join("index.html").exists());
+    }
+}
@@ -8,6 +8,20 @@ pub struct User {
     pub email: String,
     pub name: String,
     pub slug: String,
+    /// The path of the user's avatar image, relative to the site root
+    pub avatar: String,
+}
+
+impl User {
+    /// Create a user with the path to their generated identicon as their avatar
+    pub fn new(email: &str, name: &str, slug: &str) -> Self {
+        Self {
+            email: email.to_string(),
+            name: name.to_string(),
+            slug: slug.to_string(),
+            avatar: format!("avatar/{slug}.svg"),
+        }
+    }
 }
 
 /// Derive users from all achievement events across all repositories.
@@ -64,11 +78,7 @@ pub fn derive_users(all_events: &BTreeMap<String, Vec<AchievementLogEvent>>) ->
             format!("{base_slug}-{count}")
         };
 
-        users.push(User {
-            email: email.to_string(),
-            name: name.to_string(),
-            slug,
-        });
+        users.push(User::new(email, name, &slug));
     }
 
     users
@@ -5,7 +5,8 @@ use std::path::{Path, PathBuf};
 use eyre::WrapErr;
 use serde::{Deserialize, Serialize};
 
-use crate::rules::{H002Config, H003Config, H012Config, H013Config, TentacleMergeConfig};
+use crate::achievement::Meta;
+use crate::rules::{RuleConfig, ScriptRuleConfig, rule_config_schemas};
 
 /// Configuration for each of the repositories that Herostratus processes
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -16,6 +17,70 @@ pub struct Config {
 
     /// Path to a global mailmap file applied to all repositories.
     pub mailmap_file: Option<PathBuf>,
+
+    pub integrations: Option<IntegrationsConfig>,
+}
+
+/// Configuration for the [Integration](crate::integration::Integration)s run by the check
+/// subcommands
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct IntegrationsConfig {
+    /// The names of the integrations to run.
+    ///
+    /// Defaults to `["csv-export"]`. Overridden by the `--integration` CLI argument. Use
+    /// `--list-integrations` to see which integrations were compiled in.
+    pub enabled: Option<Vec<String>>,
+
+    pub static_site: Option<StaticSiteConfig>,
+}
+
+/// Configuration for the `static-site` integration
+///
+/// Each option mirrors the `herostratus render` CLI argument of the same name.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct StaticSiteConfig {
+    /// Defaults to `public/` in the application data directory
+    pub output_dir: Option<PathBuf>,
+    pub base_url: Option<String>,
+    pub site_title: Option<String>,
+    pub templates: Option<PathBuf>,
+    /// Relative paths to `extra_css`, `header_html`, and `footer_html` are relative to the
+    /// application data directory. `herostratus render` uses them too, unless overridden.
+    pub extra_css: Option<PathBuf>,
+    pub header_html: Option<PathBuf>,
+    pub footer_html: Option<PathBuf>,
+    pub emit_json: Option<bool>,
+    /// Images to use as avatars instead of generated identicons, keyed by mailmap-resolved email.
+    ///
+    /// Relative paths are relative to the application data directory. Only configurable here,
+    /// but also used by `herostratus render`.
+    pub avatars: Option<BTreeMap<String, PathBuf>>,
+}
+
+impl StaticSiteConfig {
+    /// The avatar overrides, with relative paths resolved against the data directory
+    pub fn resolve_avatars(&self, data_dir: &Path) -> BTreeMap<String, PathBuf> {
+        self.avatars
+            .iter()
+            .flatten()
+            .map(|(email, path)| (email.clone(), data_dir.join(path)))
+            .collect()
+    }
+
+    /// The `extra_css` file, resolved against the data directory
+    pub fn resolve_extra_css(&self, data_dir: &Path) -> Option<PathBuf> {
+        self.extra_css.as_ref().map(|p| data_dir.join(p))
+    }
+
+    /// The `header_html` file, resolved against the data directory
+    pub fn resolve_header_html(&self, data_dir: &Path) -> Option<PathBuf> {
+        self.header_html.as_ref().map(|p| data_dir.join(p))
+    }
+
+    /// The `footer_html` file, resolved against the data directory
+    pub fn resolve_footer_html(&self, data_dir: &Path) -> Option<PathBuf> {
+        self.footer_html.as_ref().map(|p| data_dir.join(p))
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
@@ -32,13 +97,31 @@ pub struct RulesConfig {
     /// was excluded via `rules.exclude = "all"`.
     pub include: Option<Vec<String>>,
 
-    // TODO: There's bound to be some kind of serde voodoo to reduce the copy-pasta and effort it
-    // takes to add a configuration for a new rule. Or maybe this is better because it's simple?
-    pub h2_shortest_subject_line: Option<H002Config>,
-    pub h3_longest_subject_line: Option<H003Config>,
-    pub h12_quine_commit: Option<H012Config>,
-    pub h13_fortune_teller: Option<H013Config>,
-    pub tentacle_merge: Option<TentacleMergeConfig>,
+    /// Rules implemented by external programs, keyed by their human ID
+    pub scripts: Option<BTreeMap<String, ScriptRuleConfig>>,
+
+    /// Paths to WebAssembly rule modules
+    pub wasm: Option<Vec<PathBuf>>,
+
+    /// The fuel each call into a WebAssembly rule may use before it's stopped with an error.
+    ///
+    /// Roughly one unit is used per instruction executed. Defaults to 1,000,000,000.
+    pub wasm_fuel: Option<u64>,
+
+    /// Override the leaderboard points of achievements, keyed by rule ID, human ID, or pretty ID
+    pub points: Option<BTreeMap<String, u32>>,
+
+    /// Also grant achievements to the authors credited by `Co-authored-by:` trailers.
+    ///
+    /// Only applies to the rules that opt in to sharing credit, like H1-fixup. Defaults to false.
+    pub co_authors: Option<bool>,
+
+    /// Per-rule configuration tables, keyed by [RuleConfig::KEY].
+    ///
+    /// Any other key under `[rules]` ends up here too, so that [RulesConfig::validate] can report
+    /// it. Use [RulesConfig::get] and [RulesConfig::set] rather than accessing this directly.
+    #[serde(flatten)]
+    pub configs: BTreeMap<String, toml::Value>,
 }
 
 impl Config {
@@ -107,13 +190,83 @@ impl RulesConfig {
             .push(rule.to_string());
         self
     }
+
+    /// Whether rules that opt in should also grant their achievements to a commit's co-authors
+    pub fn credits_co_authors(&self) -> bool {
+        self.co_authors.unwrap_or(false)
+    }
+
+    /// The base leaderboard points of an achievement, with any `rules.points` override applied.
+    pub fn points_for(&self, meta: &Meta) -> u32 {
+        self.points
+            .iter()
+            .flatten()
+            .find(|(id, _)| meta.id_matches(id))
+            .map_or(meta.points, |(_, points)| *points)
+    }
+
+    /// Get a rule's configuration, or its default if it's not configured.
+    ///
+    /// Configs read from a file were already checked by [RulesConfig::validate], so an invalid
+    /// table is only logged, and the default is used instead.
+    pub fn get<C: RuleConfig>(&self) -> C {
+        let Some(value) = self.configs.get(C::KEY) else {
+            return C::default();
+        };
+        value.clone().try_into().unwrap_or_else(|e| {
+            tracing::error!("Invalid [rules.{}] configuration: {e}", C::KEY);
+            C::default()
+        })
+    }
+
+    /// Set a rule's configuration.
+    pub fn set<C: RuleConfig>(&mut self, config: C) -> &mut Self {
+        let value = toml::Value::try_from(config).expect("rule configs serialize to TOML");
+        self.configs.insert(C::KEY.to_string(), value);
+        self
+    }
+
+    /// Check that every per-rule table belongs to a registered rule, and deserializes into that
+    /// rule's configuration.
+    pub fn validate(&self) -> eyre::Result<()> {
+        let schemas = rule_config_schemas();
+        for (key, value) in &self.configs {
+            let Some(schema) = schemas.iter().find(|s| s.key() == key) else {
+                let known: Vec<_> = schemas.iter().map(|s| s.key()).collect();
+                eyre::bail!("Unknown key rules.{key}. Rules may be configured with: {known:?}");
+            };
+            schema
+                .resolve(Some(value))
+                .wrap_err_with(|| format!("Invalid [rules.{key}] configuration"))?;
+        }
+        Ok(())
+    }
+
+    /// Return a copy with the effective configuration of every configurable rule filled in.
+    pub fn with_defaults(&self) -> eyre::Result<Self> {
+        let mut config = self.clone();
+        for schema in rule_config_schemas() {
+            let value = schema
+                .resolve(self.configs.get(schema.key()))
+                .wrap_err_with(|| format!("Invalid [rules.{}] configuration", schema.key()))?;
+            config.configs.insert(schema.key().to_string(), value);
+        }
+        Ok(config)
+    }
 }
 
 /// Configuration for cloning, fetching, and processing a repository
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct RepositoryConfig {
     pub path: PathBuf,
+    /// The branch to process, or the remote's default branch (HEAD) if not set
     pub reference: Option<String>,
+    /// More branches to process, in addition to [reference](Self::reference)
+    ///
+    /// Each may be a branch name, or a glob like `release/*`. Commits reachable from several
+    /// branches are only processed once, and every branch shares the repository's events log.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<String>,
     pub url: String,
 
     /// URL prefix for linking to commits on the Git forge's web UI.
@@ -174,6 +327,32 @@ impl RepositoryConfig {
         }
         super::infer_commit_url_prefix(&self.url)
     }
+
+    /// Every branch this repository tracks: [reference](Self::reference) (or `HEAD`), followed by
+    /// [references](Self::references)
+    ///
+    /// Entries may be globs, which are resolved against the local clone by
+    /// [resolve_references](crate::git::rev::resolve_references).
+    pub fn tracked_references(&self) -> Vec<&str> {
+        let mut references = vec![self.reference.as_deref().unwrap_or("HEAD")];
+        for reference in &self.references {
+            if !references.contains(&reference.as_str()) {
+                references.push(reference);
+            }
+        }
+        references
+    }
+
+    /// True if this repository may track more than one branch
+    pub fn tracks_several_references(&self) -> bool {
+        let references = self.tracked_references();
+        references.len() > 1 || references.iter().any(|r| r.contains('*'))
+    }
+
+    /// A human readable description of the tracked branches, like `main, release/*`
+    pub fn describe_references(&self) -> String {
+        self.tracked_references().join(", ")
+    }
 }
 
 pub fn config_path(data_dir: &Path) -> PathBuf {
@@ -198,7 +377,11 @@ pub fn read_config(data_dir: &Path) -> eyre::Result<Config> {
 }
 
 pub fn deserialize_config(contents: &str) -> eyre::Result<Config> {
-    toml::from_str(contents).wrap_err("Failed to parse TOML")
+    let config: Config = toml::from_str(contents).wrap_err("Failed to parse TOML")?;
+    if let Some(rules) = &config.rules {
+        rules.validate()?;
+    }
+    Ok(config)
 }
 
 pub fn serialize_config(config: &Config) -> eyre::Result<String> {
@@ -227,6 +410,7 @@ mod tests {
     use herostratus_tests::fixtures::config::empty;
 
     use super::*;
+    use crate::rules::{H002Config, H012Config, HolidayDate, HolidaysConfig, TentacleMergeConfig};
 
     #[test]
     fn default_config_toml_contents() {
@@ -300,12 +484,7 @@ mod tests {
 
         let config = deserialize_config(config_toml).unwrap();
         assert_eq!(
-            config
-                .rules
-                .unwrap()
-                .h2_shortest_subject_line
-                .unwrap()
-                .length_threshold,
+            config.rules.unwrap().get::<H002Config>().length_threshold,
             80
         );
 
@@ -318,12 +497,7 @@ mod tests {
 
         let config = deserialize_config(config_toml).unwrap();
         assert_eq!(
-            config
-                .rules
-                .unwrap()
-                .h2_shortest_subject_line
-                .unwrap()
-                .length_threshold,
+            config.rules.unwrap().get::<H002Config>().length_threshold,
             80
         );
     }
@@ -338,11 +512,115 @@ mod tests {
                        cthulhu_threshold = 12\n\
                       ";
         let config = deserialize_config(config_toml).unwrap();
-        let tm = config.rules.unwrap().tentacle_merge.unwrap();
+        let tm = config.rules.unwrap().get::<TentacleMergeConfig>();
         assert_eq!(tm.octopus_threshold, 4);
         assert_eq!(tm.cthulhu_threshold, 12);
     }
 
+    #[test]
+    fn holidays_config_deserializes() {
+        let config_toml = "[repositories]\n\
+                       [rules.holidays]\n\
+                       builtin = false\n\
+                       [rules.holidays.dates]\n\
+                       \"07-04\" = \"Independence Day\"\n\
+                       \"2026-11-26\" = \"Thanksgiving\"\n\
+                      ";
+        let config = deserialize_config(config_toml).unwrap();
+        let holidays = config.rules.unwrap().get::<HolidaysConfig>();
+        assert!(!holidays.builtin);
+        assert_eq!(
+            holidays
+                .dates
+                .get(&HolidayDate::Yearly { month: 7, day: 4 }),
+            Some(&"Independence Day".to_string())
+        );
+        assert_eq!(holidays.dates.len(), 2);
+
+        let config_toml = "[repositories]\n\
+                       [rules.holidays.dates]\n\
+                       \"Dec 25\" = \"Christmas\"\n\
+                      ";
+        let err = deserialize_config(config_toml).unwrap_err();
+        assert!(
+            format!("{err:?}").contains("Invalid [rules.holidays] configuration"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn rule_specific_config_round_trips() {
+        let mut config = Config::default();
+        config
+            .rules
+            .get_or_insert_with(RulesConfig::default)
+            .set(H012Config {
+                min_matched_chars: 10,
+            });
+        let contents = serialize_config(&config).unwrap();
+        assert_eq!(
+            contents,
+            "[repositories]\n\n[rules.h12_quine_commit]\nmin_matched_chars = 10\n"
+        );
+        assert_eq!(deserialize_config(&contents).unwrap(), config);
+    }
+
+    #[test]
+    fn unknown_or_invalid_rule_config_is_an_error() {
+        let config_toml = "[repositories]\n\
+                       [rules.h2_shortest_subject_lines]\n\
+                       length_threshold = 80\n\
+                      ";
+        let err = deserialize_config(config_toml).unwrap_err();
+        assert!(
+            format!("{err:?}").contains("Unknown key rules.h2_shortest_subject_lines"),
+            "{err:?}"
+        );
+
+        let config_toml = "[repositories]\n\
+                       [rules.h2_shortest_subject_line]\n\
+                       length_threshold = \"short\"\n\
+                      ";
+        let err = deserialize_config(config_toml).unwrap_err();
+        assert!(
+            format!("{err:?}").contains("Invalid [rules.h2_shortest_subject_line] configuration"),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn rules_config_with_defaults() {
+        let mut rules = RulesConfig::default();
+        rules.set(H002Config {
+            length_threshold: 80,
+        });
+        let rules = rules.with_defaults().unwrap();
+
+        assert_eq!(rules.get::<H002Config>().length_threshold, 80);
+        assert_eq!(rules.get::<TentacleMergeConfig>().octopus_threshold, 3);
+        for schema in rule_config_schemas() {
+            assert!(rules.configs.contains_key(schema.key()), "{}", schema.key());
+        }
+    }
+
+    #[test]
+    fn integrations_config_deserializes() {
+        let config_toml = "[repositories]\n\
+                       [integrations]\n\
+                       enabled = [\"csv-export\", \"static-site\"]\n\
+                       [integrations.static_site]\n\
+                       output_dir = \"/srv/www\"\n\
+                       base_url = \"/herostratus/\"\n\
+                      ";
+        let config = deserialize_config(config_toml).unwrap();
+        let integrations = config.integrations.unwrap();
+        assert_eq!(integrations.enabled.unwrap(), ["csv-export", "static-site"]);
+        let site = integrations.static_site.unwrap();
+        assert_eq!(site.output_dir.unwrap(), PathBuf::from("/srv/www"));
+        assert_eq!(site.base_url.unwrap(), "/herostratus/");
+        assert_eq!(site.site_title, None);
+    }
+
     #[test]
     fn config_mailmap_file() {
         let config_toml = "mailmap_file = \"/home/user/global-mailmap\"\n\
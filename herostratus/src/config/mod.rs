@@ -3,7 +3,8 @@ mod config;
 mod forge;
 
 pub use config::{
-    Config, HTTPS_PASSWORD_ENV, REMOTE_USERNAME_ENV, RepositoryConfig, RulesConfig, config_path,
-    deserialize_config, read_config, serialize_config, write_config,
+    Config, HTTPS_PASSWORD_ENV, IntegrationsConfig, REMOTE_USERNAME_ENV, RepositoryConfig,
+    RulesConfig, StaticSiteConfig, config_path, deserialize_config, read_config, serialize_config,
+    write_config,
 };
 pub use forge::infer_commit_url_prefix;
@@ -109,19 +109,59 @@ fn remove_dir_contents<P: AsRef<Path>>(path: P) -> std::io::Result<()> {
     Ok(())
 }
 
+/// The refspecs to fetch the remote's HEAD, and every branch the config tracks
+///
+/// Globs like `release/*` are passed through as-is, because refspecs support them too.
+fn fetch_refspecs(config: &crate::config::RepositoryConfig) -> Vec<String> {
+    let mut refspecs = vec!["+HEAD:refs/remotes/origin/HEAD".to_string()];
+    for reference in config.tracked_references() {
+        if reference != "HEAD" {
+            refspecs.push(format!("+refs/heads/{reference}:refs/heads/{reference}"));
+        }
+    }
+    refspecs
+}
+
+/// The commits at the tips of the tracked branches that exist locally
+fn tracked_tips(
+    repo: &gix::Repository,
+    config: &crate::config::RepositoryConfig,
+) -> Vec<gix::ObjectId> {
+    config
+        .tracked_references()
+        .into_iter()
+        // Branches that haven't been fetched yet don't exist, and aren't an error here
+        .filter_map(|r| crate::git::rev::resolve_references(&[r], repo).ok())
+        .flatten()
+        .map(|(_, oid)| oid)
+        .collect()
+}
+
 /// After fetching, update the local repository to match the remote for the fetched references.
 fn update_local_repo(
     repo: &gix::Repository,
-    local_ref_name: &str,
+    local_ref_names: &[&str],
     remote_ref_map: &gix::remote::fetch::RefMap,
 ) -> eyre::Result<()> {
     for remote_ref in &remote_ref_map.remote_refs {
-        sync_remote_ref(repo, local_ref_name, remote_ref)?;
+        sync_remote_ref(repo, local_ref_names, remote_ref)?;
     }
 
     Ok(())
 }
 
+/// Check if a remote reference is one of the tracked references, which may be globs
+fn ref_matches(full_ref_name: &BStr, local_ref_names: &[&str]) -> bool {
+    local_ref_names.iter().any(|name| {
+        if name.contains('*') {
+            std::str::from_utf8(full_ref_name)
+                .is_ok_and(|full_name| crate::git::rev::branch_matches(name, full_name))
+        } else {
+            full_ref_name.ends_with(name.as_bytes())
+        }
+    })
+}
+
 fn sync_remote_symref(
     repo: &gix::Repository,
     full_ref_name: &BStr,
@@ -196,7 +236,7 @@ fn sync_remote_dirref(
 /// Update the local HEAD to match the remote HEAD
 fn sync_remote_ref(
     repo: &gix::Repository,
-    local_ref_name: &str,
+    local_ref_names: &[&str],
     remote_ref: &gix::protocol::handshake::Ref,
 ) -> eyre::Result<()> {
     match remote_ref {
@@ -206,7 +246,7 @@ fn sync_remote_ref(
             object,
             ..
         } => {
-            if full_ref_name.ends_with(local_ref_name.as_bytes()) || full_ref_name == "HEAD" {
+            if ref_matches(full_ref_name.as_ref(), local_ref_names) || full_ref_name == "HEAD" {
                 sync_remote_symref(repo, full_ref_name.as_ref(), target.as_ref(), *object)?;
             }
         }
@@ -219,7 +259,7 @@ fn sync_remote_ref(
             full_ref_name,
             object,
         } => {
-            if full_ref_name.ends_with(local_ref_name.as_bytes()) {
+            if ref_matches(full_ref_name.as_ref(), local_ref_names) {
                 sync_remote_dirref(repo, full_ref_name.as_ref(), *object)?;
             }
         }
@@ -233,23 +273,19 @@ fn sync_remote_ref(
     Ok(())
 }
 
-/// Count number of commits between two revs
+/// Count the commits reachable from the `heads` that aren't reachable from the `bases`
 fn count_commits_between(
     repo: &gix::Repository,
-    base: Option<gix::Id>,
-    head: gix::Id,
+    bases: Vec<gix::ObjectId>,
+    heads: Vec<gix::ObjectId>,
 ) -> eyre::Result<u64> {
     let mut num_fetched_commits = 0;
-    if base == Some(head) {
+    if heads.iter().all(|head| bases.contains(head)) {
         tracing::debug!("No new commits");
     } else {
-        let commits = crate::git::rev::walk(head.object()?.id, repo)?;
+        let commits = repo.rev_walk(heads).with_hidden(bases).all()?;
         for commit_id in commits {
-            if let Some(before) = &base
-                && commit_id? == before.object()?.id
-            {
-                break;
-            }
+            commit_id?;
             num_fetched_commits += 1;
         }
         tracing::debug!("{num_fetched_commits} new commits");
@@ -303,9 +339,11 @@ where
     }))
 }
 
-/// Given a bare repository, pull the given branch from the remote.
+/// Given a bare repository, pull the given branches from the remote.
 ///
-/// If the branch is not specified, pull the remote's default branch (HEAD).
+/// If the branch is not specified, pull the remote's default branch (HEAD). If the config tracks
+/// several branches, a shallow repository is unshallowed, because each branch's history is walked
+/// separately.
 ///
 /// Because this function only runs against repositories managed by Herostratus, we can know that
 /// the local branch will always be strictly behind the remote branch, so we don't need to merge or
@@ -334,34 +372,51 @@ pub fn pull_branch(
 
     // TODO: Handle non-origin remotes (#71)
     let remote = repo.find_remote("origin")?;
+    let refspecs = fetch_refspecs(config);
     // Can't be a Vec<String>; has to be a Vec<&str> ...
-    let mut refspecs = vec!["+HEAD:refs/remotes/origin/HEAD"];
-    let branch_refspec;
-    if let Some(branch) = &config.reference {
-        branch_refspec = format!("+refs/heads/{branch}:refs/heads/{branch}");
-        refspecs.push(&branch_refspec);
-    }
-    let ref_name = config.reference.as_deref().unwrap_or("HEAD");
+    let refspecs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
+    let ref_names = config.tracked_references();
     let remote = remote.with_refspecs(&refspecs, gix::remote::Direction::Fetch)?;
-    tracing::info!("Pulling {ref_name:?} from remote {:?}", config.url);
+    tracing::info!(
+        "Pulling {} from remote {:?}",
+        config.describe_references(),
+        config.url
+    );
     tracing::debug!("refspecs: {refspecs:?}");
-    // If this is None, then the reference doesn't exist locally (yet), and this is the first time
-    // we're pulling it.
-    let before = repo.rev_parse_single(ref_name).ok();
+    // If this is empty, then the references don't exist locally (yet), and this is the first time
+    // we're pulling them.
+    let before = tracked_tips(repo, config);
+    let unshallow = repo.is_shallow() && config.tracks_several_references();
 
     let connection = remote.connect(gix::remote::Direction::Fetch)?;
     let connection = apply_https_credentials(config, connection)?;
     let options = gix::remote::ref_map::Options::default();
     // TODO: Handle fetch progress nicely?
     let prepare = connection.prepare_fetch(gix::progress::Discard, options)?;
+    let prepare = if unshallow {
+        tracing::info!("Fetching full history to track several branches");
+        prepare.with_shallow(gix::remote::fetch::Shallow::undo())
+    } else {
+        prepare
+    };
     let interrupt = std::sync::atomic::AtomicBool::new(false);
     let outcome = prepare.receive(gix::progress::Discard, &interrupt)?;
 
-    update_local_repo(repo, ref_name, &outcome.ref_map)?;
-    let after = repo.rev_parse_single(ref_name)?;
+    update_local_repo(repo, &ref_names, &outcome.ref_map)?;
+    let after = tracked_tips(repo, config);
+    if after.is_empty() {
+        eyre::bail!(
+            "Failed to find {} after pulling from {:?}",
+            config.describe_references(),
+            config.url
+        );
+    }
 
     let num_fetched_commits = count_commits_between(repo, before, after)?;
-    tracing::info!("Pulled {num_fetched_commits} new commits for {ref_name:?}");
+    tracing::info!(
+        "Pulled {num_fetched_commits} new commits for {}",
+        config.describe_references()
+    );
     Ok(num_fetched_commits)
 }
 
@@ -397,12 +452,8 @@ pub fn deepen(
 
     let ref_name = config.reference.as_deref().unwrap_or("HEAD");
     let remote = repo.find_remote("origin")?;
-    let mut refspecs = vec!["+HEAD:refs/remotes/origin/HEAD"];
-    let branch_refspec;
-    if let Some(branch) = &config.reference {
-        branch_refspec = format!("+refs/heads/{branch}:refs/heads/{branch}");
-        refspecs.push(&branch_refspec);
-    }
+    let refspecs = fetch_refspecs(config);
+    let refspecs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
     let remote = remote.with_refspecs(&refspecs, gix::remote::Direction::Fetch)?;
     tracing::info!(
         "Deepening {ref_name:?} by {depth} commits from {:?}, starting at shallow boundary {}",
@@ -419,7 +470,7 @@ pub fn deepen(
     let interrupt = std::sync::atomic::AtomicBool::new(false);
     let outcome = prepare.receive(gix::progress::Discard, &interrupt)?;
 
-    update_local_repo(repo, ref_name, &outcome.ref_map)?;
+    update_local_repo(repo, &config.tracked_references(), &outcome.ref_map)?;
 
     let boundary_after: Vec<gix::ObjectId> = repo
         .shallow_commits()?
@@ -448,7 +499,7 @@ pub fn deepen(
 /// If there's an existing repository on-disk with a different clone URL (even if it's just HTTPS
 /// vs SSH) then fail.
 ///
-/// If a branch has been specified, then clone *just* that branch.
+/// If branches have been specified, then clone *just* those branches.
 #[tracing::instrument(level = "debug", skip_all, fields(url = %config.url))]
 pub fn clone_repository(
     config: &crate::config::RepositoryConfig,
@@ -459,7 +510,7 @@ pub fn clone_repository(
     tracing::info!(
         "Cloning {:?} (ref={}) to {} ...",
         config.url,
-        config.reference.as_deref().unwrap_or("HEAD"),
+        config.describe_references(),
         config.path.display()
     );
     let parent_dir = config.path.parent().ok_or(eyre::eyre!(
@@ -530,18 +581,13 @@ pub fn clone_repository(
         prepare
     };
 
-    let branch = config.reference.clone();
+    let refspecs = fetch_refspecs(config);
 
-    // Configure the remote with the right refspecs for fetching just the configure branch and the
-    // remote's HEAD.
+    // Configure the remote with the right refspecs for fetching just the configured branches and
+    // the remote's HEAD.
     let mut prepare = prepare.configure_remote(move |mut remote| {
         // Can't be a Vec<String>; has to be a Vec<&str> ...
-        let mut refspecs = vec!["+HEAD:refs/remotes/origin/HEAD"];
-        let branch_refspec;
-        if let Some(branch) = &branch {
-            branch_refspec = format!("+refs/heads/{branch}:refs/heads/{branch}");
-            refspecs.push(&branch_refspec);
-        }
+        let refspecs: Vec<&str> = refspecs.iter().map(String::as_str).collect();
         // By default, gix will set the default wildcard refspec, which would fetch everything. But
         // we want to only fetch what the user configured.
         remote.replace_refspecs(&refspecs, gix::remote::Direction::Fetch)?;
@@ -840,6 +886,47 @@ mod tests {
         assert!(downstream.repo.find_commit(commit2).is_err());
     }
 
+    #[test]
+    fn test_pull_branch_glob() {
+        let (upstream, mut downstream) = fixtures::repository::upstream_downstream().unwrap();
+        upstream.set_branch("release/1.0").unwrap();
+        let release1 = upstream.commit("commit on release/1.0").create().unwrap();
+        upstream.set_branch("release/2.0").unwrap();
+        let release2 = upstream.commit("commit on release/2.0").create().unwrap();
+        upstream.set_branch("feature").unwrap();
+        let feature = upstream.commit("commit on feature").create().unwrap();
+        upstream.set_branch("main").unwrap();
+
+        let url = downstream
+            .repo
+            .find_remote("origin")
+            .unwrap()
+            .url(gix::remote::Direction::Fetch)
+            .unwrap()
+            .to_string();
+        let config = crate::config::RepositoryConfig {
+            reference: None,
+            references: vec!["release/*".to_string()],
+            url,
+            ..Default::default()
+        };
+
+        // The initial commit on main, and one on each release branch
+        let fetched_commits = pull_branch(&config, &mut downstream.repo).unwrap();
+        assert_eq!(fetched_commits, 3);
+
+        assert!(downstream.repo.find_reference("release/1.0").is_ok());
+        assert!(downstream.repo.find_reference("release/2.0").is_ok());
+        assert!(downstream.repo.find_commit(release1).is_ok());
+        assert!(downstream.repo.find_commit(release2).is_ok());
+        // Branches that don't match the glob aren't fetched
+        assert!(downstream.repo.find_reference("feature").is_err());
+        assert!(downstream.repo.find_commit(feature).is_err());
+
+        let fetched_commits = pull_branch(&config, &mut downstream.repo).unwrap();
+        assert_eq!(fetched_commits, 0);
+    }
+
     #[test]
     fn test_clone_file_url() {
         let upstream = repository::Builder::new()
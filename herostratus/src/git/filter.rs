@@ -0,0 +1,167 @@
+use chrono::{DateTime, Utc};
+use eyre::WrapErr;
+use gix::bstr::BStr;
+
+/// Select which commits are processed, like the commit limiting options of `git log`
+#[derive(Debug, Default, Clone)]
+pub struct CommitFilter {
+    /// Only commits committed at or after this time
+    pub since: Option<DateTime<Utc>>,
+    /// Only commits committed before this time
+    pub until: Option<DateTime<Utc>>,
+    /// Only commits whose `Author Name <email>` contains this, ignoring case
+    pub author: Option<String>,
+    /// Only commits that change a path matching one of these
+    pub pathspecs: Vec<String>,
+}
+
+impl CommitFilter {
+    pub fn is_empty(&self) -> bool {
+        self.since.is_none()
+            && self.until.is_none()
+            && self.author.is_none()
+            && self.pathspecs.is_empty()
+    }
+
+    pub fn matches(&self, oid: gix::ObjectId, repo: &gix::Repository) -> eyre::Result<bool> {
+        if self.is_empty() {
+            return Ok(true);
+        }
+        let commit = repo.find_commit(oid)?;
+
+        if self.since.is_some() || self.until.is_some() {
+            let seconds = commit.time()?.seconds;
+            let time = DateTime::from_timestamp(seconds, 0).unwrap_or_default();
+            if self.since.is_some_and(|since| time < since)
+                || self.until.is_some_and(|until| time >= until)
+            {
+                return Ok(false);
+            }
+        }
+
+        if let Some(pattern) = &self.author {
+            let author = commit.author()?;
+            let author = format!("{} <{}>", author.name, author.email).to_lowercase();
+            if !author.contains(&pattern.to_lowercase()) {
+                return Ok(false);
+            }
+        }
+
+        if !self.pathspecs.is_empty() {
+            return self.touches_pathspecs(&commit, repo);
+        }
+        Ok(true)
+    }
+
+    /// Whether the commit changes any path matched by [CommitFilter::pathspecs], compared to its
+    /// first parent
+    fn touches_pathspecs(
+        &self,
+        commit: &gix::Commit,
+        repo: &gix::Repository,
+    ) -> eyre::Result<bool> {
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent_ids().next() {
+            Some(pid) => match repo.find_commit(pid) {
+                Ok(parent) => parent.tree()?,
+                // Shallow clone -- parent commit is missing
+                Err(_) => repo.empty_tree(),
+            },
+            None => repo.empty_tree(),
+        };
+
+        let mut found = false;
+        let mut changes = parent_tree.changes()?;
+        changes.options(|o| {
+            o.track_rewrites(None);
+        });
+        let outcome = changes.for_each_to_obtain_tree(&tree, |change| {
+            found = self
+                .pathspecs
+                .iter()
+                .any(|spec| pathspec_matches(spec, change.location()));
+            let action = if found {
+                gix::object::tree::diff::Action::Break(())
+            } else {
+                gix::object::tree::diff::Action::Continue(())
+            };
+            Ok::<_, std::convert::Infallible>(action)
+        });
+        match outcome {
+            Ok(_) => {}
+            // Breaking out early on the first match is reported as cancellation
+            Err(gix::object::tree::diff::for_each::Error::Diff(
+                gix::diff::tree_with_rewrites::Error::Diff(gix::diff::tree::Error::Cancelled),
+            )) => {}
+            Err(e) => {
+                return Err(e).wrap_err_with(|| format!("Failed to diff commit {}", commit.id));
+            }
+        }
+        Ok(found)
+    }
+}
+
+/// Match a path relative to the repository root against a pathspec.
+///
+/// A pathspec matches the path itself and everything beneath it, and may contain glob patterns.
+fn pathspec_matches(spec: &str, path: &BStr) -> bool {
+    let spec = spec.trim_end_matches('/');
+    let prefix_match = path
+        .strip_prefix(spec.as_bytes())
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(b"/"));
+    prefix_match || gix::glob::wildmatch(spec.into(), path, gix::glob::wildmatch::Mode::empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use herostratus_tests::fixtures::repository;
+
+    use super::*;
+
+    #[test]
+    fn pathspecs() {
+        assert!(pathspec_matches("src", "src/main.rs".into()));
+        assert!(pathspec_matches("src/", "src/main.rs".into()));
+        assert!(pathspec_matches("src/main.rs", "src/main.rs".into()));
+        assert!(!pathspec_matches("src", "srcs/main.rs".into()));
+        assert!(pathspec_matches("*.rs", "src/main.rs".into()));
+        assert!(!pathspec_matches("*.md", "src/main.rs".into()));
+    }
+
+    #[test]
+    fn filter_by_author_and_time() {
+        let temp_repo = repository::Builder::new()
+            .commit("first")
+            .author("Alice", "alice@example.com")
+            .time(1_700_000_000)
+            .commit("second")
+            .author("Bob", "bob@example.com")
+            .time(1_700_000_100)
+            .build()
+            .unwrap();
+        let repo = &temp_repo.repo;
+        let second = repo.head_id().unwrap().detach();
+        let first = repo.rev_parse_single("HEAD~1").unwrap().detach();
+
+        let filter = CommitFilter {
+            author: Some("ALICE@".into()),
+            ..Default::default()
+        };
+        assert!(filter.matches(first, repo).unwrap());
+        assert!(!filter.matches(second, repo).unwrap());
+
+        let filter = CommitFilter {
+            since: DateTime::from_timestamp(1_700_000_050, 0),
+            ..Default::default()
+        };
+        assert!(!filter.matches(first, repo).unwrap());
+        assert!(filter.matches(second, repo).unwrap());
+
+        let filter = CommitFilter {
+            until: DateTime::from_timestamp(1_700_000_100, 0),
+            ..Default::default()
+        };
+        assert!(filter.matches(first, repo).unwrap());
+        assert!(!filter.matches(second, repo).unwrap());
+    }
+}
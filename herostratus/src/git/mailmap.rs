@@ -49,6 +49,48 @@ impl MailmapResolver {
         let sig = commit.author()?;
         Ok(self.snapshot.resolve(sig))
     }
+
+    /// Resolve the identities credited by the commit's `Co-authored-by:` trailers.
+    ///
+    /// Trailers that don't parse as `Name <email>` are skipped. Each co-author is returned once,
+    /// and the commit's own author is never returned, even if they credited themselves.
+    pub fn resolve_co_authors(
+        &self,
+        commit: &gix::Commit,
+    ) -> eyre::Result<Vec<gix::actor::Signature>> {
+        let author = self.resolve_author(commit)?;
+        let message = commit.message()?;
+        let Some(body) = message.body() else {
+            return Ok(Vec::new());
+        };
+
+        let mut co_authors: Vec<gix::actor::Signature> = Vec::new();
+        for trailer in body.trailers() {
+            if !trailer.token.eq_ignore_ascii_case(b"Co-authored-by") {
+                continue;
+            }
+            let Ok(identity) = gix::actor::IdentityRef::from_bytes(&trailer.value) else {
+                tracing::debug!(
+                    "Skipping malformed trailer: Co-authored-by: {}",
+                    trailer.value
+                );
+                continue;
+            };
+            let identity = identity.trim();
+            let sig = gix::actor::SignatureRef {
+                name: identity.name,
+                email: identity.email,
+                time: "",
+            };
+            let resolved = self.snapshot.resolve(sig);
+            let is_known =
+                |s: &gix::actor::Signature| s.email.eq_ignore_ascii_case(&resolved.email);
+            if !is_known(&author) && !co_authors.iter().any(is_known) {
+                co_authors.push(resolved);
+            }
+        }
+        Ok(co_authors)
+    }
 }
 
 /// Read a mailmap file from disk and merge its entries into the snapshot.
@@ -196,4 +238,55 @@ mod tests {
         assert_eq!(author.name, "Unmapped");
         assert_eq!(author.email, "unmapped@example.com");
     }
+
+    #[test]
+    fn co_authors_are_resolved() {
+        let temp_repo = repository::Builder::new()
+            .commit(
+                "Pair on the parser\n\n\
+                 Some details\n\n\
+                 Co-authored-by: Old Name <old@example.com>\n\
+                 co-authored-by: Bob <bob@example.com>\n\
+                 Co-authored-by: Bob Again <BOB@example.com>\n\
+                 Co-authored-by: Herostratus <Herostratus@example.com>\n\
+                 Co-authored-by: nobody in particular\n\
+                 Signed-off-by: Carol <carol@example.com>\n",
+            )
+            .build()
+            .unwrap();
+
+        let mailmap_dir = tempfile::tempdir().unwrap();
+        let mailmap_path = mailmap_dir.path().join("mailmap");
+        std::fs::write(
+            &mailmap_path,
+            "New Name <new@example.com> Old Name <old@example.com>\n",
+        )
+        .unwrap();
+        let resolver =
+            MailmapResolver::new(gix::mailmap::Snapshot::default(), Some(&mailmap_path), None)
+                .unwrap();
+
+        let head = temp_repo.repo.head_commit().unwrap();
+        let co_authors = resolver.resolve_co_authors(&head).unwrap();
+        let co_authors: Vec<_> = co_authors
+            .iter()
+            .map(|sig| format!("{} <{}>", sig.name, sig.email))
+            .collect();
+        // Duplicates, the author, malformed trailers, and other trailers are skipped
+        assert_eq!(
+            co_authors,
+            ["New Name <new@example.com>", "Bob <bob@example.com>"]
+        );
+    }
+
+    #[test]
+    fn no_co_authors() {
+        let temp_repo = repository::Builder::new()
+            .commit("Subject only")
+            .build()
+            .unwrap();
+        let resolver = MailmapResolver::new(gix::mailmap::Snapshot::default(), None, None).unwrap();
+        let head = temp_repo.repo.head_commit().unwrap();
+        assert!(resolver.resolve_co_authors(&head).unwrap().is_empty());
+    }
 }
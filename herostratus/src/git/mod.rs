@@ -1,4 +1,5 @@
 pub mod clone;
 pub mod deepen;
+pub mod filter;
 pub mod mailmap;
 pub mod rev;
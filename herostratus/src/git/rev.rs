@@ -14,12 +14,100 @@ pub fn parse(reference: &str, repo: &gix::Repository) -> eyre::Result<gix::Objec
     Ok(oid)
 }
 
+/// Parse a reference, or a revision range like `main..feature`
+///
+/// Returns the tip to walk from, and the commits to hide from the walk. Either side of the range
+/// may be omitted, and defaults to `HEAD`.
+pub fn parse_range(
+    spec: &str,
+    repo: &gix::Repository,
+) -> eyre::Result<(gix::ObjectId, Vec<gix::ObjectId>)> {
+    if spec.contains("...") {
+        eyre::bail!("Symmetric difference ranges ({spec:?}) are not supported");
+    }
+    let Some((hide, tip)) = spec.split_once("..") else {
+        return Ok((parse(spec, repo)?, Vec::new()));
+    };
+    let or_head = |s: &'_ str| if s.is_empty() { "HEAD" } else { s }.to_string();
+    let tip = parse(&or_head(tip), repo)?;
+    let hide = parse(&or_head(hide), repo)?;
+    Ok((tip, vec![hide]))
+}
+
+/// Check if a full reference name like `refs/heads/release/1.0` is the branch named by `pattern`
+///
+/// Like a Git refspec, the pattern may contain a single `*`, which matches any number of
+/// characters, including `/`.
+pub fn branch_matches(pattern: &str, full_name: &str) -> bool {
+    let Some(name) = full_name.strip_prefix("refs/heads/") else {
+        return false;
+    };
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => {
+            name.len() >= prefix.len() + suffix.len()
+                && name.starts_with(prefix)
+                && name.ends_with(suffix)
+        }
+        None => name == pattern,
+    }
+}
+
+/// Resolve the branches tracked by a repository to the commits at their tips
+///
+/// Globs like `release/*` are expanded to every matching local branch, sorted by name. Everything
+/// else is resolved with [parse]. Returns the branch names in the order they were given, without
+/// duplicates.
+pub fn resolve_references(
+    references: &[&str],
+    repo: &gix::Repository,
+) -> eyre::Result<Vec<(String, gix::ObjectId)>> {
+    let mut resolved: Vec<(String, gix::ObjectId)> = Vec::new();
+    for reference in references {
+        let mut matches = Vec::new();
+        if reference.contains('*') {
+            for branch in repo.references()?.local_branches()? {
+                let branch = branch.map_err(|e| eyre::eyre!(e))?;
+                let full_name = branch.name().as_bstr().to_string();
+                if branch_matches(reference, &full_name) {
+                    let oid = parse(&full_name, repo)?;
+                    let name = full_name.trim_start_matches("refs/heads/").to_string();
+                    matches.push((name, oid));
+                }
+            }
+            if matches.is_empty() {
+                tracing::warn!("No branches match {reference:?}");
+            }
+            matches.sort();
+        } else {
+            let oid = parse(reference, repo)
+                .wrap_err_with(|| format!("Failed to rev-parse: {reference:?}"))?;
+            matches.push((reference.to_string(), oid));
+        }
+
+        for (name, oid) in matches {
+            if !resolved.iter().any(|(n, _)| *n == name) {
+                resolved.push((name, oid));
+            }
+        }
+    }
+    Ok(resolved)
+}
+
 pub fn walk(
     oid: gix::ObjectId,
     repo: &gix::Repository,
 ) -> eyre::Result<impl Iterator<Item = eyre::Result<gix::ObjectId>> + '_> {
-    tracing::debug!("Walking backwards from {oid}");
-    let walk = repo.rev_walk(Some(oid));
+    walk_hidden(oid, Vec::new(), repo)
+}
+
+/// Walk backwards from `oid`, skipping any commits reachable from `hidden`
+pub fn walk_hidden(
+    oid: gix::ObjectId,
+    hidden: Vec<gix::ObjectId>,
+    repo: &gix::Repository,
+) -> eyre::Result<impl Iterator<Item = eyre::Result<gix::ObjectId>> + '_> {
+    tracing::debug!("Walking backwards from {oid}, hiding {hidden:?}");
+    let walk = repo.rev_walk(Some(oid)).with_hidden(hidden);
     let walk = walk.sorting(gix::revision::walk::Sorting::ByCommitTime(
         gix::traverse::commit::simple::CommitTimeOrder::NewestFirst,
     ));
@@ -66,6 +154,61 @@ mod test {
         );
     }
 
+    #[test]
+    fn walk_revision_range() {
+        let temp_repo = repository::Builder::new()
+            .commit("commit1")
+            .tag("base")
+            .commit("commit2")
+            .commit("commit3")
+            .build()
+            .unwrap();
+        let repo = &temp_repo.repo;
+
+        let (tip, hidden) = parse_range("base..", repo).unwrap();
+        let commits: Vec<_> = walk_hidden(tip, hidden, repo)
+            .unwrap()
+            .map(|oid| repo.find_commit(oid.unwrap()).unwrap())
+            .collect();
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].message().unwrap().summary().as_ref(), "commit3");
+        assert_eq!(commits[1].message().unwrap().summary().as_ref(), "commit2");
+
+        let (_, hidden) = parse_range("HEAD", repo).unwrap();
+        assert!(hidden.is_empty());
+        assert!(parse_range("base...HEAD", repo).is_err());
+    }
+
+    #[test]
+    fn resolve_branch_globs() {
+        assert!(branch_matches("release/*", "refs/heads/release/1.0"));
+        assert!(branch_matches("release/*", "refs/heads/release/1.x/rc"));
+        assert!(branch_matches("*-stable", "refs/heads/2.0-stable"));
+        assert!(branch_matches("main", "refs/heads/main"));
+        assert!(!branch_matches("release/*", "refs/heads/main"));
+        assert!(!branch_matches("release/*", "refs/tags/release/1.0"));
+        assert!(!branch_matches("main", "refs/heads/not-main"));
+
+        let temp_repo = repository::Builder::new()
+            .commit("commit1")
+            .branch("release/2.0")
+            .commit("commit2")
+            .branch("release/1.0")
+            .commit("commit3")
+            .build()
+            .unwrap();
+        let repo = &temp_repo.repo;
+
+        let resolved = resolve_references(&["HEAD", "release/*", "release/1.0"], repo).unwrap();
+        let names: Vec<_> = resolved.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["HEAD", "release/1.0", "release/2.0"]);
+        assert_eq!(resolved[1].1, parse("release/1.0", repo).unwrap());
+
+        // A glob that matches nothing isn't an error, but a missing branch is
+        assert!(resolve_references(&["feature/*"], repo).unwrap().is_empty());
+        assert!(resolve_references(&["feature"], repo).is_err());
+    }
+
     #[test]
     fn rev_parse_and_walk_tags() {
         let temp_repo = repository::Builder::new()
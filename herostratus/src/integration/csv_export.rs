@@ -0,0 +1,75 @@
+use crate::achievement::{
+    AchievementLog, GrantStats, upsert_repository_csv, write_achievements_csv,
+};
+use crate::config::RulesConfig;
+use crate::integration::{Integration, IntegrationFactory, RepositoryContext};
+
+/// Export the achievement catalog and per-repository statistics as CSV files.
+///
+/// Writes `export/achievements.csv` and `export/repositories.csv` in the data directory. These,
+/// together with the `export/events/` logs, are the input to `herostratus render`. Does nothing
+/// for stateless `check` runs.
+#[derive(Default)]
+pub struct CsvExport {
+    /// The catalog is the same for every repository in a run, so it's only written once
+    wrote_achievements: bool,
+}
+
+inventory::submit!(IntegrationFactory::new(
+    "csv-export",
+    "Write achievements.csv and repositories.csv to the export directory",
+    |_| Ok(Box::new(CsvExport::default())),
+));
+
+impl Integration for CsvExport {
+    fn name(&self) -> &'static str {
+        "csv-export"
+    }
+
+    fn on_repository_start(
+        &mut self,
+        repo: &RepositoryContext,
+        _prior: &AchievementLog,
+    ) -> eyre::Result<()> {
+        let Some(data_dir) = repo.data_dir else {
+            return Ok(());
+        };
+        if self.wrote_achievements {
+            return Ok(());
+        }
+        let default_rc = RulesConfig::default();
+        let rules_config = repo
+            .config
+            .and_then(|c| c.rules.as_ref())
+            .unwrap_or(&default_rc);
+        let rules = crate::rules::all_rules(rules_config);
+        write_achievements_csv(data_dir, &rules, rules_config)?;
+        self.wrote_achievements = true;
+        Ok(())
+    }
+
+    fn on_repository_complete(
+        &mut self,
+        repo: &RepositoryContext,
+        stats: &GrantStats,
+    ) -> eyre::Result<()> {
+        let Some(data_dir) = repo.data_dir else {
+            return Ok(());
+        };
+        if stats.num_commits_processed == 0 {
+            return Ok(());
+        }
+
+        let repo_config = repo.repo_config;
+        let url = repo_config.map(|rc| rc.url.as_str()).unwrap_or("");
+        let commit_url_prefix = repo_config.and_then(|rc| rc.resolve_commit_url_prefix());
+        upsert_repository_csv(
+            data_dir,
+            repo.name,
+            url,
+            commit_url_prefix.as_deref(),
+            repo.reference,
+            stats.num_commits_processed,
+        )
+    }
+}
@@ -0,0 +1,291 @@
+//! Pluggable sinks for the achievements generated by a run
+//!
+//! An [Integration] is notified at each stage of processing a repository, and once more after
+//! `check-all` has processed every repository. Each implementation lives in its own module behind
+//! a cargo feature, and registers itself with [inventory::submit!] so that it can be selected by
+//! name from the CLI or `config.toml`.
+
+#[cfg(feature = "csv-export")]
+mod csv_export;
+#[cfg(feature = "static-site")]
+mod static_site;
+
+use std::path::{Path, PathBuf};
+
+use crate::achievement::{AchievementEvent, AchievementLog, GrantStats};
+use crate::config::{Config, RepositoryConfig};
+
+/// The integrations enabled when neither the CLI nor `config.toml` select any.
+#[cfg(feature = "csv-export")]
+pub const DEFAULT_INTEGRATIONS: &[&str] = &["csv-export"];
+#[cfg(not(feature = "csv-export"))]
+pub const DEFAULT_INTEGRATIONS: &[&str] = &[];
+
+/// What an [Integration] is told about the repository being processed.
+pub struct RepositoryContext<'a> {
+    /// The repository name from `config.toml`, or the directory name for stateless `check`
+    pub name: &'a str,
+    /// The Git reference being processed
+    pub reference: &'a str,
+    /// The path to the Git repository
+    pub git_dir: &'a Path,
+    /// The application data directory. `None` for stateless `check`.
+    pub data_dir: Option<&'a Path>,
+    pub config: Option<&'a Config>,
+    pub repo_config: Option<&'a RepositoryConfig>,
+}
+
+impl RepositoryContext<'_> {
+    /// The directory holding the checkpoint and rule caches for this repository
+    pub fn cache_dir(&self) -> Option<PathBuf> {
+        self.data_dir.map(|d| d.join("cache").join(self.name))
+    }
+
+    /// The directory holding the CSV exports shared by every repository
+    pub fn export_dir(&self) -> Option<PathBuf> {
+        self.data_dir.map(|d| d.join("export"))
+    }
+}
+
+/// A sink for the achievements generated by a run.
+///
+/// Every hook has a no-op default, so implementations only need to override the ones they care
+/// about. The hooks for a repository are called in order: [on_repository_start], [on_event] once
+/// per event, then [on_repository_complete]. A single instance is reused for every repository
+/// processed by `check-all`, so cross-repository state can be kept on `self`.
+///
+/// When `check-all` runs with more than one job, repositories are checked on worker threads and
+/// their hooks are called afterwards, in config order. The hooks still arrive in the same order,
+/// but events are no longer delivered as they're generated.
+///
+/// [on_repository_start]: Integration::on_repository_start
+/// [on_event]: Integration::on_event
+/// [on_repository_complete]: Integration::on_repository_complete
+pub trait Integration {
+    /// The name used to select this integration
+    fn name(&self) -> &'static str;
+
+    /// Called before processing a repository, with the achievement log from previous runs.
+    fn on_repository_start(
+        &mut self,
+        _repo: &RepositoryContext,
+        _prior: &AchievementLog,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// Called for each grant or revocation as it is generated.
+    fn on_event(
+        &mut self,
+        _repo: &RepositoryContext,
+        _event: &AchievementEvent,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// Called after a repository has been fully processed.
+    fn on_repository_complete(
+        &mut self,
+        _repo: &RepositoryContext,
+        _stats: &GrantStats,
+    ) -> eyre::Result<()> {
+        Ok(())
+    }
+
+    /// Called once after `check-all` has processed every configured repository.
+    fn on_check_all_complete(&mut self, _config: &Config, _data_dir: &Path) -> eyre::Result<()> {
+        Ok(())
+    }
+}
+
+/// A factory to build [Integration]s.
+///
+/// Each integration registers an [IntegrationFactory] via [inventory::submit!].
+pub struct IntegrationFactory {
+    name: &'static str,
+    description: &'static str,
+    requires: &'static [&'static str],
+    factory: fn(&Config) -> eyre::Result<Box<dyn Integration>>,
+}
+
+impl IntegrationFactory {
+    pub const fn new(
+        name: &'static str,
+        description: &'static str,
+        factory: fn(&Config) -> eyre::Result<Box<dyn Integration>>,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            requires: &[],
+            factory,
+        }
+    }
+
+    /// Other integrations that must be selected alongside this one, like the exports it reads
+    pub const fn requires(mut self, requires: &'static [&'static str]) -> Self {
+        self.requires = requires;
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// Use the factory to build the [Integration].
+    pub fn build(&self, config: &Config) -> eyre::Result<Box<dyn Integration>> {
+        (self.factory)(config)
+    }
+}
+
+inventory::collect!(IntegrationFactory);
+
+/// Every [IntegrationFactory] compiled into this build, sorted by name.
+pub fn available_integrations() -> Vec<&'static IntegrationFactory> {
+    let mut factories: Vec<_> = inventory::iter::<IntegrationFactory>.into_iter().collect();
+    factories.sort_by_key(|f| f.name);
+    factories
+}
+
+/// The set of [Integration]s selected for a run.
+///
+/// Fans each hook out to every selected integration, in the order they were selected.
+#[derive(Default)]
+pub struct Integrations {
+    integrations: Vec<Box<dyn Integration>>,
+}
+
+impl Integrations {
+    /// Build the integrations selected by name.
+    ///
+    /// The `selected` names (from the CLI) take precedence over `integrations.enabled` from the
+    /// config file, which in turn takes precedence over [DEFAULT_INTEGRATIONS].
+    pub fn new(selected: &[String], config: Option<&Config>) -> eyre::Result<Self> {
+        let default_config = Config::default();
+        let config = config.unwrap_or(&default_config);
+
+        let names: Vec<&str> = if !selected.is_empty() {
+            selected.iter().map(String::as_str).collect()
+        } else if let Some(enabled) = config
+            .integrations
+            .as_ref()
+            .and_then(|i| i.enabled.as_ref())
+        {
+            enabled.iter().map(String::as_str).collect()
+        } else {
+            DEFAULT_INTEGRATIONS.to_vec()
+        };
+
+        let available = available_integrations();
+        let mut integrations = Vec::new();
+        for &name in &names {
+            let Some(factory) = available.iter().find(|f| f.name == name) else {
+                let available: Vec<_> = available.iter().map(|f| f.name).collect();
+                eyre::bail!(
+                    "Integration {name:?} not found. Available integrations: {available:?}"
+                );
+            };
+            if let Some(missing) = factory.requires.iter().find(|r| !names.contains(r)) {
+                eyre::bail!(
+                    "Integration {name:?} requires the {missing:?} integration to be enabled"
+                );
+            }
+            tracing::debug!("Enabling integration {name:?}");
+            integrations.push(factory.build(config)?);
+        }
+
+        Ok(Self { integrations })
+    }
+
+    pub fn on_repository_start(
+        &mut self,
+        repo: &RepositoryContext,
+        prior: &AchievementLog,
+    ) -> eyre::Result<()> {
+        for i in &mut self.integrations {
+            i.on_repository_start(repo, prior)?;
+        }
+        Ok(())
+    }
+
+    pub fn on_event(
+        &mut self,
+        repo: &RepositoryContext,
+        event: &AchievementEvent,
+    ) -> eyre::Result<()> {
+        for i in &mut self.integrations {
+            i.on_event(repo, event)?;
+        }
+        Ok(())
+    }
+
+    pub fn on_repository_complete(
+        &mut self,
+        repo: &RepositoryContext,
+        stats: &GrantStats,
+    ) -> eyre::Result<()> {
+        for i in &mut self.integrations {
+            i.on_repository_complete(repo, stats)?;
+        }
+        Ok(())
+    }
+
+    pub fn on_check_all_complete(&mut self, config: &Config, data_dir: &Path) -> eyre::Result<()> {
+        for i in &mut self.integrations {
+            tracing::debug!("Finishing integration {:?}", i.name());
+            i.on_check_all_complete(config, data_dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "csv-export")]
+    fn defaults_to_csv_export() {
+        let integrations = Integrations::new(&[], None).unwrap();
+        let names: Vec<_> = integrations.integrations.iter().map(|i| i.name()).collect();
+        assert_eq!(names, DEFAULT_INTEGRATIONS);
+    }
+
+    #[test]
+    #[cfg(all(feature = "csv-export", feature = "static-site"))]
+    fn cli_overrides_config() {
+        let config = Config {
+            integrations: Some(crate::config::IntegrationsConfig {
+                enabled: Some(vec!["csv-export".into(), "static-site".into()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let integrations = Integrations::new(&[], Some(&config)).unwrap();
+        let names: Vec<_> = integrations.integrations.iter().map(|i| i.name()).collect();
+        assert_eq!(names, ["csv-export", "static-site"]);
+
+        let integrations = Integrations::new(&["csv-export".into()], Some(&config)).unwrap();
+        let names: Vec<_> = integrations.integrations.iter().map(|i| i.name()).collect();
+        assert_eq!(names, ["csv-export"]);
+    }
+
+    #[test]
+    #[cfg(feature = "static-site")]
+    fn static_site_requires_csv_export() {
+        let result = Integrations::new(&["static-site".into()], None);
+        let err = result.err().unwrap().to_string();
+        assert!(err.contains("\"csv-export\""), "{err}");
+    }
+
+    #[test]
+    fn unknown_integration_is_an_error() {
+        let result = Integrations::new(&["carrier-pigeon".into()], None);
+        assert!(result.is_err());
+    }
+}
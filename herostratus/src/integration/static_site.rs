@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::cli::RenderArgs;
+use crate::config::{Config, StaticSiteConfig};
+use crate::integration::{Integration, IntegrationFactory};
+
+/// Render the static site after `check-all` has processed every repository.
+///
+/// Configured by the `[integrations.static_site]` table. Equivalent to running `herostratus
+/// render` after `herostratus check-all`. Requires the `csv-export` integration, which writes the
+/// exports that are rendered.
+pub struct StaticSite {
+    config: StaticSiteConfig,
+}
+
+inventory::submit!(
+    IntegrationFactory::new(
+        "static-site",
+        "Render the static site after check-all",
+        |config| {
+            let config = config
+                .integrations
+                .as_ref()
+                .and_then(|i| i.static_site.clone())
+                .unwrap_or_default();
+            Ok(Box::new(StaticSite { config }))
+        },
+    )
+    .requires(&["csv-export"])
+);
+
+impl Integration for StaticSite {
+    fn name(&self) -> &'static str {
+        "static-site"
+    }
+
+    fn on_check_all_complete(&mut self, _config: &Config, data_dir: &Path) -> eyre::Result<()> {
+        let args = RenderArgs {
+            export_dir: Some(data_dir.join("export")),
+            output_dir: self
+                .config
+                .output_dir
+                .clone()
+                .unwrap_or_else(|| data_dir.join("public")),
+            base_url: self.config.base_url.clone().unwrap_or_else(|| "/".into()),
+            site_title: self
+                .config
+                .site_title
+                .clone()
+                .unwrap_or_else(|| "Herostratus".into()),
+            templates: self.config.templates.clone(),
+            extra_css: self.config.resolve_extra_css(data_dir),
+            header_html: self.config.resolve_header_html(data_dir),
+            footer_html: self.config.resolve_footer_html(data_dir),
+            emit_json: self.config.emit_json.unwrap_or_default(),
+            avatars: self.config.resolve_avatars(data_dir),
+        };
+        crate::commands::render(&args)
+    }
+}
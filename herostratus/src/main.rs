@@ -24,7 +24,10 @@ fn main() -> eyre::Result<()> {
     let _trace_guard = herostratus::trace::init(args.log_level, use_color);
 
     if args.get_config {
-        let config = herostratus::config::read_config(&data_dir)?;
+        let mut config = herostratus::config::read_config(&data_dir)?;
+        // Show the effective configuration of every rule, not just the ones that were configured
+        let rules = config.rules.take().unwrap_or_default();
+        config.rules = Some(rules.with_defaults()?);
         println!("{}", herostratus::config::serialize_config(&config)?);
         return Ok(());
     }
@@ -45,6 +48,13 @@ fn main() -> eyre::Result<()> {
         return Ok(());
     }
 
+    if args.list_integrations {
+        for factory in herostratus::integration::available_integrations() {
+            println!("{:25}\t{}", factory.name(), factory.description());
+        }
+        return Ok(());
+    }
+
     match args.command {
         // Shortcoming of clap; you can't have required_unless_present_any=[] for subcommands
         None => {
@@ -71,14 +81,48 @@ fn main() -> eyre::Result<()> {
                 stats.print_summary();
             }
         }
+        Some(herostratus::cli::Command::Explain(eargs)) => {
+            let config = args
+                .data_dir
+                .map(|d| herostratus::config::read_config(&d))
+                .transpose()?;
+            herostratus::commands::explain(&eargs, config.as_ref()).wrap_err(format!(
+                "Failed to explain commit {:?} in repository {:?}",
+                eargs.commit,
+                eargs.path.display()
+            ))?;
+        }
         Some(herostratus::cli::Command::Render(mut rargs)) => {
             if rargs.export_dir.is_none() && data_dir.exists() {
                 rargs.export_dir = Some(data_dir.join("export"));
             } else if rargs.export_dir.is_none() {
                 panic!("render subcommand requires --export-dir");
             }
+            // Like check, render doesn't need a config, so only read it if there is one
+            if herostratus::config::config_path(&data_dir).exists() {
+                let config = herostratus::config::read_config(&data_dir)?;
+                if let Some(site) = config.integrations.and_then(|i| i.static_site) {
+                    rargs.avatars = site.resolve_avatars(&data_dir);
+                    // The CLI flags take precedence over the config
+                    rargs.extra_css = rargs
+                        .extra_css
+                        .or_else(|| site.resolve_extra_css(&data_dir));
+                    rargs.header_html = rargs
+                        .header_html
+                        .or_else(|| site.resolve_header_html(&data_dir));
+                    rargs.footer_html = rargs
+                        .footer_html
+                        .or_else(|| site.resolve_footer_html(&data_dir));
+                }
+            }
             herostratus::commands::render(&rargs)?;
         }
+        Some(herostratus::cli::Command::Query(mut qargs)) => {
+            if qargs.export_dir.is_none() {
+                qargs.export_dir = Some(data_dir.join("export"));
+            }
+            herostratus::commands::query(&qargs).wrap_err("Failed to query achievement events")?;
+        }
         // The other subcommands are stateful, and require reading the application configuration
         Some(command) => {
             let mut config = herostratus::config::read_config(&data_dir)?;
@@ -91,6 +135,10 @@ fn main() -> eyre::Result<()> {
                     herostratus::commands::add(&args, &mut config, &data_dir)
                         .wrap_err(format!("Failed to add repository with url: {:?}", args.url))?;
                 }
+                herostratus::cli::Command::Remove(args) => {
+                    herostratus::commands::remove(&args, &mut config, &data_dir)
+                        .wrap_err(format!("Failed to remove repository {:?}", args.repository))?;
+                }
                 herostratus::cli::Command::CheckOne(args) => {
                     let stats = herostratus::commands::check_one(&args, &runtime_config, &data_dir)
                         .wrap_err(format!("Failed to check repository {:?}", args.repository))?;
@@ -103,6 +151,8 @@ fn main() -> eyre::Result<()> {
                         .wrap_err("Failed to check all repositories")?;
                     if args.summary {
                         herostratus::commands::print_check_all_summary(&stats);
+                        herostratus::commands::print_top_scorers(&runtime_config, &data_dir)
+                            .wrap_err("Failed to compute the leaderboard")?;
                     }
                 }
                 herostratus::cli::Command::FetchAll(args) => {
@@ -1,29 +1,50 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
 
 /// Per-commit metadata that pairs with observations flowing through the channel.
 ///
 /// Rules see `CommitContext` + `Observation` -- they never touch the raw `gix::Commit`. Mailmap
 /// resolution happens once in the ObserverEngine before constructing this struct.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct CommitContext {
+    #[serde(serialize_with = "serialize_oid")]
     pub oid: gix::ObjectId,
     pub author_name: String,
     pub author_email: String,
     /// The committer timestamp from the git commit.
     pub commit_timestamp: DateTime<Utc>,
+    /// The author timestamp, in the author's own timezone. Use this for the author's local time of
+    /// day, which UTC loses.
+    pub author_timestamp: DateTime<FixedOffset>,
+    /// The other authors credited by the commit's `Co-authored-by:` trailers, resolved through the
+    /// mailmap. Never includes the author.
+    pub co_authors: Vec<CoAuthor>,
+}
+
+/// An author credited by a `Co-authored-by: Name <email>` trailer
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct CoAuthor {
+    pub name: String,
+    pub email: String,
+}
+
+fn serialize_oid<S: serde::Serializer>(oid: &gix::ObjectId, s: S) -> Result<S::Ok, S::Error> {
+    s.collect_str(oid)
 }
 
 #[cfg(test)]
 impl CommitContext {
     /// Create a test CommitContext with a null OID and an email derived from the name.
     ///
-    /// The email is `{lowercase_name}@example.com`. The commit timestamp is the Unix epoch.
+    /// The email is `{lowercase_name}@example.com`. The commit and author timestamps are the Unix
+    /// epoch, in UTC.
     pub fn test(name: &str) -> Self {
         Self {
             oid: gix::ObjectId::null(gix::hash::Kind::Sha1),
             author_name: name.to_string(),
             author_email: format!("{}@example.com", name.to_lowercase()),
             commit_timestamp: DateTime::UNIX_EPOCH,
+            author_timestamp: DateTime::UNIX_EPOCH.fixed_offset(),
+            co_authors: Vec::new(),
         }
     }
 }
@@ -0,0 +1,175 @@
+use std::mem::Discriminant;
+
+use crate::observer::observation::Observation;
+use crate::observer::observer::{DiffAction, LineCounts, Observer};
+use crate::observer::observer_factory::ObserverFactory;
+
+/// Emits [Observation::DiffStats] with the lines added, lines removed, and files changed by each
+/// commit that changes any files.
+///
+/// Nothing is emitted for the boundary commit of a shallow clone, since its parent is missing.
+#[derive(Default)]
+pub struct DiffStatsObserver {
+    lines_added: u64,
+    lines_removed: u64,
+    files_changed: u64,
+}
+
+inventory::submit!(ObserverFactory::new::<DiffStatsObserver>());
+
+impl Observer for DiffStatsObserver {
+    fn emits(&self) -> Discriminant<Observation> {
+        Observation::DIFF_STATS
+    }
+
+    fn is_interested_in_diff(&self) -> bool {
+        true
+    }
+
+    fn is_interested_in_line_counts(&self) -> bool {
+        true
+    }
+
+    fn on_commit(
+        &mut self,
+        _commit: &gix::Commit,
+        _repo: &gix::Repository,
+    ) -> eyre::Result<Option<Observation>> {
+        Ok(None)
+    }
+
+    fn on_diff_start(&mut self) -> eyre::Result<()> {
+        *self = Self::default();
+        Ok(())
+    }
+
+    fn on_diff_line_counts(
+        &mut self,
+        change: &gix::object::tree::diff::ChangeDetached,
+        counts: Option<LineCounts>,
+    ) -> eyre::Result<DiffAction> {
+        // The tree diff reports directories alongside the files in them
+        if change.entry_mode().is_tree() {
+            return Ok(DiffAction::Continue);
+        }
+        self.files_changed += 1;
+        if let Some(counts) = counts {
+            self.lines_added += counts.added;
+            self.lines_removed += counts.removed;
+        }
+        Ok(DiffAction::Continue)
+    }
+
+    fn on_diff_end(&mut self) -> eyre::Result<Option<Observation>> {
+        if self.files_changed == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Observation::DiffStats {
+            lines_added: self.lines_added,
+            lines_removed: self.lines_removed,
+            files_changed: self.files_changed,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use herostratus_tests::fixtures::repository;
+
+    use super::*;
+    use crate::config::RepositoryConfig;
+    use crate::observer::impls::test_helpers::{observe_all, observe_repo};
+
+    fn stats(lines_added: u64, lines_removed: u64, files_changed: u64) -> Observation {
+        Observation::DiffStats {
+            lines_added,
+            lines_removed,
+            files_changed,
+        }
+    }
+
+    #[test]
+    fn counts_lines_and_files() {
+        let repo = repository::Builder::new()
+            .commit("add files")
+            .file("a.txt", b"one\ntwo\nthree\n")
+            .file("dir/b.txt", b"four\n")
+            .commit("edit and delete")
+            .file("a.txt", b"one\n2\nthree\nfour\n")
+            .remove("dir/b.txt")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, DiffStatsObserver::default());
+        assert_eq!(observations, [stats(4, 0, 2), stats(2, 2, 2)]);
+    }
+
+    #[test]
+    fn renames_only_count_changed_lines() {
+        let content = b"one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten\n";
+        let mut edited = content.to_vec();
+        edited.extend_from_slice(b"eleven\n");
+        let repo = repository::Builder::new()
+            .commit("add file")
+            .file("old.txt", content)
+            .commit("move file")
+            .remove("old.txt")
+            .file("new.txt", content)
+            .commit("move and edit file")
+            .remove("new.txt")
+            .file("newer.txt", &edited)
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, DiffStatsObserver::default());
+        assert_eq!(
+            observations,
+            [stats(10, 0, 1), stats(0, 0, 1), stats(1, 0, 1)]
+        );
+    }
+
+    #[test]
+    fn binary_files_have_no_lines() {
+        let repo = repository::Builder::new()
+            .commit("add binary")
+            .file("image.bin", b"\x00\x01\x02\n\x03\n")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, DiffStatsObserver::default());
+        assert_eq!(observations, [stats(0, 0, 1)]);
+    }
+
+    #[test]
+    fn shallow_boundary_not_observed() {
+        let upstream = repository::Builder::new()
+            .commit("add files")
+            .file("a.txt", b"one\ntwo\nthree\n")
+            .commit("edit file")
+            .file("a.txt", b"one\n2\nthree\n")
+            .commit("add another file")
+            .file("b.txt", b"four\n")
+            .build()
+            .unwrap();
+        let tempdir = tempfile::tempdir().unwrap();
+        let config = RepositoryConfig {
+            url: format!("file://{}", upstream.tempdir.path().display()),
+            path: tempdir.path().join("shallow"),
+            ..Default::default()
+        };
+        let shallow = crate::git::clone::clone_repository(&config, false, Some(2)).unwrap();
+        assert!(shallow.is_shallow());
+
+        // The boundary commit "edit file" would otherwise look like it added all of a.txt
+        let observations = observe_repo(&shallow, DiffStatsObserver::default());
+        assert_eq!(observations, [stats(1, 0, 1)]);
+    }
+
+    #[test]
+    fn empty_commit_not_observed() {
+        let repo = repository::Builder::new()
+            .commit("first")
+            .commit("empty")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, DiffStatsObserver::default());
+        assert!(observations.is_empty());
+    }
+}
@@ -1,4 +1,5 @@
 mod ci_config;
+mod diff_stats;
 mod empty_commit;
 mod fixup;
 mod hex_tokens;
@@ -6,7 +7,9 @@ mod non_unicode;
 mod parent_count;
 mod profanity;
 mod quine_prefix;
+mod signed;
 mod subject;
+mod trailers;
 mod whitespace_only;
 
 #[cfg(test)]
@@ -14,31 +17,42 @@ mod test_helpers {
     use herostratus_tests::fixtures::repository::TempRepository;
 
     use crate::git::mailmap::MailmapResolver;
-    use crate::observer::{Observation, Observer, ObserverData, ObserverEngine};
+    use crate::observer::{EngineCommand, Observation, Observer, ObserverData, ObserverEngine};
 
     /// Run a single observer against all commits in the repo (oldest first) and collect the
     /// observations it emits.
     pub fn observe_all(
         repo: &TempRepository,
         observer: impl Observer + 'static,
+    ) -> Vec<Observation> {
+        observe_repo(&repo.repo, observer)
+    }
+
+    /// Like [observe_all], for a repository that isn't a [TempRepository], like a shallow clone
+    pub fn observe_repo(
+        repo: &gix::Repository,
+        observer: impl Observer + 'static,
     ) -> Vec<Observation> {
         let mailmap = MailmapResolver::new(gix::mailmap::Snapshot::default(), None, None).unwrap();
         let observers: Vec<Box<dyn Observer>> = vec![Box::new(observer)];
-        let mut engine = ObserverEngine::new(&repo.repo, observers, mailmap).unwrap();
+        let mut engine = ObserverEngine::new(repo, observers, mailmap).unwrap();
 
-        let head = crate::git::rev::parse("HEAD", &repo.repo).unwrap();
-        let oids: Vec<_> = crate::git::rev::walk(head, &repo.repo)
+        let head = crate::git::rev::parse("HEAD", repo).unwrap();
+        let oids: Vec<_> = crate::git::rev::walk(head, repo)
             .unwrap()
             .map(|r| r.unwrap())
             .collect();
         // Walk returns newest-first; reverse to process oldest-first
         let oids: Vec<_> = oids.into_iter().rev().collect();
 
-        let (tx, rx) = std::sync::mpsc::channel();
-        engine.run(oids, &tx).unwrap();
+        let (tx, rx) = std::sync::mpsc::sync_channel(oids.len());
+        engine
+            .run(oids.into_iter().map(EngineCommand::Process), &tx)
+            .unwrap();
         drop(tx);
 
         rx.iter()
+            .flatten()
             .filter_map(|msg| match msg {
                 ObserverData::Observation(obs) => Some(obs),
                 _ => None,
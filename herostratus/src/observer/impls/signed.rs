@@ -0,0 +1,105 @@
+use std::mem::Discriminant;
+
+use gix::bstr::ByteSlice;
+
+use crate::observer::observation::{Observation, SignatureKind};
+use crate::observer::observer::Observer;
+use crate::observer::observer_factory::ObserverFactory;
+
+/// The first line of each kind of signature, the same ones Git uses to tell them apart
+const ARMOR: &[(&str, SignatureKind)] = &[
+    ("-----BEGIN PGP SIGNATURE-----", SignatureKind::Gpg),
+    ("-----BEGIN PGP MESSAGE-----", SignatureKind::Gpg),
+    ("-----BEGIN SSH SIGNATURE-----", SignatureKind::Ssh),
+    ("-----BEGIN SIGNED MESSAGE-----", SignatureKind::X509),
+];
+
+/// Emits [Observation::Signed] when the commit has a `gpgsig` header in a recognized format.
+///
+/// Signatures are never verified, since that would need the signer's public key.
+#[derive(Default)]
+pub struct SignedObserver;
+
+inventory::submit!(ObserverFactory::new::<SignedObserver>());
+
+impl Observer for SignedObserver {
+    fn emits(&self) -> Discriminant<Observation> {
+        Observation::SIGNED
+    }
+
+    #[tracing::instrument(target = "perf", level = "debug", name = "Signed::on_commit", skip_all)]
+    fn on_commit(
+        &mut self,
+        commit: &gix::Commit,
+        _repo: &gix::Repository,
+    ) -> eyre::Result<Option<Observation>> {
+        let Some((signature, _)) = commit.signature()? else {
+            return Ok(None);
+        };
+        let signature = signature.trim_start();
+        let kind = ARMOR
+            .iter()
+            .find(|(armor, _)| signature.starts_with(armor.as_bytes()))
+            .map(|(_, kind)| *kind);
+        Ok(kind.map(|kind| Observation::Signed { kind }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use herostratus_tests::fixtures::repository;
+    use herostratus_tests::fixtures::signatures::{PGP_SIGNATURE, SSH_SIGNATURE, X509_SIGNATURE};
+
+    use super::*;
+    use crate::observer::impls::test_helpers::observe_all;
+
+    #[test]
+    fn unsigned_commit() {
+        let repo = repository::Builder::new()
+            .commit("Unsigned")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, SignedObserver);
+        assert!(observations.is_empty());
+    }
+
+    #[test]
+    fn signature_kinds() {
+        let repo = repository::Builder::new()
+            .commit("PGP")
+            .gpgsig(PGP_SIGNATURE)
+            .commit("SSH")
+            .gpgsig(SSH_SIGNATURE)
+            .commit("X.509")
+            .gpgsig(X509_SIGNATURE)
+            .commit("Unsigned")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, SignedObserver);
+        assert_eq!(
+            observations,
+            [
+                Observation::Signed {
+                    kind: SignatureKind::Gpg
+                },
+                Observation::Signed {
+                    kind: SignatureKind::Ssh
+                },
+                Observation::Signed {
+                    kind: SignatureKind::X509
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_signature_format() {
+        let repo = repository::Builder::new()
+            .commit("Signed with something else")
+            .gpgsig("-----BEGIN FOO SIGNATURE-----\nabc\n-----END FOO SIGNATURE-----")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, SignedObserver);
+        assert!(observations.is_empty());
+    }
+}
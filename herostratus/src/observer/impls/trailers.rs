@@ -0,0 +1,120 @@
+use std::mem::Discriminant;
+
+use crate::observer::observation::{Observation, Trailer};
+use crate::observer::observer::Observer;
+use crate::observer::observer_factory::ObserverFactory;
+
+/// Emits [Observation::Trailers] when the commit message ends with a block of trailers, like
+/// `Signed-off-by:` or `Fixes:`.
+#[derive(Default)]
+pub struct TrailersObserver;
+
+inventory::submit!(ObserverFactory::new::<TrailersObserver>());
+
+impl Observer for TrailersObserver {
+    fn emits(&self) -> Discriminant<Observation> {
+        Observation::TRAILERS
+    }
+
+    #[tracing::instrument(
+        target = "perf",
+        level = "debug",
+        name = "Trailers::on_commit",
+        skip_all
+    )]
+    fn on_commit(
+        &mut self,
+        commit: &gix::Commit,
+        _repo: &gix::Repository,
+    ) -> eyre::Result<Option<Observation>> {
+        let msg = commit.message()?;
+        let Some(body) = msg.body() else {
+            return Ok(None);
+        };
+        let trailers: Vec<Trailer> = body
+            .trailers()
+            .map(|t| Trailer {
+                token: t.token.to_string(),
+                value: t.value.to_string(),
+            })
+            .collect();
+
+        if trailers.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Observation::Trailers { trailers }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use herostratus_tests::fixtures::repository;
+
+    use super::*;
+    use crate::observer::impls::test_helpers::observe_all;
+
+    fn trailer(token: &str, value: &str) -> Trailer {
+        Trailer {
+            token: token.to_string(),
+            value: value.to_string(),
+        }
+    }
+
+    #[test]
+    fn no_body() {
+        let repo = repository::Builder::new()
+            .commit("Subject only")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, TrailersObserver);
+        assert!(observations.is_empty());
+    }
+
+    #[test]
+    fn body_without_trailers() {
+        let repo = repository::Builder::new()
+            .commit("Subject")
+            .body("Just a description of the change.")
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, TrailersObserver);
+        assert!(observations.is_empty());
+    }
+
+    #[test]
+    fn trailers_in_order() {
+        let repo = repository::Builder::new()
+            .commit("Fix the frobnicator")
+            .body(
+                "It was broken.\n\n\
+                 Fixes: 0123456789ab (\"Add the frobnicator\")\n\
+                 Reviewed-by: Bob <bob@example.com>\n\
+                 Signed-off-by: Alice <alice@example.com>",
+            )
+            .build()
+            .unwrap();
+        let observations = observe_all(&repo, TrailersObserver);
+        assert_eq!(
+            observations,
+            [Observation::Trailers {
+                trailers: vec![
+                    trailer("Fixes", "0123456789ab (\"Add the frobnicator\")"),
+                    trailer("Reviewed-by", "Bob <bob@example.com>"),
+                    trailer("Signed-off-by", "Alice <alice@example.com>"),
+                ]
+            }]
+        );
+    }
+
+    #[test]
+    fn trailer_identity() {
+        let t = trailer("signed-off-by", "Alice Example <alice@example.com>");
+        assert!(t.is("Signed-off-by"));
+        assert_eq!(
+            t.identity(),
+            Some(("Alice Example".to_string(), "alice@example.com".to_string()))
+        );
+        assert_eq!(trailer("Fixes", "0123456789ab").identity(), None);
+    }
+}
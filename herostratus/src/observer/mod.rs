@@ -12,9 +12,9 @@ mod impls;
 #[cfg(test)]
 mod test_observers;
 
-pub use commit_context::CommitContext;
-pub use observation::Observation;
-pub use observer::{DiffAction, Observer};
+pub use commit_context::{CoAuthor, CommitContext};
+pub use observation::{Observation, SignatureKind, Trailer};
+pub use observer::{DiffAction, LineCounts, Observer};
 pub use observer_data::ObserverData;
-pub use observer_engine::ObserverEngine;
+pub use observer_engine::{EngineCommand, ObserverEngine};
 pub use observer_factory::{ObserverFactory, builtin_observers};
@@ -4,8 +4,12 @@ use std::mem::{Discriminant, discriminant};
 ///
 /// Observations carry only the extracted fact. Commit metadata (oid, author) is carried separately
 /// by [CommitContext](super::CommitContext).
+///
+/// Serializes as a JSON object tagged with the snake_case variant name (e.g. `{"type": "subject",
+/// "subject": "..."}`) for [external rules](crate::rules::external).
 #[non_exhaustive]
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum Observation {
     /// The commit subject line starts with a fixup/squash/amend/WIP/TODO/FIXME/DROPME prefix.
     Fixup,
@@ -44,11 +48,62 @@ pub enum Observation {
     /// root commits (count == 0) and ordinary commits (count == 1).
     ParentCount { count: usize },
 
+    /// The trailers at the end of the commit message, like `Signed-off-by:` and `Reviewed-by:`, in
+    /// the order they appear. Tokens are kept as written, so compare them case-insensitively.
+    Trailers { trailers: Vec<Trailer> },
+
+    /// The commit carries a signature in its `gpgsig` header. The signature isn't verified.
+    Signed { kind: SignatureKind },
+
+    /// How big the commit's diff is. Renamed files only count the lines that changed, and binary
+    /// files count as changed files without any lines. Merge commits and empty commits are excluded.
+    DiffStats {
+        lines_added: u64,
+        lines_removed: u64,
+        files_changed: u64,
+    },
+
     /// Test-only variant for use in unit tests.
     #[cfg(test)]
     Dummy,
 }
 
+/// A single `Token: value` trailer from a commit message.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct Trailer {
+    pub token: String,
+    pub value: String,
+}
+
+impl Trailer {
+    /// Whether this trailer's token is `token`, ignoring case
+    pub fn is(&self, token: &str) -> bool {
+        self.token.eq_ignore_ascii_case(token)
+    }
+
+    /// Parse the value as a `Name <email>` identity, like the value of a `Signed-off-by:` trailer
+    pub fn identity(&self) -> Option<(String, String)> {
+        let identity = gix::actor::IdentityRef::from_bytes(self.value.as_bytes()).ok()?;
+        let identity = identity.trim();
+        if identity.email.is_empty() {
+            return None;
+        }
+        Some((identity.name.to_string(), identity.email.to_string()))
+    }
+}
+
+/// The format of a commit signature, as told by its armor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureKind {
+    /// An OpenPGP signature, as made by `gpg`
+    Gpg,
+    /// An SSH signature, as made by `ssh-keygen -Y sign`
+    Ssh,
+    /// An X.509 (S/MIME) signature, as made by `gpgsm`
+    X509,
+}
+
 impl Observation {
     pub const FIXUP: Discriminant<Self> = discriminant(&Observation::Fixup);
     pub const SUBJECT: Discriminant<Self> = {
@@ -81,9 +136,49 @@ impl Observation {
     pub const CI_CONFIG: Discriminant<Self> = discriminant(&Observation::CiConfig);
     pub const PARENT_COUNT: Discriminant<Self> =
         discriminant(&Observation::ParentCount { count: 0 });
+    pub const TRAILERS: Discriminant<Self> = {
+        let obs = Observation::Trailers {
+            trailers: Vec::new(),
+        };
+        let d = discriminant(&obs);
+        std::mem::forget(obs);
+        d
+    };
+    pub const SIGNED: Discriminant<Self> = discriminant(&Observation::Signed {
+        kind: SignatureKind::Gpg,
+    });
+    pub const DIFF_STATS: Discriminant<Self> = discriminant(&Observation::DiffStats {
+        lines_added: 0,
+        lines_removed: 0,
+        files_changed: 0,
+    });
 
     #[cfg(test)]
     pub const DUMMY: Discriminant<Self> = discriminant(&Observation::Dummy);
+
+    /// Look up a variant by its serialized snake_case name.
+    ///
+    /// Used by [external rules](crate::rules::external) to declare which observations they
+    /// consume.
+    pub fn discriminant_from_name(name: &str) -> Option<Discriminant<Self>> {
+        let d = match name {
+            "fixup" => Self::FIXUP,
+            "subject" => Self::SUBJECT,
+            "non_unicode_message" => Self::NON_UNICODE_MESSAGE,
+            "empty_commit" => Self::EMPTY_COMMIT,
+            "whitespace_only" => Self::WHITESPACE_ONLY,
+            "profanity" => Self::PROFANITY,
+            "quine_prefix" => Self::QUINE_PREFIX,
+            "hex_tokens" => Self::HEX_TOKENS,
+            "ci_config" => Self::CI_CONFIG,
+            "parent_count" => Self::PARENT_COUNT,
+            "trailers" => Self::TRAILERS,
+            "signed" => Self::SIGNED,
+            "diff_stats" => Self::DIFF_STATS,
+            _ => return None,
+        };
+        Some(d)
+    }
 }
 
 #[cfg(test)]
@@ -105,4 +200,43 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn discriminant_from_serialized_name() {
+        let observations = [
+            Observation::Fixup,
+            Observation::Subject {
+                subject: "subject".into(),
+            },
+            Observation::NonUnicodeMessage,
+            Observation::EmptyCommit,
+            Observation::WhitespaceOnly,
+            Observation::Profanity { words: Vec::new() },
+            Observation::QuinePrefix { matched_length: 7 },
+            Observation::HexTokens { tokens: Vec::new() },
+            Observation::CiConfig,
+            Observation::ParentCount { count: 1 },
+            Observation::Trailers {
+                trailers: Vec::new(),
+            },
+            Observation::Signed {
+                kind: SignatureKind::Ssh,
+            },
+            Observation::DiffStats {
+                lines_added: 1,
+                lines_removed: 2,
+                files_changed: 1,
+            },
+        ];
+        for obs in observations {
+            let json = serde_json::to_value(&obs).unwrap();
+            let name = json["type"].as_str().unwrap();
+            assert_eq!(
+                Observation::discriminant_from_name(name),
+                Some(discriminant(&obs)),
+                "{name}"
+            );
+        }
+        assert_eq!(Observation::discriminant_from_name("Subject"), None);
+    }
 }
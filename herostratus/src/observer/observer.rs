@@ -14,7 +14,9 @@ use super::observation::Observation;
 /// 1. [on_commit](Self::on_commit) -- called for every commit
 /// 2. If [is_interested_in_diff](Self::is_interested_in_diff) returns true:
 ///    1. [on_diff_start](Self::on_diff_start)
-///    2. [on_diff_change](Self::on_diff_change) for each change in the diff
+///    2. [on_diff_change](Self::on_diff_change) for each change in the diff, or
+///       [on_diff_line_counts](Self::on_diff_line_counts) if
+///       [is_interested_in_line_counts](Self::is_interested_in_line_counts) returns true
 ///    3. [on_diff_end](Self::on_diff_end) -- always called, regardless of errors or
 ///       [DiffAction::Cancel]
 ///
@@ -42,6 +44,16 @@ pub trait Observer: Send {
         false
     }
 
+    /// Whether this observer needs the number of lines each change added and removed. Default:
+    /// false.
+    ///
+    /// Counting lines means a blob diff of every changed file, so the engine only does it when an
+    /// observer asks. Observers that return true must also be
+    /// [interested in the diff](Self::is_interested_in_diff).
+    fn is_interested_in_line_counts(&self) -> bool {
+        false
+    }
+
     /// Called for every commit. Returns zero or one observations.
     fn on_commit(
         &mut self,
@@ -65,6 +77,21 @@ pub trait Observer: Send {
         Ok(DiffAction::Cancel)
     }
 
+    /// Called for each file-level change in the diff instead of
+    /// [on_diff_change](Self::on_diff_change), for observers interested in line counts.
+    ///
+    /// `counts` is `None` for changes without lines, like binary files, directories, and submodules.
+    ///
+    /// Not called for the boundary commit of a shallow clone, whose parent is missing, since every
+    /// file would look to have been added by it.
+    fn on_diff_line_counts(
+        &mut self,
+        _change: &gix::object::tree::diff::ChangeDetached,
+        _counts: Option<LineCounts>,
+    ) -> eyre::Result<DiffAction> {
+        Ok(DiffAction::Cancel)
+    }
+
     /// Called once after all diff changes, regardless of errors or [DiffAction::Cancel].
     ///
     /// Returns zero or one observations summarizing the diff.
@@ -73,6 +100,15 @@ pub trait Observer: Send {
     }
 }
 
+/// How many lines a single file change added and removed.
+///
+/// A rename only counts the lines that changed between the old and new file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineCounts {
+    pub added: u64,
+    pub removed: u64,
+}
+
 /// Controls whether the observer engine continues sending diff changes to an observer.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DiffAction {
@@ -1,15 +1,25 @@
 use std::collections::HashSet;
 use std::mem::Discriminant;
+use std::sync::mpsc;
 
 use eyre::WrapErr;
 use rayon::prelude::*;
 
-use super::commit_context::CommitContext;
+use super::commit_context::{CoAuthor, CommitContext};
 use super::observation::Observation;
-use super::observer::{DiffAction, Observer};
+use super::observer::{DiffAction, LineCounts, Observer};
 use super::observer_data::ObserverData;
 use crate::git::mailmap::MailmapResolver;
 
+/// A request for an [ObserverEngine] that's serving commands with [ObserverEngine::run]
+#[derive(Debug)]
+pub enum EngineCommand {
+    /// Process this commit, and send back its [ObserverData]
+    Process(gix::ObjectId),
+    /// Apply [ObserverEngine::retire_all_except] before processing any later commits
+    RetireAllExcept(HashSet<Discriminant<Observation>>),
+}
+
 /// Runs [Observer]s against commits in a repository, producing [ObserverData] messages.
 ///
 /// For each commit, the engine resolves the author via the mailmap, calls each observer's
@@ -44,6 +54,9 @@ use crate::git::mailmap::MailmapResolver;
 /// For diffs, changes are collected into owned
 /// [ChangeDetached](gix::object::tree::diff::ChangeDetached) form first, then each observer
 /// processes the full set of changes independently in parallel.
+///
+/// The engine is `Send`, so the whole engine can also run on its own thread with
+/// [ObserverEngine::run], observing upcoming commits while the rules process earlier ones.
 pub struct ObserverEngine {
     // Shared handle used to create thread-local Repository instances. Because ObserverEngine
     // owns this instead of borrowing &'repo gix::Repository, Pipeline does not hold a lifetime
@@ -114,15 +127,33 @@ impl ObserverEngine {
                 .wrap_err_with(|| format!("Failed to find commit {oid}"))?;
 
             let author = self.mailmap.resolve_author(&commit)?;
+            let co_authors = self
+                .mailmap
+                .resolve_co_authors(&commit)?
+                .into_iter()
+                .map(|sig| CoAuthor {
+                    name: sig.name.to_string(),
+                    email: sig.email.to_string(),
+                })
+                .collect();
             let committer = commit.committer()?;
             let committer_time = committer.time()?;
             let commit_timestamp =
                 chrono::DateTime::from_timestamp(committer_time.seconds, 0).unwrap_or_default();
+            let author_time = commit.author()?.time()?;
+            let author_timestamp = chrono::FixedOffset::east_opt(author_time.offset)
+                .and_then(|offset| {
+                    chrono::DateTime::from_timestamp(author_time.seconds, 0)
+                        .map(|t| t.with_timezone(&offset))
+                })
+                .unwrap_or_default();
             CommitContext {
                 oid,
                 author_name: author.name.to_string(),
                 author_email: author.email.to_string(),
                 commit_timestamp,
+                author_timestamp,
+                co_authors,
             }
         };
         self.num_commits_processed += 1;
@@ -178,21 +209,26 @@ impl ObserverEngine {
         Ok(data)
     }
 
-    /// Process all commits, sending [ObserverData] through the channel.
+    /// Serve [EngineCommand]s in order, sending each processed commit's [ObserverData] through
+    /// the channel.
     ///
-    /// Stops gracefully (returns `Ok`) if the receiver is dropped.
-    #[cfg(test)]
+    /// This is how the engine runs ahead of the [RuleEngine](crate::rules::RuleEngine) on its own
+    /// thread. Returns when the commands run out, or gracefully (`Ok`) if the receiver is dropped.
+    /// Infrastructure errors stop processing and are returned, which closes the channel.
     pub fn run(
         &mut self,
-        oids: impl IntoIterator<Item = gix::ObjectId>,
-        tx: &std::sync::mpsc::Sender<ObserverData>,
+        commands: impl IntoIterator<Item = EngineCommand>,
+        tx: &mpsc::SyncSender<Vec<ObserverData>>,
     ) -> eyre::Result<()> {
-        for oid in oids {
-            let messages = self.process_commit(oid)?;
-            for msg in messages {
-                if tx.send(msg).is_err() {
-                    return Ok(());
+        for command in commands {
+            match command {
+                EngineCommand::Process(oid) => {
+                    let messages = self.process_commit(oid)?;
+                    if tx.send(messages).is_err() {
+                        return Ok(());
+                    }
                 }
+                EngineCommand::RetireAllExcept(needed) => self.retire_all_except(&needed),
             }
         }
         Ok(())
@@ -206,7 +242,9 @@ impl ObserverEngine {
     /// observers can maintain consistent internal state.
     ///
     /// Changes are collected into owned [ChangeDetached](gix::object::tree::diff::ChangeDetached)
-    /// form, then each observer processes the full set independently in parallel.
+    /// form, then each observer processes the full set independently in parallel. If any observer
+    /// is [interested in line counts](Observer::is_interested_in_line_counts), every change is
+    /// line-diffed through the diff cache first.
     #[tracing::instrument(target = "perf", skip_all)]
     fn diff_commit(&mut self, oid: gix::ObjectId) -> eyre::Result<Vec<Observation>> {
         for observer in &mut self.observers {
@@ -260,23 +298,39 @@ impl ObserverEngine {
         let commit_tree = commit
             .tree()
             .wrap_err_with(|| format!("Failed to get tree for commit {oid}"))?;
+        let mut parent_missing = false;
         let parent_tree = match parent_oid {
             Some(pid) => match repo.find_commit(pid) {
                 Ok(parent) => parent
                     .tree()
                     .wrap_err_with(|| format!("Failed to get tree for parent commit {pid}"))?,
                 // Shallow clone -- parent commit is missing, so diff against empty tree.
-                Err(_) => repo.empty_tree(),
+                Err(_) => {
+                    parent_missing = true;
+                    repo.empty_tree()
+                }
             },
             // Root commit -- no parent, so diff against empty tree.
             None => repo.empty_tree(),
         };
 
+        // Against the empty tree, the shallow boundary commit would appear to add every line in
+        // the repository, so line-count observers don't see its changes at all.
+        let any_line_count_observer = !parent_missing
+            && self
+                .observers
+                .iter()
+                .any(|o| o.is_interested_in_diff() && o.is_interested_in_line_counts());
+
         let mut changes_iter = parent_tree
             .changes()
             .wrap_err("Failed to create tree changes iterator")?;
+        // Rename detection is only worth its cost when counting lines, so that a moved file counts
+        // as a single Rewrite rather than a Deletion and an Addition. Use git's defaults regardless
+        // of the repository's diff.renames.
+        let rewrites = any_line_count_observer.then(gix::diff::Rewrites::default);
         changes_iter.options(|o| {
-            o.track_rewrites(None);
+            o.track_rewrites(rewrites);
         });
 
         // Collect all changes into owned form so we can dispatch to observers in parallel.
@@ -300,6 +354,17 @@ impl ObserverEngine {
         }
         drop(guard);
 
+        let line_counts = if any_line_count_observer {
+            let _guard = tracing::info_span!(target: "perf", "OE::count_lines").entered();
+            changes
+                .iter()
+                .map(|change| count_lines(change, repo, diff_cache))
+                .collect::<eyre::Result<Vec<_>>>()
+                .wrap_err_with(|| format!("Failed to count lines changed by commit {oid}"))?
+        } else {
+            Vec::new()
+        };
+
         // Run each diff-interested observer over the collected changes in parallel.
         let _guard = tracing::info_span!(target: "perf", "OE::on_diff_changes").entered();
         let sync_repo = &self.sync_repo;
@@ -310,8 +375,19 @@ impl ObserverEngine {
             .filter(|obs| obs.is_interested_in_diff())
             .filter_map(|obs| {
                 let repo = tl_repo.get_or(|| sync_repo.to_thread_local());
-                for change in &changes {
-                    match obs.on_diff_change(change, repo) {
+                let wants_line_counts = obs.is_interested_in_line_counts();
+                let changes = if wants_line_counts && parent_missing {
+                    &[][..]
+                } else {
+                    &changes[..]
+                };
+                for (i, change) in changes.iter().enumerate() {
+                    let action = if wants_line_counts {
+                        obs.on_diff_line_counts(change, line_counts[i])
+                    } else {
+                        obs.on_diff_change(change, repo)
+                    };
+                    match action {
                         Ok(DiffAction::Cancel) => break,
                         Ok(DiffAction::Continue) => {}
                         Err(e) => {
@@ -354,6 +430,27 @@ impl ObserverEngine {
     }
 }
 
+/// Count the lines added and removed by a single change, using the engine's diff cache
+///
+/// Returns `None` for directories and submodules, which have no lines, and for binary files.
+fn count_lines(
+    change: &gix::object::tree::diff::ChangeDetached,
+    repo: &gix::Repository,
+    diff_cache: &mut gix::diff::blob::Platform,
+) -> eyre::Result<Option<LineCounts>> {
+    use gix::prelude::TreeDiffChangeExt;
+
+    if !change.entry_mode().is_blob_or_symlink() {
+        return Ok(None);
+    }
+    let mut platform = change.attach(repo, repo).diff(diff_cache)?;
+    let counts = platform.line_counts()?.map(|stats| LineCounts {
+        added: stats.insertions.into(),
+        removed: stats.removals.into(),
+    });
+    Ok(counts)
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::mpsc;
@@ -375,6 +472,10 @@ mod tests {
             author_email: "Herostratus@example.com".to_string(),
             // matches DEFAULT_TIME (1711656630) used by the repository::Builder fixture
             commit_timestamp: chrono::DateTime::from_timestamp(1711656630, 0).unwrap(),
+            author_timestamp: chrono::DateTime::from_timestamp(1711656630, 0)
+                .unwrap()
+                .fixed_offset(),
+            co_authors: Vec::new(),
         }
     }
 
@@ -543,6 +644,10 @@ mod tests {
             author_name: "Canonical Name".to_string(),
             author_email: "canonical@example.com".to_string(),
             commit_timestamp: chrono::DateTime::from_timestamp(1711656630, 0).unwrap(),
+            author_timestamp: chrono::DateTime::from_timestamp(1711656630, 0)
+                .unwrap()
+                .fixed_offset(),
+            co_authors: Vec::new(),
         };
         assert_eq!(
             data,
@@ -612,11 +717,12 @@ mod tests {
         let mut engine =
             ObserverEngine::new(&temp_repo.repo, observers, default_mailmap()).unwrap();
 
-        let (tx, rx) = mpsc::channel();
-        engine.run(vec![oid1, oid2], &tx).unwrap();
+        let (tx, rx) = mpsc::sync_channel(2);
+        let commands = [oid1, oid2].map(EngineCommand::Process);
+        engine.run(commands, &tx).unwrap();
         drop(tx);
 
-        let messages: Vec<_> = rx.iter().collect();
+        let messages: Vec<_> = rx.iter().flatten().collect();
         assert_eq!(
             messages,
             [
@@ -649,11 +755,54 @@ mod tests {
         let mut engine =
             ObserverEngine::new(&temp_repo.repo, observers, default_mailmap()).unwrap();
 
-        let (tx, rx) = mpsc::channel();
+        let (tx, rx) = mpsc::sync_channel(0);
         drop(rx);
 
         // Should not error even though the receiver is dropped
-        engine.run(oids, &tx).unwrap();
+        engine
+            .run(oids.into_iter().map(EngineCommand::Process), &tx)
+            .unwrap();
+    }
+
+    #[test]
+    fn run_retires_observers_between_commits() {
+        let temp_repo = repository::Builder::new()
+            .commit("first")
+            .commit("second")
+            .build()
+            .unwrap();
+
+        let oid1 = crate::git::rev::parse("HEAD~1", &temp_repo.repo).unwrap();
+        let oid2 = crate::git::rev::parse("HEAD", &temp_repo.repo).unwrap();
+
+        let observers: Vec<Box<dyn Observer>> = vec![Box::new(AlwaysObserver)];
+        let mut engine =
+            ObserverEngine::new(&temp_repo.repo, observers, default_mailmap()).unwrap();
+
+        let (tx, rx) = mpsc::sync_channel(2);
+        let commands = [
+            EngineCommand::Process(oid1),
+            EngineCommand::RetireAllExcept(HashSet::new()),
+            EngineCommand::Process(oid2),
+        ];
+        engine.run(commands, &tx).unwrap();
+        drop(tx);
+
+        let messages: Vec<_> = rx.iter().collect();
+        assert_eq!(
+            messages,
+            [
+                vec![
+                    ObserverData::CommitStart(default_ctx(oid1)),
+                    ObserverData::Observation(Observation::Dummy),
+                    ObserverData::CommitComplete,
+                ],
+                vec![
+                    ObserverData::CommitStart(default_ctx(oid2)),
+                    ObserverData::CommitComplete,
+                ],
+            ]
+        );
     }
 
     #[test]
@@ -0,0 +1,372 @@
+//! Rules that are not compiled into Herostratus
+//!
+//! External rules speak a line-oriented JSON protocol. Herostratus sends one [Request] per line,
+//! and the rule answers each with exactly one [Response] line. See `docs/user/external-rules.md`.
+
+mod script;
+#[cfg(feature = "wasm-rules")]
+mod wasm;
+
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::mem::Discriminant;
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use serde::{Deserialize, Serialize};
+
+pub use self::script::{ScriptRule, ScriptRuleConfig};
+#[cfg(feature = "wasm-rules")]
+pub use self::wasm::WasmRule;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+
+/// A message sent to an external rule
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request<'a> {
+    /// The rule's cache from the previous run, or `null` if there is none
+    InitCache {
+        cache: &'a serde_json::Value,
+    },
+    CommitStart {
+        commit: &'a CommitContext,
+    },
+    Observation {
+        observation: &'a Observation,
+    },
+    CommitComplete,
+    Finalize,
+    /// Ask the rule for its cache, to be saved for the next run
+    FiniCache,
+}
+
+/// An external rule's answer to a [Request]
+///
+/// Every field is optional, so `{}` is always a valid response.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Response {
+    #[serde(default)]
+    pub grants: Vec<GrantSpec>,
+    #[serde(default)]
+    pub cache: serde_json::Value,
+}
+
+impl Response {
+    /// The grant in response to [Request::Observation] or [Request::CommitComplete]
+    pub fn single_grant(self, meta: &Meta, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let mut grants = self.grants.into_iter();
+        let grant = grants.next();
+        if grants.next().is_some() {
+            tracing::warn!(
+                rule = meta.human_id,
+                "Ignoring extra grants; only one grant may be made per observation or commit"
+            );
+        }
+        grant.map(|g| g.into_grant(meta, Some(ctx))).transpose()
+    }
+
+    /// The grants in response to [Request::Finalize]
+    pub fn all_grants(self, meta: &Meta) -> eyre::Result<Vec<Grant>> {
+        self.grants
+            .into_iter()
+            .map(|g| g.into_grant(meta, None))
+            .collect()
+    }
+}
+
+/// A [Grant] as returned by an external rule
+///
+/// Missing fields default to the commit currently being processed, as in [Meta::grant].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GrantSpec {
+    pub commit: Option<String>,
+    pub user_name: Option<String>,
+    pub user_email: Option<String>,
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+impl GrantSpec {
+    /// Convert to a [Grant], filling in any missing fields from `ctx`.
+    ///
+    /// Grants from [Request::Finalize] have no current commit, so they must be fully specified.
+    pub fn into_grant(self, meta: &Meta, ctx: Option<&CommitContext>) -> eyre::Result<Grant> {
+        let mut grant = match ctx {
+            Some(ctx) => meta.grant(ctx),
+            None => {
+                let (Some(_), Some(user_name), Some(user_email), Some(timestamp)) = (
+                    &self.commit,
+                    self.user_name.clone(),
+                    self.user_email.clone(),
+                    self.timestamp,
+                ) else {
+                    eyre::bail!(
+                        "grants outside of a commit must set commit, user_name, user_email, and timestamp"
+                    );
+                };
+                Grant {
+                    commit: gix::ObjectId::null(gix::hash::Kind::Sha1),
+                    user_name,
+                    user_email,
+                    timestamp,
+                    name_override: None,
+                    description_override: None,
+                }
+            }
+        };
+
+        if let Some(commit) = self.commit {
+            grant.commit = gix::ObjectId::from_hex(commit.as_bytes())?;
+        }
+        if let Some(user_name) = self.user_name {
+            grant.user_name = user_name;
+        }
+        if let Some(user_email) = self.user_email {
+            grant.user_email = user_email;
+        }
+        if let Some(timestamp) = self.timestamp {
+            grant.timestamp = timestamp;
+        }
+        grant.name_override = self.name;
+        grant.description_override = self.description;
+        Ok(grant)
+    }
+}
+
+/// The [AchievementKind] of an external rule, as spelled in `config.toml`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum KindConfig {
+    PerUser,
+    PerUserRepeat,
+    Global,
+    GlobalRevocable,
+}
+
+impl From<KindConfig> for AchievementKind {
+    fn from(kind: KindConfig) -> Self {
+        match kind {
+            KindConfig::PerUser => AchievementKind::PerUser { recurrent: false },
+            KindConfig::PerUserRepeat => AchievementKind::PerUser { recurrent: true },
+            KindConfig::Global => AchievementKind::Global { revocable: false },
+            KindConfig::GlobalRevocable => AchievementKind::Global { revocable: true },
+        }
+    }
+}
+
+/// Leak `value` with `leak`, unless an equal value was already leaked
+///
+/// External rules are rebuilt whenever the rules are, so this keeps the leaked memory bounded by
+/// the config rather than growing with every rebuild.
+fn intern<T: ?Sized + Eq + Hash>(
+    interned: &Mutex<HashSet<&'static T>>,
+    value: &T,
+    leak: impl FnOnce() -> &'static T,
+) -> &'static T {
+    let mut interned = interned.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(leaked) = interned.get(value) {
+        return leaked;
+    }
+    let leaked = leak();
+    interned.insert(leaked);
+    leaked
+}
+
+fn intern_str(value: &str) -> &'static str {
+    static STRINGS: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+    intern(&STRINGS, value, || Box::leak(value.into()))
+}
+
+/// Build a [Meta] from runtime strings.
+///
+/// [Meta] borrows `'static` strings because builtin rules declare theirs as constants. External
+/// rules only have a handful of strings, so [interning](intern) them is cheaper than making every
+/// builtin rule pay for owned strings.
+fn leak_meta(
+    id: usize,
+    human_id: &str,
+    name: &str,
+    description: &str,
+    kind: AchievementKind,
+) -> eyre::Result<Meta> {
+    if human_id.is_empty() || !human_id.chars().all(|c| c.is_ascii_lowercase() || c == '-') {
+        eyre::bail!("rule human ID {human_id:?} must only contain lowercase letters and hyphens");
+    }
+    Ok(Meta {
+        id,
+        human_id: intern_str(human_id),
+        name: intern_str(name),
+        description: intern_str(description),
+        kind,
+        // Configurable with rules.points, like any other rule
+        points: Meta::DEFAULT_POINTS,
+    })
+}
+
+/// Look up the observation variants named by an external rule, interning the resulting slice for
+/// the same reason as [leak_meta].
+fn leak_consumes(names: &[String]) -> eyre::Result<&'static [Discriminant<Observation>]> {
+    let consumes = names
+        .iter()
+        .map(|name| {
+            Observation::discriminant_from_name(name)
+                .ok_or_else(|| eyre::eyre!("unknown observation {name:?}"))
+        })
+        .collect::<eyre::Result<Vec<_>>>()?;
+    type Consumes = [Discriminant<Observation>];
+    static CONSUMES: LazyLock<Mutex<HashSet<&'static Consumes>>> = LazyLock::new(Default::default);
+    Ok(intern(&CONSUMES, &consumes[..], || {
+        Box::leak(consumes.clone().into_boxed_slice())
+    }))
+}
+
+/// Build every external rule declared in the config.
+///
+/// External rules that fail validation, or whose ID collides with another rule, are skipped with
+/// an error log rather than failing the whole run.
+pub(crate) fn external_rules(
+    config: &crate::config::RulesConfig,
+    taken_ids: &mut Vec<usize>,
+) -> Vec<Box<dyn super::RulePlugin>> {
+    let mut rules: Vec<Box<dyn super::RulePlugin>> = Vec::new();
+    for (human_id, script) in config.scripts.iter().flatten() {
+        if taken_ids.contains(&script.id) {
+            tracing::error!(
+                "Skipping script rule {human_id:?}: ID {} is already used",
+                script.id
+            );
+            continue;
+        }
+        match ScriptRule::new(human_id, script) {
+            Ok(rule) => {
+                taken_ids.push(script.id);
+                rules.push(Box::new(rule));
+            }
+            Err(e) => tracing::error!("Skipping script rule {human_id:?} because: '{e:?}'"),
+        }
+    }
+    for path in config.wasm.iter().flatten() {
+        match load_wasm_rule(path, config.wasm_fuel) {
+            Ok(rule) if taken_ids.contains(&rule.meta().id) => tracing::error!(
+                "Skipping WASM rule {path:?}: ID {} is already used",
+                rule.meta().id
+            ),
+            Ok(rule) => {
+                taken_ids.push(rule.meta().id);
+                rules.push(rule);
+            }
+            Err(e) => tracing::error!("Skipping WASM rule {path:?} because: '{e:?}'"),
+        }
+    }
+    rules
+}
+
+#[cfg(feature = "wasm-rules")]
+fn load_wasm_rule(
+    path: &std::path::Path,
+    fuel: Option<u64>,
+) -> eyre::Result<Box<dyn super::RulePlugin>> {
+    let fuel = fuel.unwrap_or(wasm::DEFAULT_FUEL);
+    Ok(Box::new(WasmRule::load(path, fuel)?))
+}
+
+#[cfg(not(feature = "wasm-rules"))]
+fn load_wasm_rule(
+    _path: &std::path::Path,
+    _fuel: Option<u64>,
+) -> eyre::Result<Box<dyn super::RulePlugin>> {
+    eyre::bail!("Herostratus was built without the 'wasm-rules' feature")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const META: Meta = Meta {
+        id: 1000,
+        human_id: "test",
+        name: "Test",
+        description: "A test achievement",
+        kind: AchievementKind::PerUser { recurrent: false },
+        points: Meta::DEFAULT_POINTS,
+    };
+
+    #[test]
+    fn grant_spec_defaults_to_current_commit() {
+        let ctx = CommitContext::test("Alice");
+        let grant = GrantSpec::default().into_grant(&META, Some(&ctx)).unwrap();
+        assert_eq!(grant.commit, ctx.oid);
+        assert_eq!(grant.user_email, "alice@example.com");
+        assert_eq!(grant.name_override, None);
+
+        let spec: GrantSpec =
+            serde_json::from_str(r#"{"user_name": "Bob", "name": "Test (Bob)"}"#).unwrap();
+        let grant = spec.into_grant(&META, Some(&ctx)).unwrap();
+        assert_eq!(grant.user_name, "Bob");
+        assert_eq!(grant.user_email, "alice@example.com");
+        assert_eq!(grant.name_override.as_deref(), Some("Test (Bob)"));
+    }
+
+    #[test]
+    fn grant_spec_without_commit_must_be_complete() {
+        let spec: GrantSpec = serde_json::from_str(r#"{"user_name": "Bob"}"#).unwrap();
+        assert!(spec.into_grant(&META, None).is_err());
+
+        let spec: GrantSpec = serde_json::from_str(
+            r#"{
+                "commit": "e83c5163316f89bfbde7d9ab23ca2e25604af290",
+                "user_name": "Bob",
+                "user_email": "bob@example.com",
+                "timestamp": "2024-01-01T00:00:00Z"
+            }"#,
+        )
+        .unwrap();
+        let grant = spec.into_grant(&META, None).unwrap();
+        assert_eq!(
+            grant.commit.to_string(),
+            "e83c5163316f89bfbde7d9ab23ca2e25604af290"
+        );
+    }
+
+    #[test]
+    fn request_wire_format() {
+        let ctx = CommitContext::test("Alice");
+        let line = serde_json::to_string(&Request::CommitStart { commit: &ctx }).unwrap();
+        assert_eq!(
+            line,
+            r#"{"type":"commit_start","commit":{"oid":"0000000000000000000000000000000000000000","author_name":"Alice","author_email":"alice@example.com","commit_timestamp":"1970-01-01T00:00:00Z","author_timestamp":"1970-01-01T00:00:00Z","co_authors":[]}}"#
+        );
+
+        let obs = Observation::ParentCount { count: 2 };
+        let line = serde_json::to_string(&Request::Observation { observation: &obs }).unwrap();
+        assert_eq!(
+            line,
+            r#"{"type":"observation","observation":{"type":"parent_count","count":2}}"#
+        );
+    }
+
+    #[test]
+    fn invalid_human_id() {
+        let kind = AchievementKind::Global { revocable: false };
+        assert!(leak_meta(1000, "Inside Joke", "Joke", "An inside joke", kind).is_err());
+        assert!(leak_meta(1000, "inside-joke", "Joke", "An inside joke", kind).is_ok());
+    }
+
+    #[test]
+    fn rebuilding_reuses_leaked_metadata() {
+        let kind = AchievementKind::Global { revocable: false };
+        let first = leak_meta(1000, "inside-joke", "Joke", "An inside joke", kind).unwrap();
+        let second = leak_meta(1000, "inside-joke", "Joke", "An inside joke", kind).unwrap();
+        assert!(std::ptr::eq(first.name, second.name));
+        assert!(std::ptr::eq(first.description, second.description));
+
+        let names = ["subject".to_string(), "empty_commit".to_string()];
+        let first = leak_consumes(&names).unwrap();
+        let second = leak_consumes(&names).unwrap();
+        assert!(std::ptr::eq(first, second));
+        assert_eq!(first, [Observation::SUBJECT, Observation::EMPTY_COMMIT]);
+    }
+}
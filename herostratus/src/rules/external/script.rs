@@ -0,0 +1,298 @@
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader, Write};
+use std::mem::{Discriminant, discriminant};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use eyre::WrapErr;
+use serde::{Deserialize, Serialize};
+
+use super::{KindConfig, Request, Response, leak_consumes, leak_meta};
+use crate::achievement::{Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::RulePlugin;
+
+/// Configuration for a rule implemented by an external program
+///
+/// Declared as `[rules.scripts.<human-id>]` in `config.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ScriptRuleConfig {
+    /// The rule ID. Must not collide with any builtin rule.
+    pub id: usize,
+    pub name: String,
+    pub description: String,
+    pub kind: KindConfig,
+    /// The program and its arguments
+    pub command: Vec<String>,
+    /// The working directory for the program. Defaults to Herostratus's working directory.
+    pub working_dir: Option<PathBuf>,
+    /// The snake_case names of the [Observation]s to send to the program
+    pub consumes: Vec<String>,
+    /// Bump this to discard the rule's cache and previous grants, and re-process all history
+    #[serde(default = "default_version")]
+    pub version: u32,
+}
+
+fn default_version() -> u32 {
+    1
+}
+
+/// How long a script rule has to exit after its stdin is closed, before it's killed
+const EXIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The spawned program behind a [ScriptRule]
+struct ScriptProcess {
+    child: Child,
+    /// Only `None` while dropping
+    stdin: Option<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    exit_timeout: Duration,
+}
+
+impl ScriptProcess {
+    fn spawn(config: &ScriptRuleConfig) -> eyre::Result<Self> {
+        let Some((program, args)) = config.command.split_first() else {
+            eyre::bail!("script rule command is empty");
+        };
+        let mut cmd = Command::new(program);
+        cmd.args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit());
+        if let Some(dir) = &config.working_dir {
+            cmd.current_dir(dir);
+        }
+        tracing::debug!("Spawning script rule {:?}", config.command);
+        let mut child = cmd
+            .spawn()
+            .wrap_err(format!("failed to spawn {:?}", config.command))?;
+        let stdin = child.stdin.take();
+        let stdout = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        Ok(Self {
+            child,
+            stdin,
+            stdout,
+            exit_timeout: EXIT_TIMEOUT,
+        })
+    }
+
+    fn call(&mut self, request: &Request) -> eyre::Result<Response> {
+        let mut line = serde_json::to_string(request)?;
+        line.push('\n');
+        let stdin = self.stdin.as_mut().expect("stdin is piped");
+        stdin
+            .write_all(line.as_bytes())
+            .wrap_err("failed to write to script rule")?;
+        stdin.flush()?;
+
+        let mut line = String::new();
+        if self.stdout.read_line(&mut line)? == 0 {
+            eyre::bail!("script rule exited before responding");
+        }
+        serde_json::from_str(&line).wrap_err(format!("invalid script rule response: {line:?}"))
+    }
+}
+
+impl Drop for ScriptProcess {
+    fn drop(&mut self) {
+        // Closing stdin is the signal for the script to exit. Give it the chance to do so cleanly,
+        // so that it doesn't outlive Herostratus, but don't hang on one that ignores EOF.
+        drop(self.stdin.take());
+        let deadline = Instant::now() + self.exit_timeout;
+        loop {
+            match self.child.try_wait() {
+                Ok(Some(status)) if !status.success() => {
+                    tracing::warn!("script rule exited with {status}");
+                    return;
+                }
+                Ok(Some(_)) => return,
+                Ok(None) if Instant::now() < deadline => {
+                    std::thread::sleep(Duration::from_millis(10))
+                }
+                Ok(None) => {
+                    tracing::warn!(
+                        "script rule didn't exit within {:?} of closing its stdin; killing it",
+                        self.exit_timeout
+                    );
+                    break;
+                }
+                Err(e) => {
+                    tracing::warn!("failed to wait on script rule because: '{e:?}'");
+                    break;
+                }
+            }
+        }
+        if let Err(e) = self.child.kill() {
+            tracing::warn!("failed to kill script rule because: '{e:?}'");
+        }
+        let _ = self.child.wait();
+    }
+}
+
+/// A [RulePlugin] that forwards everything to an external program over stdin/stdout
+///
+/// The program is spawned the first time it is needed, and kept alive for the whole pipeline
+/// run.
+pub struct ScriptRule {
+    meta: Meta,
+    consumes: &'static [Discriminant<Observation>],
+    config: ScriptRuleConfig,
+    process: RefCell<Option<ScriptProcess>>,
+}
+
+impl ScriptRule {
+    pub fn new(human_id: &str, config: &ScriptRuleConfig) -> eyre::Result<Self> {
+        let meta = leak_meta(
+            config.id,
+            human_id,
+            &config.name,
+            &config.description,
+            config.kind.into(),
+        )?;
+        let consumes = leak_consumes(&config.consumes)?;
+        Ok(Self {
+            meta,
+            consumes,
+            config: config.clone(),
+            process: RefCell::new(None),
+        })
+    }
+
+    fn call(&self, request: &Request) -> eyre::Result<Response> {
+        let mut process = self.process.borrow_mut();
+        if process.is_none() {
+            *process = Some(ScriptProcess::spawn(&self.config)?);
+        }
+        let process = process.as_mut().expect("process was just spawned");
+        process.call(request)
+    }
+}
+
+impl RulePlugin for ScriptRule {
+    fn has_cache(&self) -> bool {
+        // There's no way to know whether the script is stateful, so always give it the chance
+        true
+    }
+    fn version(&self) -> u32 {
+        self.config.version
+    }
+    fn init_cache(&mut self, cache: serde_json::Value) -> eyre::Result<()> {
+        self.call(&Request::InitCache { cache: &cache })?;
+        Ok(())
+    }
+    fn fini_cache(&self) -> eyre::Result<serde_json::Value> {
+        Ok(self.call(&Request::FiniCache)?.cache)
+    }
+
+    fn meta(&self) -> &Meta {
+        &self.meta
+    }
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        self.consumes
+    }
+    fn commit_start(&mut self, ctx: &CommitContext) -> eyre::Result<()> {
+        self.call(&Request::CommitStart { commit: ctx })?;
+        Ok(())
+    }
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        if !self.consumes.contains(&discriminant(obs)) {
+            return Ok(None);
+        }
+        let response = self.call(&Request::Observation { observation: obs })?;
+        response.single_grant(&self.meta, ctx)
+    }
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let response = self.call(&Request::CommitComplete)?;
+        response.single_grant(&self.meta, ctx)
+    }
+    fn finalize(&mut self) -> eyre::Result<Vec<Grant>> {
+        self.call(&Request::Finalize)?.all_grants(&self.meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Grants on every observation, and remembers how many it has granted across runs
+    const SCRIPT: &str = r#"
+        count=0
+        while read -r line; do
+            case "$line" in
+                *'"type":"init_cache"'*'"count":'*)
+                    count=$(echo "$line" | sed 's/.*"count":\([0-9]*\).*/\1/')
+                    echo '{}' ;;
+                *'"type":"observation"'*)
+                    count=$((count + 1))
+                    echo '{"grants":[{"name":"Counted"}]}' ;;
+                *'"type":"fini_cache"'*)
+                    echo "{\"cache\":{\"count\":$count}}" ;;
+                *)
+                    echo '{}' ;;
+            esac
+        done
+    "#;
+
+    fn config() -> ScriptRuleConfig {
+        ScriptRuleConfig {
+            id: 1000,
+            name: "Counter".into(),
+            description: "Counts empty commits".into(),
+            kind: KindConfig::PerUserRepeat,
+            command: vec!["sh".into(), "-c".into(), SCRIPT.into()],
+            working_dir: None,
+            consumes: vec!["empty_commit".into()],
+            version: 1,
+        }
+    }
+
+    #[test]
+    fn round_trips_grants_and_cache() {
+        let mut rule = ScriptRule::new("counter", &config()).unwrap();
+        assert_eq!(rule.consumes(), &[Observation::EMPTY_COMMIT]);
+        rule.init_cache(serde_json::json!({"count": 41})).unwrap();
+
+        let ctx = CommitContext::test("Alice");
+        rule.commit_start(&ctx).unwrap();
+        // Not subscribed, so the script never sees it
+        let grant = rule.process(&ctx, &Observation::Fixup).unwrap();
+        assert!(grant.is_none());
+        let grant = rule.process(&ctx, &Observation::EmptyCommit).unwrap();
+        let grant = grant.unwrap();
+        assert_eq!(grant.user_email, "alice@example.com");
+        assert_eq!(grant.name_override.as_deref(), Some("Counted"));
+        assert!(rule.commit_complete(&ctx).unwrap().is_none());
+        assert!(rule.finalize().unwrap().is_empty());
+
+        assert_eq!(rule.fini_cache().unwrap(), serde_json::json!({"count": 42}));
+    }
+
+    #[test]
+    fn unknown_observation_is_an_error() {
+        let mut config = config();
+        config.consumes = vec!["nonexistent".into()];
+        assert!(ScriptRule::new("counter", &config).is_err());
+    }
+
+    #[test]
+    fn missing_program_is_an_error() {
+        let mut config = config();
+        config.command = vec!["/this/program/does/not/exist".into()];
+        let mut rule = ScriptRule::new("counter", &config).unwrap();
+        let ctx = CommitContext::test("Alice");
+        assert!(rule.commit_start(&ctx).is_err());
+    }
+
+    #[test]
+    fn script_ignoring_eof_is_killed() {
+        let mut config = config();
+        config.command = vec!["sh".into(), "-c".into(), "exec sleep 60".into()];
+        let mut process = ScriptProcess::spawn(&config).unwrap();
+        process.exit_timeout = Duration::from_millis(100);
+
+        let start = Instant::now();
+        drop(process);
+        assert!(start.elapsed() < Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,258 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::mem::{Discriminant, discriminant};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, PoisonError};
+
+use eyre::WrapErr;
+use serde::Deserialize;
+use wasmi::{Engine, Linker, Memory, Module, Store, TrapCode, TypedFunc};
+
+use super::{KindConfig, Request, Response, leak_consumes, leak_meta};
+use crate::achievement::{Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::RulePlugin;
+
+/// The metadata a WASM module returns from `herostratus_meta`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WasmMeta {
+    id: usize,
+    human_id: String,
+    name: String,
+    description: String,
+    kind: KindConfig,
+    consumes: Vec<String>,
+}
+
+/// Split a `(ptr << 32) | len` pair returned by the module
+fn unpack(packed: i64) -> (i32, i32) {
+    let packed = packed as u64;
+    ((packed >> 32) as i32, packed as u32 as i32)
+}
+
+/// The fuel each call into a module may use, when `rules.wasm_fuel` isn't set
+///
+/// Roughly one unit is used per instruction executed.
+pub const DEFAULT_FUEL: u64 = 1_000_000_000;
+
+/// An instantiated WASM module and the exports Herostratus calls
+struct WasmInstance {
+    store: Store<()>,
+    /// The fuel each call may use before it's stopped, so that a runaway module can't hang the run
+    fuel: u64,
+    memory: Memory,
+    alloc: TypedFunc<i32, i32>,
+    dealloc: TypedFunc<(i32, i32), ()>,
+    call: TypedFunc<(i32, i32), i64>,
+}
+
+/// The engine every module is compiled with, so that compiled modules can be shared
+static ENGINE: LazyLock<Engine> = LazyLock::new(|| {
+    let mut config = wasmi::Config::default();
+    config.consume_fuel(true);
+    Engine::new(&config)
+});
+
+/// Read and compile the module at `path`, or reuse the module compiled by a previous call
+///
+/// The rules are rebuilt by every caller that needs their metadata, so without this each rebuild
+/// would recompile every module.
+fn compile(path: &Path) -> eyre::Result<Module> {
+    static MODULES: LazyLock<Mutex<HashMap<PathBuf, Module>>> = LazyLock::new(Default::default);
+    let mut modules = MODULES.lock().unwrap_or_else(PoisonError::into_inner);
+    if let Some(module) = modules.get(path) {
+        return Ok(module.clone());
+    }
+    let bytes = std::fs::read(path).wrap_err(format!("failed to read {path:?}"))?;
+    let module = Module::new(&ENGINE, bytes)?;
+    modules.insert(path.to_path_buf(), module.clone());
+    Ok(module)
+}
+
+impl WasmInstance {
+    fn load(path: &Path, fuel: u64) -> eyre::Result<(Self, WasmMeta, u32)> {
+        let module = compile(path)?;
+        let engine = module.engine();
+        let mut store = Store::new(engine, ());
+        store.set_fuel(fuel)?;
+        // Plugins get no imports; they can only see what they're sent
+        let linker = Linker::<()>::new(engine);
+        let instance = linker
+            .instantiate_and_start(&mut store, &module)
+            .map_err(|e| out_of_fuel(e, fuel))?;
+
+        let memory = instance
+            .get_memory(&store, "memory")
+            .ok_or_else(|| eyre::eyre!("module does not export its memory"))?;
+        let version = instance.get_typed_func::<(), i32>(&store, "herostratus_version")?;
+        let meta = instance.get_typed_func::<(), i64>(&store, "herostratus_meta")?;
+        let mut this = Self {
+            alloc: instance.get_typed_func(&store, "herostratus_alloc")?,
+            dealloc: instance.get_typed_func(&store, "herostratus_dealloc")?,
+            call: instance.get_typed_func(&store, "herostratus_call")?,
+            store,
+            fuel,
+            memory,
+        };
+
+        this.refuel()?;
+        let version = version
+            .call(&mut this.store, ())
+            .map_err(|e| out_of_fuel(e, fuel))? as u32;
+        this.refuel()?;
+        let packed = meta
+            .call(&mut this.store, ())
+            .map_err(|e| out_of_fuel(e, fuel))?;
+        let meta = this.take(packed)?;
+        let meta = serde_json::from_slice(&meta).wrap_err("invalid herostratus_meta")?;
+        Ok((this, meta, version))
+    }
+
+    /// Copy a buffer handed over by the module out of its memory, and free it
+    fn take(&mut self, packed: i64) -> eyre::Result<Vec<u8>> {
+        let fuel = self.fuel;
+        let (ptr, len) = unpack(packed);
+        let mut buf = vec![0; len as u32 as usize];
+        self.memory
+            .read(&self.store, ptr as u32 as usize, &mut buf)?;
+        self.dealloc
+            .call(&mut self.store, (ptr, len))
+            .map_err(|e| out_of_fuel(e, fuel))?;
+        Ok(buf)
+    }
+
+    /// Reset the fuel budget before the next call into the module
+    fn refuel(&mut self) -> eyre::Result<()> {
+        Ok(self.store.set_fuel(self.fuel)?)
+    }
+
+    fn call(&mut self, request: &Request) -> eyre::Result<Response> {
+        let request = serde_json::to_vec(request)?;
+        let len = i32::try_from(request.len())?;
+        let fuel = self.fuel;
+        self.refuel()?;
+        let ptr = self
+            .alloc
+            .call(&mut self.store, len)
+            .map_err(|e| out_of_fuel(e, fuel))?;
+        self.memory
+            .write(&mut self.store, ptr as u32 as usize, &request)?;
+        let packed = self
+            .call
+            .call(&mut self.store, (ptr, len))
+            .map_err(|e| out_of_fuel(e, fuel))?;
+        self.dealloc
+            .call(&mut self.store, (ptr, len))
+            .map_err(|e| out_of_fuel(e, fuel))?;
+
+        let response = self.take(packed)?;
+        serde_json::from_slice(&response).wrap_err(format!(
+            "invalid WASM rule response: {:?}",
+            String::from_utf8_lossy(&response)
+        ))
+    }
+}
+
+/// Replace a trap from running out of fuel with an error saying so
+fn out_of_fuel(error: wasmi::Error, fuel: u64) -> eyre::Report {
+    if error.as_trap_code() == Some(TrapCode::OutOfFuel) {
+        eyre::eyre!(
+            "WASM rule ran out of fuel ({fuel} per call); raise rules.wasm_fuel to allow it"
+        )
+    } else {
+        error.into()
+    }
+}
+
+/// A [RulePlugin] backed by a WebAssembly module
+///
+/// The module is sandboxed: it imports nothing, and only sees the [Request]s it is sent.
+pub struct WasmRule {
+    meta: Meta,
+    consumes: &'static [Discriminant<Observation>],
+    version: u32,
+    instance: RefCell<WasmInstance>,
+}
+
+impl WasmRule {
+    /// Load the module at `path`, limiting each call into it to `fuel`
+    pub fn load(path: &Path, fuel: u64) -> eyre::Result<Self> {
+        let (instance, meta, version) = WasmInstance::load(path, fuel)?;
+        Ok(Self {
+            meta: leak_meta(
+                meta.id,
+                &meta.human_id,
+                &meta.name,
+                &meta.description,
+                meta.kind.into(),
+            )?,
+            consumes: leak_consumes(&meta.consumes)?,
+            version,
+            instance: RefCell::new(instance),
+        })
+    }
+
+    fn call(&self, request: &Request) -> eyre::Result<Response> {
+        self.instance.borrow_mut().call(request)
+    }
+}
+
+impl RulePlugin for WasmRule {
+    fn has_cache(&self) -> bool {
+        true
+    }
+    fn version(&self) -> u32 {
+        self.version
+    }
+    fn init_cache(&mut self, cache: serde_json::Value) -> eyre::Result<()> {
+        self.call(&Request::InitCache { cache: &cache })?;
+        Ok(())
+    }
+    fn fini_cache(&self) -> eyre::Result<serde_json::Value> {
+        Ok(self.call(&Request::FiniCache)?.cache)
+    }
+
+    fn meta(&self) -> &Meta {
+        &self.meta
+    }
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        self.consumes
+    }
+    fn commit_start(&mut self, ctx: &CommitContext) -> eyre::Result<()> {
+        self.call(&Request::CommitStart { commit: ctx })?;
+        Ok(())
+    }
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        if !self.consumes.contains(&discriminant(obs)) {
+            return Ok(None);
+        }
+        let response = self.call(&Request::Observation { observation: obs })?;
+        response.single_grant(&self.meta, ctx)
+    }
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let response = self.call(&Request::CommitComplete)?;
+        response.single_grant(&self.meta, ctx)
+    }
+    fn finalize(&mut self) -> eyre::Result<Vec<Grant>> {
+        self.call(&Request::Finalize)?.all_grants(&self.meta)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compiled_modules_are_reused() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("empty.wasm");
+        // The smallest valid module: just the magic number and version
+        std::fs::write(&path, b"\0asm\x01\0\0\0").unwrap();
+        compile(&path).unwrap();
+
+        // Compiled once, so the file isn't read again
+        std::fs::remove_file(&path).unwrap();
+        compile(&path).unwrap();
+    }
+}
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "I'll fix that up later",
     description: "Prefix a commit message with a !fixup marker",
     kind: AchievementKind::PerUser { recurrent: false },
+    points: 10,
 };
 
 /// Grant an achievement for commits starting with a fixup/squash/amend/WIP/etc prefix.
@@ -26,6 +27,7 @@ inventory::submit!(RuleFactory::default::<Fixup>());
 
 impl Rule for Fixup {
     type Cache = ();
+    const CREDITS_CO_AUTHORS: bool = true;
 
     fn meta(&self) -> &Meta {
         &META
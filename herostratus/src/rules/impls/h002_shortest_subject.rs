@@ -4,7 +4,7 @@ use crate::achievement::{AchievementKind, Grant, Meta};
 use crate::config::RulesConfig;
 use crate::observer::{CommitContext, Observation};
 use crate::rules::rule::Rule;
-use crate::rules::rule_plugin::RuleFactory;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct H002Config {
@@ -19,12 +19,17 @@ impl Default for H002Config {
     }
 }
 
+impl RuleConfig for H002Config {
+    const KEY: &'static str = "h2_shortest_subject_line";
+}
+
 const META: Meta = Meta {
     id: 2,
     human_id: "shortest-subject-line",
     name: "Brevity is the soul of wit",
     description: "The shortest subject line",
     kind: AchievementKind::Global { revocable: true },
+    points: 10,
 };
 
 #[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
@@ -53,14 +58,13 @@ fn shortest_subject_factory(
     config: &RulesConfig,
 ) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
     Box::new(ShortestSubject {
-        threshold: config
-            .h2_shortest_subject_line
-            .as_ref()
-            .map_or(10, |c| c.length_threshold),
+        threshold: config.get::<H002Config>().length_threshold,
         ..Default::default()
     })
 }
-inventory::submit!(RuleFactory::new(shortest_subject_factory));
+inventory::submit!(RuleFactory::with_config::<H002Config>(
+    shortest_subject_factory
+));
 
 impl Rule for ShortestSubject {
     type Cache = ShortestCache;
@@ -4,7 +4,7 @@ use crate::achievement::{AchievementKind, Grant, Meta};
 use crate::config::RulesConfig;
 use crate::observer::{CommitContext, Observation};
 use crate::rules::rule::Rule;
-use crate::rules::rule_plugin::RuleFactory;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct H003Config {
@@ -19,12 +19,17 @@ impl Default for H003Config {
     }
 }
 
+impl RuleConfig for H003Config {
+    const KEY: &'static str = "h3_longest_subject_line";
+}
+
 const META: Meta = Meta {
     id: 3,
     human_id: "longest-subject-line",
     name: "50 characters was more of a suggestion anyways",
     description: "The longest subject line",
     kind: AchievementKind::Global { revocable: true },
+    points: 10,
 };
 
 #[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
@@ -51,14 +56,13 @@ impl Default for LongestSubject {
 
 fn longest_subject_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
     Box::new(LongestSubject {
-        threshold: config
-            .h3_longest_subject_line
-            .as_ref()
-            .map_or(72, |c| c.length_threshold),
+        threshold: config.get::<H003Config>().length_threshold,
         ..Default::default()
     })
 }
-inventory::submit!(RuleFactory::new(longest_subject_factory));
+inventory::submit!(RuleFactory::with_config::<H003Config>(
+    longest_subject_factory
+));
 
 impl Rule for LongestSubject {
     type Cache = LongestCache;
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "But ... How?!",
     description: "Make a commit message containing a non UTF-8 byte",
     kind: AchievementKind::PerUser { recurrent: false },
+    points: 10,
 };
 
 /// Grant an achievement for commits with non-UTF-8 bytes in the message.
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "You can always add more later",
     description: "Create an empty commit containing no changes",
     kind: AchievementKind::PerUser { recurrent: false },
+    points: 10,
 };
 
 /// Grant an achievement for empty commits (no file changes).
@@ -26,6 +27,7 @@ inventory::submit!(RuleFactory::default::<EmptyCommit>());
 
 impl Rule for EmptyCommit {
     type Cache = ();
+    const CREDITS_CO_AUTHORS: bool = true;
 
     fn meta(&self) -> &Meta {
         &META
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "Whitespace Warrior",
     description: "Make a whitespace-only change",
     kind: AchievementKind::PerUser { recurrent: false },
+    points: 10,
 };
 
 /// Grant an achievement for commits where every file change is whitespace-only.
@@ -26,6 +27,7 @@ inventory::submit!(RuleFactory::default::<WhitespaceOnly>());
 
 impl Rule for WhitespaceOnly {
     type Cache = ();
+    const CREDITS_CO_AUTHORS: bool = true;
 
     fn meta(&self) -> &Meta {
         &META
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "First!",
     description: "Be the first person to swear in the repository",
     kind: AchievementKind::Global { revocable: false },
+    points: 20,
 };
 
 /// Grant an achievement to the first person who swears in the repository.
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "Potty Mouth",
     description: "Use profanity in a commit message",
     kind: AchievementKind::PerUser { recurrent: false },
+    points: 10,
 };
 
 /// Grant an achievement when a user swears for the first time.
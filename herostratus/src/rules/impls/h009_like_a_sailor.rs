@@ -12,6 +12,7 @@ const META: Meta = Meta {
     name: "Swears Like a Sailor",
     description: "Use profanity in many commit messages",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
 };
 
 const THRESHOLDS: &[usize] = &[5, 10, 25, 100];
@@ -14,6 +14,7 @@ const META: Meta = Meta {
     name: "Most Profound",
     description: "The author with the most profanity in their commit messages",
     kind: AchievementKind::Global { revocable: true },
+    points: 10,
 };
 
 /// Grant an achievement to the most profane author in the repository.
@@ -4,7 +4,7 @@ use crate::achievement::{AchievementKind, Grant, Meta};
 use crate::config::RulesConfig;
 use crate::observer::{CommitContext, Observation};
 use crate::rules::rule::Rule;
-use crate::rules::rule_plugin::RuleFactory;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct H012Config {
@@ -20,12 +20,17 @@ impl Default for H012Config {
     }
 }
 
+impl RuleConfig for H012Config {
+    const KEY: &'static str = "h12_quine_commit";
+}
+
 const META: Meta = Meta {
     id: 12,
     human_id: "quine-commit",
     name: "How Did You Even Do That?!",
     description: "A commit message that contains a prefix of its own commit hash",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 100,
 };
 
 /// Grant an achievement when a commit message contains a prefix of its own commit hash.
@@ -44,16 +49,14 @@ impl Default for QuineCommit {
 
 fn quine_commit_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
     Box::new(QuineCommit {
-        threshold: config
-            .h12_quine_commit
-            .as_ref()
-            .map_or(7, |c| c.min_matched_chars),
+        threshold: config.get::<H012Config>().min_matched_chars,
     })
 }
-inventory::submit!(RuleFactory::new(quine_commit_factory));
+inventory::submit!(RuleFactory::with_config::<H012Config>(quine_commit_factory));
 
 impl Rule for QuineCommit {
     type Cache = ();
+    const CREDITS_CO_AUTHORS: bool = true;
 
     fn meta(&self) -> &Meta {
         &META
@@ -6,7 +6,7 @@ use crate::achievement::{AchievementKind, Grant, Meta};
 use crate::config::RulesConfig;
 use crate::observer::{CommitContext, Observation};
 use crate::rules::rule::Rule;
-use crate::rules::rule_plugin::RuleFactory;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
 
 #[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 pub struct H013Config {
@@ -25,12 +25,17 @@ impl Default for H013Config {
     }
 }
 
+impl RuleConfig for H013Config {
+    const KEY: &'static str = "h13_fortune_teller";
+}
+
 const META: Meta = Meta {
     id: 13,
     human_id: "fortune-teller",
     name: "Fortune Teller",
     description: "A commit message that predicts a future commit's hash",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 50,
 };
 
 struct Token {
@@ -84,18 +89,16 @@ impl Default for FortuneTeller {
 }
 
 fn fortune_teller_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
-    let h013 = config
-        .h13_fortune_teller
-        .as_ref()
-        .cloned()
-        .unwrap_or_default();
+    let h013 = config.get::<H013Config>();
     Box::new(FortuneTeller {
         min_matched_chars: h013.min_matched_chars,
         max_matched_chars: h013.max_matched_chars,
         ..Default::default()
     })
 }
-inventory::submit!(RuleFactory::new(fortune_teller_factory));
+inventory::submit!(RuleFactory::with_config::<H013Config>(
+    fortune_teller_factory
+));
 
 impl Rule for FortuneTeller {
     type Cache = FortuneTellerCache;
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "Automating your own misery",
     description: "Be the first person to add a CI configuration file",
     kind: AchievementKind::Global { revocable: false },
+    points: 20,
 };
 
 /// Grant an achievement to the first person who adds a CI configuration file to the repository.
@@ -13,6 +13,7 @@ const META: Meta = Meta {
     name: "So You Have a Thing for Tentacles?",
     description: "Create an octopus merge commit with three or more parents",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 20,
 };
 
 #[derive(Default)]
@@ -22,10 +23,12 @@ pub struct Octopus {
 
 fn octopus_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
     Box::new(Octopus {
-        config: config.tentacle_merge.clone().unwrap_or_default(),
+        config: config.get::<TentacleMergeConfig>(),
     })
 }
-inventory::submit!(RuleFactory::new(octopus_factory));
+inventory::submit!(RuleFactory::with_config::<TentacleMergeConfig>(
+    octopus_factory
+));
 
 impl Rule for Octopus {
     type Cache = ();
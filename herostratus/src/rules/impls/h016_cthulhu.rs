@@ -13,6 +13,7 @@ const META: Meta = Meta {
     name: "Ph'nglui mglw'nafh Cthulhu R'lyeh wgah'nagl fhtagn",
     description: "Conduct the unholy rite of binding many parent lineages into a single dark covenant. The stars grow correct.",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 50,
 };
 
 #[derive(Default)]
@@ -22,10 +23,12 @@ pub struct Cthulhu {
 
 fn cthulhu_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
     Box::new(Cthulhu {
-        config: config.tentacle_merge.clone().unwrap_or_default(),
+        config: config.get::<TentacleMergeConfig>(),
     })
 }
-inventory::submit!(RuleFactory::new(cthulhu_factory));
+inventory::submit!(RuleFactory::with_config::<TentacleMergeConfig>(
+    cthulhu_factory
+));
 
 impl Rule for Cthulhu {
     type Cache = ();
@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "Ex Nihilo",
     description: "Create an empty initial commit",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 20,
 };
 
 #[derive(Default)]
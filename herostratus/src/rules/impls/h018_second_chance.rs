@@ -11,6 +11,7 @@ const META: Meta = Meta {
     name: "Second Chance",
     description: "Add an additional root commit to a repository",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 20,
 };
 
 /// The dynamic name for a root commit at the given chronological index
@@ -12,6 +12,7 @@ const META: Meta = Meta {
     name: "Imitation Is the Sincerest Form of Flattery",
     description: "Copy a previous commit's subject line",
     kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
 };
 
 const THRESHOLDS: &[(usize, &str)] = &[
@@ -148,6 +149,10 @@ mod tests {
             author_name: name.to_string(),
             author_email: format!("{}@example.com", name.to_lowercase()),
             commit_timestamp: DateTime::<Utc>::from_timestamp(ts, 0).unwrap(),
+            author_timestamp: DateTime::<Utc>::from_timestamp(ts, 0)
+                .unwrap()
+                .fixed_offset(),
+            co_authors: Vec::new(),
         }
     }
 
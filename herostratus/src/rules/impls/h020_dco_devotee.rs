@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use super::milestones::count_commit_at;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 20,
+    human_id: "dco-devotee",
+    name: "DCO Devotee",
+    description: "Sign off on many of your own commits",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+const THRESHOLDS: &[usize] = &[10, 50, 100, 500];
+
+/// Grant an achievement when a user hits milestones of commits they've signed off.
+///
+/// A commit counts when one of its `Signed-off-by:` trailers names the commit's author. Sign-offs
+/// added by maintainers who apply someone else's patch don't count for the maintainer.
+#[derive(Default)]
+pub struct DcoDevotee {
+    counts: BTreeMap<String, usize>,
+}
+
+inventory::submit!(RuleFactory::default::<DcoDevotee>());
+
+impl Rule for DcoDevotee {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::TRAILERS]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        let Observation::Trailers { trailers } = obs else {
+            return Ok(None);
+        };
+
+        // The author's email may have been rewritten by the mailmap, so also accept their name
+        let signed_off = trailers
+            .iter()
+            .filter(|t| t.is("Signed-off-by"))
+            .filter_map(|t| t.identity())
+            .any(|(name, email)| {
+                email.eq_ignore_ascii_case(&ctx.author_email) || name == ctx.author_name
+            });
+        if !signed_off {
+            return Ok(None);
+        }
+
+        Ok(count_commit_at(&META, THRESHOLDS, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observer::Trailer;
+
+    fn trailers(trailers: &[(&str, &str)]) -> Observation {
+        Observation::Trailers {
+            trailers: trailers
+                .iter()
+                .map(|(token, value)| Trailer {
+                    token: token.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    fn signed_off_by(name: &str) -> Observation {
+        let value = format!("{name} <{}@example.com>", name.to_lowercase());
+        trailers(&[("Signed-off-by", &value)])
+    }
+
+    #[test]
+    fn grants_at_thresholds() {
+        let mut rule = DcoDevotee::default();
+        let ctx = CommitContext::test("Alice");
+        let mut granted = Vec::new();
+        for i in 1..=50 {
+            if let Some(grant) = rule.process(&ctx, &signed_off_by("Alice")).unwrap() {
+                granted.push((i, grant.name_override.unwrap()));
+            }
+        }
+        assert_eq!(
+            granted,
+            [
+                (10, "DCO Devotee (10)".to_string()),
+                (50, "DCO Devotee (50)".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn only_the_authors_sign_off_counts() {
+        let mut rule = DcoDevotee::default();
+        let ctx = CommitContext::test("Alice");
+        let obs = trailers(&[
+            ("Reviewed-by", "Alice <alice@example.com>"),
+            ("Signed-off-by", "Bob <bob@example.com>"),
+        ]);
+        for _ in 0..10 {
+            assert!(rule.process(&ctx, &obs).unwrap().is_none());
+        }
+        assert!(rule.counts.is_empty());
+    }
+
+    #[test]
+    fn sign_off_matches_case_insensitively() {
+        let mut rule = DcoDevotee::default();
+        let ctx = CommitContext::test("Alice");
+        let obs = trailers(&[("signed-off-by", "A. Person <ALICE@example.com>")]);
+        rule.process(&ctx, &obs).unwrap();
+        assert_eq!(rule.counts.get("alice@example.com"), Some(&1));
+    }
+
+    #[test]
+    fn cache_preserves_counts() {
+        let mut rule = DcoDevotee::default();
+        let ctx = CommitContext::test("Alice");
+        for _ in 0..9 {
+            rule.process(&ctx, &signed_off_by("Alice")).unwrap();
+        }
+
+        let mut rule2 = DcoDevotee::default();
+        rule2.init_cache(rule.fini_cache());
+        let grant = rule2.process(&ctx, &signed_off_by("Alice")).unwrap();
+        assert!(grant.is_some(), "expected grant at threshold 10");
+    }
+}
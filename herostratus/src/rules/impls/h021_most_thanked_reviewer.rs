@@ -0,0 +1,188 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use chrono::{DateTime, Utc};
+
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 21,
+    human_id: "most-thanked-reviewer",
+    name: "Most Thanked Reviewer",
+    description: "Be named in the most Reviewed-by trailers",
+    kind: AchievementKind::Global { revocable: true },
+    points: 10,
+};
+
+/// Grant an achievement to the reviewer named by the most `Reviewed-by:` trailers.
+///
+/// Reviewers are identified by the email in the trailer, as written, since they needn't have
+/// authored any commits themselves. Authors reviewing their own commits aren't counted.
+#[derive(Default)]
+pub struct MostThankedReviewer {
+    counts: BTreeMap<String, usize>,
+    /// (name, email, timestamp of the commit that made them the leader)
+    leader: Option<(String, String, DateTime<Utc>)>,
+}
+
+inventory::submit!(RuleFactory::default::<MostThankedReviewer>());
+
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+pub struct MostThankedReviewerCache {
+    counts: BTreeMap<String, usize>,
+    leader: Option<(String, String, DateTime<Utc>)>,
+}
+
+impl Rule for MostThankedReviewer {
+    type Cache = MostThankedReviewerCache;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::TRAILERS]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        let Observation::Trailers { trailers } = obs else {
+            return Ok(None);
+        };
+
+        let mut reviewers: Vec<(String, String)> = trailers
+            .iter()
+            .filter(|t| t.is("Reviewed-by"))
+            .filter_map(|t| t.identity())
+            .map(|(name, email)| (name, email.to_lowercase()))
+            .filter(|(_, email)| !email.eq_ignore_ascii_case(&ctx.author_email))
+            .collect();
+        // Thanking the same reviewer twice in one commit still counts once
+        reviewers.sort_by(|a, b| a.1.cmp(&b.1));
+        reviewers.dedup_by(|a, b| a.1 == b.1);
+
+        for (name, email) in reviewers {
+            let count = self.counts.entry(email.clone()).or_insert(0);
+            *count += 1;
+            let count = *count;
+
+            let leader_count = self
+                .leader
+                .as_ref()
+                .and_then(|(_, email, _)| self.counts.get(email).copied())
+                .unwrap_or(0);
+            if count > leader_count {
+                self.leader = Some((name, email, ctx.commit_timestamp));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn finalize(&mut self) -> eyre::Result<Vec<Grant>> {
+        let Some((ref name, ref email, timestamp)) = self.leader else {
+            return Ok(Vec::new());
+        };
+        Ok(vec![Grant {
+            commit: gix::ObjectId::null(gix::hash::Kind::Sha1),
+            user_name: name.clone(),
+            user_email: email.clone(),
+            timestamp,
+            name_override: None,
+            description_override: None,
+        }])
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache.counts;
+        self.leader = cache.leader;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        MostThankedReviewerCache {
+            counts: self.counts.clone(),
+            leader: self.leader.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observer::Trailer;
+
+    fn reviewed_by(names: &[&str]) -> Observation {
+        Observation::Trailers {
+            trailers: names
+                .iter()
+                .map(|name| Trailer {
+                    token: "Reviewed-by".to_string(),
+                    value: format!("{name} <{}@example.com>", name.to_lowercase()),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn grants_to_most_thanked_reviewer_at_finalize() {
+        let mut rule = MostThankedReviewer::default();
+        let alice = CommitContext::test("Alice");
+
+        assert!(
+            rule.process(&alice, &reviewed_by(&["Bob", "Carol"]))
+                .unwrap()
+                .is_none()
+        );
+        rule.process(&alice, &reviewed_by(&["Carol"])).unwrap();
+
+        let grants = rule.finalize().unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].user_name, "Carol");
+        assert_eq!(grants[0].user_email, "carol@example.com");
+    }
+
+    #[test]
+    fn self_reviews_and_duplicates_are_not_counted() {
+        let mut rule = MostThankedReviewer::default();
+        let alice = CommitContext::test("Alice");
+        for _ in 0..3 {
+            rule.process(&alice, &reviewed_by(&["Alice", "Bob", "Bob"]))
+                .unwrap();
+        }
+        assert_eq!(rule.counts.get("alice@example.com"), None);
+        assert_eq!(rule.counts.get("bob@example.com"), Some(&3));
+    }
+
+    #[test]
+    fn no_grant_without_reviews() {
+        let mut rule = MostThankedReviewer::default();
+        let alice = CommitContext::test("Alice");
+        let fixes = Observation::Trailers {
+            trailers: vec![Trailer {
+                token: "Fixes".to_string(),
+                value: "0123456789ab".to_string(),
+            }],
+        };
+        rule.process(&alice, &fixes).unwrap();
+        assert!(rule.finalize().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cached_leader_wins_over_new_reviewer_with_fewer() {
+        let mut rule = MostThankedReviewer::default();
+        let alice = CommitContext::test("Alice");
+        for _ in 0..3 {
+            rule.process(&alice, &reviewed_by(&["Bob"])).unwrap();
+        }
+
+        let mut rule2 = MostThankedReviewer::default();
+        rule2.init_cache(rule.fini_cache());
+        rule2.process(&alice, &reviewed_by(&["Carol"])).unwrap();
+
+        let grants = rule2.finalize().unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].user_name, "Bob");
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use super::milestones::count_commit_at;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 22,
+    human_id: "signed-sealed-delivered",
+    name: "Signed, Sealed, Delivered",
+    description: "Sign many commits",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+const THRESHOLDS: &[usize] = &[10, 50, 100, 500];
+
+/// Grant an achievement when a user hits milestones of signed commits.
+///
+/// Any kind of signature counts, and none of them are verified.
+#[derive(Default)]
+pub struct SignedSealedDelivered {
+    counts: BTreeMap<String, usize>,
+}
+
+inventory::submit!(RuleFactory::default::<SignedSealedDelivered>());
+
+impl Rule for SignedSealedDelivered {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::SIGNED]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        if !matches!(obs, Observation::Signed { .. }) {
+            return Ok(None);
+        }
+
+        Ok(count_commit_at(&META, THRESHOLDS, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observer::SignatureKind;
+
+    fn signed(kind: SignatureKind) -> Observation {
+        Observation::Signed { kind }
+    }
+
+    #[test]
+    fn grants_at_thresholds() {
+        let mut rule = SignedSealedDelivered::default();
+        let ctx = CommitContext::test("Alice");
+        let mut granted = Vec::new();
+        for i in 1..=50 {
+            // Every kind of signature counts
+            let kind = match i % 3 {
+                0 => SignatureKind::Gpg,
+                1 => SignatureKind::Ssh,
+                _ => SignatureKind::X509,
+            };
+            if let Some(grant) = rule.process(&ctx, &signed(kind)).unwrap() {
+                granted.push((i, grant.name_override.unwrap()));
+            }
+        }
+        assert_eq!(
+            granted,
+            [
+                (10, "Signed, Sealed, Delivered (10)".to_string()),
+                (50, "Signed, Sealed, Delivered (50)".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_are_per_user() {
+        let mut rule = SignedSealedDelivered::default();
+        let alice = CommitContext::test("Alice");
+        let bob = CommitContext::test("Bob");
+        for _ in 0..9 {
+            rule.process(&alice, &signed(SignatureKind::Gpg)).unwrap();
+        }
+        let grant = rule.process(&bob, &signed(SignatureKind::Gpg)).unwrap();
+        assert!(grant.is_none());
+        let grant = rule.process(&alice, &signed(SignatureKind::Gpg)).unwrap();
+        assert!(grant.is_some());
+    }
+
+    #[test]
+    fn cache_preserves_counts() {
+        let mut rule = SignedSealedDelivered::default();
+        let ctx = CommitContext::test("Alice");
+        for _ in 0..9 {
+            rule.process(&ctx, &signed(SignatureKind::Ssh)).unwrap();
+        }
+
+        let mut rule2 = SignedSealedDelivered::default();
+        rule2.init_cache(rule.fini_cache());
+        let grant = rule2.process(&ctx, &signed(SignatureKind::Ssh)).unwrap();
+        assert!(grant.is_some(), "expected grant at threshold 10");
+    }
+}
@@ -0,0 +1,117 @@
+use std::mem::Discriminant;
+
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 23,
+    human_id: "first-signature",
+    name: "John Hancock",
+    description: "Be the first person to sign a commit in the repository",
+    kind: AchievementKind::Global { revocable: false },
+    points: 20,
+};
+
+/// Grant an achievement to the first person who signs a commit in the repository.
+///
+/// Like [FirstProfanity](super::h007_first_profanity::FirstProfanity), the last signed commit
+/// seen in the newest-first walk is the first in the repository, so this grants at finalize, and
+/// caches the commit once it's settled.
+#[derive(Default)]
+pub struct FirstSignature {
+    /// The commit hash from a previous run, if already settled.
+    settled_commit: Option<String>,
+    earliest: Option<Grant>,
+}
+
+inventory::submit!(RuleFactory::default::<FirstSignature>());
+
+/// Stores the commit hash of the first signed commit once determined.
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+pub struct FirstSignatureCache {
+    commit: Option<String>,
+}
+
+impl Rule for FirstSignature {
+    type Cache = FirstSignatureCache;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::SIGNED]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        if self.settled_commit.is_some() {
+            return Ok(None);
+        }
+        if matches!(obs, Observation::Signed { .. }) {
+            self.earliest = Some(META.grant(ctx));
+        }
+        Ok(None)
+    }
+
+    fn finalize(&mut self) -> eyre::Result<Vec<Grant>> {
+        if self.settled_commit.is_some() {
+            return Ok(Vec::new());
+        }
+        if let Some(ref grant) = self.earliest {
+            self.settled_commit = Some(grant.commit.to_string());
+        }
+        Ok(self.earliest.take().into_iter().collect())
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.settled_commit = cache.commit;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        FirstSignatureCache {
+            commit: self.settled_commit.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observer::SignatureKind;
+
+    fn signed() -> Observation {
+        Observation::Signed {
+            kind: SignatureKind::Gpg,
+        }
+    }
+
+    #[test]
+    fn grants_last_seen_at_finalize() {
+        let mut rule = FirstSignature::default();
+        // Walk order is newest-first, so Bob signed first
+        let alice = CommitContext::test("Alice");
+        let bob = CommitContext::test("Bob");
+
+        assert!(rule.process(&alice, &signed()).unwrap().is_none());
+        rule.process(&bob, &signed()).unwrap();
+
+        let grants = rule.finalize().unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].user_email, "bob@example.com");
+        assert!(rule.fini_cache().commit.is_some());
+    }
+
+    #[test]
+    fn settled_cache_skips_processing() {
+        let mut rule = FirstSignature::default();
+        rule.init_cache(FirstSignatureCache {
+            commit: Some("abc123".to_string()),
+        });
+
+        let ctx = CommitContext::test("Alice");
+        rule.process(&ctx, &signed()).unwrap();
+        assert!(rule.finalize().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,166 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use chrono::Timelike;
+
+use super::local_time::in_hour_window;
+use super::milestones::count_commit;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::config::RulesConfig;
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
+
+/// The hours of the night, in the author's local time. See [in_hour_window].
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct H024Config {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for H024Config {
+    fn default() -> Self {
+        Self {
+            start_hour: 0,
+            end_hour: 5,
+        }
+    }
+}
+
+impl RuleConfig for H024Config {
+    const KEY: &'static str = "h24_night_owl";
+}
+
+const META: Meta = Meta {
+    id: 24,
+    human_id: "night-owl",
+    name: "Night Owl",
+    description: "Commit in the middle of the night",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+/// Grant an achievement for commits made late at night, in the author's own timezone.
+#[derive(Default)]
+pub struct NightOwl {
+    config: H024Config,
+    counts: BTreeMap<String, usize>,
+}
+
+fn night_owl_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
+    Box::new(NightOwl {
+        config: config.get::<H024Config>(),
+        ..Default::default()
+    })
+}
+inventory::submit!(RuleFactory::with_config::<H024Config>(night_owl_factory));
+
+impl Rule for NightOwl {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        // Only needs the commit's timestamp
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &CommitContext, _obs: &Observation) -> eyre::Result<Option<Grant>> {
+        Ok(None)
+    }
+
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let hour = ctx.author_timestamp.hour();
+        if !in_hour_window(hour, self.config.start_hour, self.config.end_hour) {
+            return Ok(None);
+        }
+        Ok(count_commit(&META, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    /// Alice, committing at the given RFC 3339 time
+    fn at(time: &str) -> CommitContext {
+        let mut ctx = CommitContext::test("Alice");
+        ctx.author_timestamp = DateTime::parse_from_rfc3339(time).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn uses_the_authors_local_time() {
+        let mut rule = NightOwl::default();
+        // 01:30 in UTC, but 18:30 the evening before for the author
+        let grant = rule
+            .commit_complete(&at("2026-03-01T18:30:00-07:00"))
+            .unwrap();
+        assert!(grant.is_none());
+
+        // 14:30 in UTC, but the middle of the night for the author
+        let grant = rule
+            .commit_complete(&at("2026-03-02T02:30:00+12:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(grant.name_override, None);
+    }
+
+    #[test]
+    fn grants_at_thresholds() {
+        let mut rule = NightOwl::default();
+        let ctx = at("2026-03-01T03:00:00+01:00");
+        let granted: Vec<_> = (1..=10)
+            .filter_map(|_| rule.commit_complete(&ctx).unwrap())
+            .map(|g| g.name_override)
+            .collect();
+        assert_eq!(granted, [None, Some("Night Owl (10)".to_string())]);
+    }
+
+    #[test]
+    fn configurable_window() {
+        let mut rule = NightOwl {
+            config: H024Config {
+                start_hour: 22,
+                end_hour: 4,
+            },
+            ..Default::default()
+        };
+        assert!(
+            rule.commit_complete(&at("2026-03-01T23:00:00+00:00"))
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            rule.commit_complete(&at("2026-03-01T04:00:00+00:00"))
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn cache_preserves_counts() {
+        let mut rule = NightOwl::default();
+        let ctx = at("2026-03-01T01:00:00+00:00");
+        for _ in 0..9 {
+            rule.commit_complete(&ctx).unwrap();
+        }
+
+        let mut rule2 = NightOwl::default();
+        rule2.init_cache(rule.fini_cache());
+        let grant = rule2.commit_complete(&ctx).unwrap();
+        assert!(grant.is_some(), "expected grant at threshold 10");
+    }
+}
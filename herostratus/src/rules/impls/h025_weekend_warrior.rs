@@ -0,0 +1,106 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use chrono::{Datelike, Weekday};
+
+use super::milestones::count_commit;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 25,
+    human_id: "weekend-warrior",
+    name: "Weekend Warrior",
+    description: "Commit on the weekend",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+/// Grant an achievement for commits made on a Saturday or Sunday, in the author's own timezone.
+#[derive(Default)]
+pub struct WeekendWarrior {
+    counts: BTreeMap<String, usize>,
+}
+
+inventory::submit!(RuleFactory::default::<WeekendWarrior>());
+
+impl Rule for WeekendWarrior {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        // Only needs the commit's timestamp
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &CommitContext, _obs: &Observation) -> eyre::Result<Option<Grant>> {
+        Ok(None)
+    }
+
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        if !matches!(ctx.author_timestamp.weekday(), Weekday::Sat | Weekday::Sun) {
+            return Ok(None);
+        }
+        Ok(count_commit(&META, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn at(time: &str) -> CommitContext {
+        let mut ctx = CommitContext::test("Alice");
+        ctx.author_timestamp = DateTime::parse_from_rfc3339(time).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn uses_the_authors_local_date() {
+        let mut rule = WeekendWarrior::default();
+        // Saturday in UTC, but still Friday for the author
+        let grant = rule
+            .commit_complete(&at("2026-03-06T20:00:00-08:00"))
+            .unwrap();
+        assert!(grant.is_none());
+
+        // Friday in UTC, but already Saturday for the author
+        let grant = rule
+            .commit_complete(&at("2026-03-07T08:00:00+13:00"))
+            .unwrap();
+        assert!(grant.is_some());
+    }
+
+    #[test]
+    fn grants_at_thresholds() {
+        let mut rule = WeekendWarrior::default();
+        let sunday = at("2026-03-08T12:00:00+00:00");
+        let granted: Vec<_> = (1..=50)
+            .filter_map(|_| rule.commit_complete(&sunday).unwrap())
+            .map(|g| g.name_override)
+            .collect();
+        assert_eq!(
+            granted,
+            [
+                None,
+                Some("Weekend Warrior (10)".to_string()),
+                Some("Weekend Warrior (50)".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use chrono::{Datelike, Timelike, Weekday};
+
+use super::local_time::in_hour_window;
+use super::milestones::count_commit;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::config::RulesConfig;
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
+
+/// The hours of Friday afternoon, in the author's local time. See [in_hour_window].
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct H026Config {
+    pub start_hour: u32,
+    pub end_hour: u32,
+}
+
+impl Default for H026Config {
+    fn default() -> Self {
+        Self {
+            start_hour: 12,
+            end_hour: 18,
+        }
+    }
+}
+
+impl RuleConfig for H026Config {
+    const KEY: &'static str = "h26_friday_deployer";
+}
+
+const META: Meta = Meta {
+    id: 26,
+    human_id: "friday-deployer",
+    name: "Friday Deployer",
+    description: "Commit on a Friday afternoon",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+/// Grant an achievement for commits made on Friday afternoons, in the author's own timezone.
+#[derive(Default)]
+pub struct FridayDeployer {
+    config: H026Config,
+    counts: BTreeMap<String, usize>,
+}
+
+fn friday_deployer_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
+    Box::new(FridayDeployer {
+        config: config.get::<H026Config>(),
+        ..Default::default()
+    })
+}
+inventory::submit!(RuleFactory::with_config::<H026Config>(
+    friday_deployer_factory
+));
+
+impl Rule for FridayDeployer {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        // Only needs the commit's timestamp
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &CommitContext, _obs: &Observation) -> eyre::Result<Option<Grant>> {
+        Ok(None)
+    }
+
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let local = ctx.author_timestamp;
+        if local.weekday() != Weekday::Fri
+            || !in_hour_window(local.hour(), self.config.start_hour, self.config.end_hour)
+        {
+            return Ok(None);
+        }
+        Ok(count_commit(&META, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn at(time: &str) -> CommitContext {
+        let mut ctx = CommitContext::test("Alice");
+        ctx.author_timestamp = DateTime::parse_from_rfc3339(time).unwrap();
+        ctx
+    }
+
+    #[test]
+    fn only_friday_afternoons() {
+        let mut rule = FridayDeployer::default();
+        for time in [
+            // Friday morning
+            "2026-03-06T11:59:00+02:00",
+            // Friday evening
+            "2026-03-06T18:00:00+02:00",
+            // Thursday afternoon
+            "2026-03-05T15:00:00+02:00",
+            // Friday afternoon in UTC, but Saturday morning for the author
+            "2026-03-07T02:00:00+12:00",
+        ] {
+            assert!(rule.commit_complete(&at(time)).unwrap().is_none(), "{time}");
+        }
+        assert!(
+            rule.commit_complete(&at("2026-03-06T16:45:00-05:00"))
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn configurable_window() {
+        let mut rule = FridayDeployer {
+            config: H026Config {
+                start_hour: 17,
+                end_hour: 24,
+            },
+            ..Default::default()
+        };
+        assert!(
+            rule.commit_complete(&at("2026-03-06T16:00:00+00:00"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            rule.commit_complete(&at("2026-03-06T23:30:00+00:00"))
+                .unwrap()
+                .is_some()
+        );
+    }
+}
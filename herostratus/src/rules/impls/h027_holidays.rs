@@ -0,0 +1,315 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::mem::Discriminant;
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::config::RulesConfig;
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::{RuleConfig, RuleFactory};
+
+/// The holidays every calendar starts with, unless `builtin = false`
+const BUILTIN_HOLIDAYS: &[(u32, u32, &str)] = &[(1, 1, "New Year's Day"), (12, 25, "Christmas")];
+
+/// The date of a holiday, either every year, or on one specific day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HolidayDate {
+    /// `MM-DD`, for holidays on the same date every year
+    Yearly { month: u32, day: u32 },
+    /// `YYYY-MM-DD`, for holidays that move around, like Thanksgiving
+    Once(NaiveDate),
+}
+
+impl HolidayDate {
+    fn matches(&self, date: NaiveDate) -> bool {
+        match *self {
+            HolidayDate::Yearly { month, day } => date.month() == month && date.day() == day,
+            HolidayDate::Once(once) => date == once,
+        }
+    }
+}
+
+impl TryFrom<String> for HolidayDate {
+    type Error = String;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
+            return Ok(HolidayDate::Once(date));
+        }
+        // Any leap year will do, so that Feb 29 is a valid yearly holiday
+        match NaiveDate::parse_from_str(&format!("2000-{s}"), "%Y-%m-%d") {
+            Ok(date) => Ok(HolidayDate::Yearly {
+                month: date.month(),
+                day: date.day(),
+            }),
+            Err(_) => Err(format!(
+                "Invalid holiday date {s:?}; expected MM-DD or YYYY-MM-DD"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for HolidayDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HolidayDate::Yearly { month, day } => write!(f, "{month:02}-{day:02}"),
+            HolidayDate::Once(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for HolidayDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        HolidayDate::try_from(s).map_err(serde::de::Error::custom)
+    }
+}
+
+impl serde::Serialize for HolidayDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The holiday calendar
+///
+/// ```toml
+/// [rules.holidays]
+/// builtin = true
+///
+/// [rules.holidays.dates]
+/// "07-04" = "Independence Day"
+/// "2026-11-26" = "Thanksgiving"
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct HolidaysConfig {
+    /// Whether to include New Year's Day and Christmas
+    pub builtin: bool,
+    /// Extra holidays, by date. These take precedence over the built-in holidays.
+    pub dates: BTreeMap<HolidayDate, String>,
+}
+
+impl Default for HolidaysConfig {
+    fn default() -> Self {
+        Self {
+            builtin: true,
+            dates: BTreeMap::new(),
+        }
+    }
+}
+
+impl RuleConfig for HolidaysConfig {
+    const KEY: &'static str = "holidays";
+}
+
+impl HolidaysConfig {
+    /// Find the holiday on the given date, if any
+    fn holiday_on(&self, date: NaiveDate) -> Option<&str> {
+        // A specific date is more specific than a yearly one
+        let custom = self
+            .dates
+            .iter()
+            .filter(|(d, _)| d.matches(date))
+            .max_by_key(|(d, _)| matches!(d, HolidayDate::Once(_)))
+            .map(|(_, name)| name.as_str());
+        let builtin = || {
+            BUILTIN_HOLIDAYS
+                .iter()
+                .filter(|_| self.builtin)
+                .find(|(month, day, _)| date.month() == *month && date.day() == *day)
+                .map(|(_, _, name)| *name)
+        };
+        custom.or_else(builtin)
+    }
+}
+
+const META: Meta = Meta {
+    id: 27,
+    human_id: "working-holiday",
+    name: "Working Holiday",
+    description: "Commit on a holiday",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+/// Grant an achievement, named for the holiday, for commits made on holidays.
+///
+/// Uses the author's local date. Each user is granted at most once per holiday, no matter how many
+/// commits they make that day.
+#[derive(Default)]
+pub struct Holidays {
+    config: HolidaysConfig,
+    /// The holiday dates each user has been granted for
+    granted: BTreeMap<String, BTreeSet<NaiveDate>>,
+}
+
+fn holidays_factory(config: &RulesConfig) -> Box<dyn crate::rules::rule_plugin::RulePlugin> {
+    Box::new(Holidays {
+        config: config.get::<HolidaysConfig>(),
+        ..Default::default()
+    })
+}
+inventory::submit!(RuleFactory::with_config::<HolidaysConfig>(holidays_factory));
+
+impl Rule for Holidays {
+    type Cache = BTreeMap<String, BTreeSet<NaiveDate>>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        // Only needs the commit's timestamp
+        &[]
+    }
+
+    fn process(&mut self, _ctx: &CommitContext, _obs: &Observation) -> eyre::Result<Option<Grant>> {
+        Ok(None)
+    }
+
+    fn commit_complete(&mut self, ctx: &CommitContext) -> eyre::Result<Option<Grant>> {
+        let date = ctx.author_timestamp.date_naive();
+        let Some(holiday) = self.config.holiday_on(date) else {
+            return Ok(None);
+        };
+        let first_time = self
+            .granted
+            .entry(ctx.author_email.clone())
+            .or_default()
+            .insert(date);
+        Ok(first_time.then(|| META.grant(ctx).with_name(holiday.to_string())))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.granted = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.granted.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+
+    use super::*;
+
+    fn at(name: &str, time: &str) -> CommitContext {
+        let mut ctx = CommitContext::test(name);
+        ctx.author_timestamp = DateTime::parse_from_rfc3339(time).unwrap();
+        ctx
+    }
+
+    fn config(builtin: bool, dates: &[(&str, &str)]) -> HolidaysConfig {
+        HolidaysConfig {
+            builtin,
+            dates: dates
+                .iter()
+                .map(|(d, n)| (HolidayDate::try_from(d.to_string()).unwrap(), n.to_string()))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn builtin_holidays_in_local_time() {
+        let mut rule = Holidays::default();
+        // Still Christmas Eve in UTC
+        let grant = rule
+            .commit_complete(&at("Alice", "2025-12-25T08:00:00+10:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(grant.name_override.as_deref(), Some("Christmas"));
+
+        // Already January 2nd in UTC
+        let grant = rule
+            .commit_complete(&at("Alice", "2026-01-01T20:00:00-08:00"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(grant.name_override.as_deref(), Some("New Year's Day"));
+
+        let grant = rule
+            .commit_complete(&at("Alice", "2026-03-01T12:00:00+00:00"))
+            .unwrap();
+        assert!(grant.is_none());
+    }
+
+    #[test]
+    fn once_per_user_per_holiday() {
+        let mut rule = Holidays::default();
+        let morning = at("Alice", "2025-12-25T09:00:00+00:00");
+        let evening = at("Alice", "2025-12-25T21:00:00+00:00");
+        assert!(rule.commit_complete(&morning).unwrap().is_some());
+        assert!(rule.commit_complete(&evening).unwrap().is_none());
+        assert!(
+            rule.commit_complete(&at("Bob", "2025-12-25T21:00:00+00:00"))
+                .unwrap()
+                .is_some()
+        );
+        // Next year's Christmas is another holiday
+        assert!(
+            rule.commit_complete(&at("Alice", "2026-12-25T09:00:00+00:00"))
+                .unwrap()
+                .is_some()
+        );
+
+        let mut rule2 = Holidays::default();
+        rule2.init_cache(rule.fini_cache());
+        assert!(rule2.commit_complete(&morning).unwrap().is_none());
+    }
+
+    #[test]
+    fn custom_holidays() {
+        let mut rule = Holidays {
+            config: config(
+                false,
+                &[
+                    ("07-04", "Independence Day"),
+                    ("11-26", "Some Thursday"),
+                    ("2026-11-26", "Thanksgiving"),
+                ],
+            ),
+            ..Default::default()
+        };
+        let name = |rule: &mut Holidays, time| {
+            rule.commit_complete(&at("Alice", time))
+                .unwrap()
+                .and_then(|g| g.name_override)
+        };
+        assert_eq!(
+            name(&mut rule, "2026-07-04T12:00:00+00:00").as_deref(),
+            Some("Independence Day")
+        );
+        assert_eq!(
+            name(&mut rule, "2026-11-26T12:00:00+00:00").as_deref(),
+            Some("Thanksgiving")
+        );
+        assert_eq!(
+            name(&mut rule, "2027-11-26T12:00:00+00:00").as_deref(),
+            Some("Some Thursday")
+        );
+        // The builtin holidays were turned off
+        assert_eq!(name(&mut rule, "2026-12-25T12:00:00+00:00"), None);
+    }
+
+    #[test]
+    fn holiday_dates() {
+        let parse = |s: &str| HolidayDate::try_from(s.to_string());
+        assert_eq!(
+            parse("02-29"),
+            Ok(HolidayDate::Yearly { month: 2, day: 29 })
+        );
+        assert_eq!(
+            parse("2026-11-26"),
+            Ok(HolidayDate::Once(
+                NaiveDate::from_ymd_opt(2026, 11, 26).unwrap()
+            ))
+        );
+        assert!(parse("13-01").is_err());
+        assert!(parse("Christmas").is_err());
+        assert_eq!(parse("7-4").unwrap().to_string(), "07-04");
+    }
+}
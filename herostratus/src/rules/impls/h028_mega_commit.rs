@@ -0,0 +1,124 @@
+use std::mem::Discriminant;
+
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 28,
+    human_id: "mega-commit",
+    name: "Mega Commit",
+    description: "Make the biggest single commit",
+    kind: AchievementKind::Global { revocable: true },
+    points: 10,
+};
+
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+pub struct MegaCommitCache {
+    largest_size: Option<u64>,
+}
+
+/// Grant an achievement for the commit that added and removed the most lines in the repository.
+#[derive(Default)]
+pub struct MegaCommit {
+    cache: MegaCommitCache,
+    candidate: Option<Grant>,
+}
+
+inventory::submit!(RuleFactory::default::<MegaCommit>());
+
+impl Rule for MegaCommit {
+    type Cache = MegaCommitCache;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::DIFF_STATS]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        let Observation::DiffStats {
+            lines_added,
+            lines_removed,
+            ..
+        } = obs
+        else {
+            return Ok(None);
+        };
+        let size = lines_added + lines_removed;
+        if size == 0 || self.cache.largest_size.is_some_and(|l| size <= l) {
+            return Ok(None);
+        }
+
+        self.cache.largest_size = Some(size);
+        self.candidate = Some(META.grant(ctx));
+        Ok(None)
+    }
+
+    fn finalize(&mut self) -> eyre::Result<Vec<Grant>> {
+        Ok(self.candidate.take().into_iter().collect())
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.cache = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.cache.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(lines_added: u64, lines_removed: u64) -> Observation {
+        Observation::DiffStats {
+            lines_added,
+            lines_removed,
+            files_changed: 1,
+        }
+    }
+
+    #[test]
+    fn grants_biggest_commit() {
+        let mut rule = MegaCommit::default();
+        rule.process(&CommitContext::test("Alice"), &diff(10, 0))
+            .unwrap();
+        // Removed lines count too
+        rule.process(&CommitContext::test("Bob"), &diff(2, 20))
+            .unwrap();
+        rule.process(&CommitContext::test("Carol"), &diff(15, 0))
+            .unwrap();
+        let grants = rule.finalize().unwrap();
+        assert_eq!(grants.len(), 1);
+        assert_eq!(grants[0].user_name, "Bob");
+    }
+
+    #[test]
+    fn binary_only_commits_are_ignored() {
+        let mut rule = MegaCommit::default();
+        rule.process(&CommitContext::test("Alice"), &diff(0, 0))
+            .unwrap();
+        assert!(rule.finalize().unwrap().is_empty());
+    }
+
+    #[test]
+    fn cache_preserves_across_runs() {
+        let mut rule = MegaCommit::default();
+        rule.process(&CommitContext::test("Alice"), &diff(100, 0))
+            .unwrap();
+        let cache = rule.fini_cache();
+        assert_eq!(cache.largest_size, Some(100));
+
+        let mut rule2 = MegaCommit::default();
+        rule2.init_cache(cache);
+        rule2
+            .process(&CommitContext::test("Bob"), &diff(50, 50))
+            .unwrap();
+        assert!(rule2.finalize().unwrap().is_empty());
+    }
+}
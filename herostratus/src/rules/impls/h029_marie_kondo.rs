@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+use std::mem::Discriminant;
+
+use super::milestones::count_commit;
+use crate::achievement::{AchievementKind, Grant, Meta};
+use crate::observer::{CommitContext, Observation};
+use crate::rules::rule::Rule;
+use crate::rules::rule_plugin::RuleFactory;
+
+const META: Meta = Meta {
+    id: 29,
+    human_id: "marie-kondo",
+    name: "Marie Kondo",
+    description: "Make a commit that removes more lines than it adds",
+    kind: AchievementKind::PerUser { recurrent: true },
+    points: 10,
+};
+
+/// Grant an achievement when a user hits milestones of net-negative commits, which remove more
+/// lines than they add.
+#[derive(Default)]
+pub struct MarieKondo {
+    counts: BTreeMap<String, usize>,
+}
+
+inventory::submit!(RuleFactory::default::<MarieKondo>());
+
+impl Rule for MarieKondo {
+    type Cache = BTreeMap<String, usize>;
+
+    fn meta(&self) -> &Meta {
+        &META
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        &[Observation::DIFF_STATS]
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        let Observation::DiffStats {
+            lines_added,
+            lines_removed,
+            ..
+        } = obs
+        else {
+            return Ok(None);
+        };
+        if lines_removed <= lines_added {
+            return Ok(None);
+        }
+
+        Ok(count_commit(&META, &mut self.counts, ctx))
+    }
+
+    fn init_cache(&mut self, cache: Self::Cache) {
+        self.counts = cache;
+    }
+
+    fn fini_cache(&self) -> Self::Cache {
+        self.counts.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diff(lines_added: u64, lines_removed: u64) -> Observation {
+        Observation::DiffStats {
+            lines_added,
+            lines_removed,
+            files_changed: 1,
+        }
+    }
+
+    #[test]
+    fn only_net_negative_commits_count() {
+        let mut rule = MarieKondo::default();
+        let ctx = CommitContext::test("Alice");
+        assert!(rule.process(&ctx, &diff(10, 5)).unwrap().is_none());
+        assert!(rule.process(&ctx, &diff(5, 5)).unwrap().is_none());
+
+        let grant = rule.process(&ctx, &diff(5, 10)).unwrap().unwrap();
+        assert_eq!(grant.user_name, "Alice");
+        assert_eq!(grant.name_override, None);
+    }
+
+    #[test]
+    fn grants_at_thresholds_per_user() {
+        let mut rule = MarieKondo::default();
+        let alice = CommitContext::test("Alice");
+        let mut granted = Vec::new();
+        for i in 1..=10 {
+            if let Some(grant) = rule.process(&alice, &diff(0, 1)).unwrap() {
+                granted.push((i, grant.name_override));
+            }
+        }
+        assert_eq!(
+            granted,
+            [(1, None), (10, Some("Marie Kondo (10)".to_string()))]
+        );
+
+        // Each user counts separately
+        let bob = CommitContext::test("Bob");
+        assert!(rule.process(&bob, &diff(0, 1)).unwrap().is_some());
+    }
+}
@@ -0,0 +1,29 @@
+/// Whether `hour` is in `[start_hour, end_hour)`.
+///
+/// The window wraps around midnight when `start_hour > end_hour`, so 22 to 5 covers the hours from
+/// 10pm until 5am. An `end_hour` of 24 means until midnight.
+pub fn in_hour_window(hour: u32, start_hour: u32, end_hour: u32) -> bool {
+    if start_hour <= end_hour {
+        start_hour <= hour && hour < end_hour
+    } else {
+        start_hour <= hour || hour < end_hour
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hour_windows() {
+        assert!(in_hour_window(0, 0, 5));
+        assert!(in_hour_window(4, 0, 5));
+        assert!(!in_hour_window(5, 0, 5));
+        assert!(in_hour_window(23, 12, 24));
+
+        // Wraps around midnight
+        assert!(in_hour_window(23, 22, 5));
+        assert!(in_hour_window(2, 22, 5));
+        assert!(!in_hour_window(12, 22, 5));
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use crate::achievement::{Grant, Meta};
+use crate::observer::CommitContext;
+
+/// How many matching commits it takes a user to reach each milestone
+pub const THRESHOLDS: &[usize] = &[1, 10, 50, 100];
+
+/// Count a matching commit for its author, and grant at each of the [THRESHOLDS]
+///
+/// The first grant has the achievement's own name. Later ones are named for their milestone, like
+/// "Night Owl (10)".
+pub fn count_commit(
+    meta: &Meta,
+    counts: &mut BTreeMap<String, usize>,
+    ctx: &CommitContext,
+) -> Option<Grant> {
+    count_commit_at(meta, THRESHOLDS, counts, ctx)
+}
+
+/// Count a matching commit for its author, and grant at each of the given `thresholds`
+///
+/// A grant for a user's very first commit has the achievement's own name. Every other grant is
+/// named for its milestone.
+pub fn count_commit_at(
+    meta: &Meta,
+    thresholds: &[usize],
+    counts: &mut BTreeMap<String, usize>,
+    ctx: &CommitContext,
+) -> Option<Grant> {
+    let count = counts.entry(ctx.author_email.clone()).or_insert(0);
+    *count += 1;
+    let count = *count;
+    match count {
+        _ if !thresholds.contains(&count) => None,
+        1 => Some(meta.grant(ctx)),
+        _ => Some(
+            meta.grant(ctx)
+                .with_name(format!("{} ({count})", meta.name)),
+        ),
+    }
+}
@@ -16,10 +16,25 @@ mod h016_cthulhu;
 mod h017_ex_nihilo;
 mod h018_second_chance;
 mod h019_flattery;
+mod h020_dco_devotee;
+mod h021_most_thanked_reviewer;
+mod h022_signed_sealed_delivered;
+mod h023_first_signature;
+mod h024_night_owl;
+mod h025_weekend_warrior;
+mod h026_friday_deployer;
+mod h027_holidays;
+mod h028_mega_commit;
+mod h029_marie_kondo;
+mod local_time;
+mod milestones;
 mod tentacle_merge_config;
 
 pub use h002_shortest_subject::H002Config;
 pub use h003_longest_subject::H003Config;
 pub use h012_quine_commit::H012Config;
 pub use h013_fortune_teller::H013Config;
+pub use h024_night_owl::H024Config;
+pub use h026_friday_deployer::H026Config;
+pub use h027_holidays::{HolidayDate, HolidaysConfig};
 pub use tentacle_merge_config::TentacleMergeConfig;
@@ -1,3 +1,5 @@
+use crate::rules::rule_plugin::RuleConfig;
+
 /// Shared configuration for the octopus and cthulhu merge rules.
 ///
 /// `octopus_threshold` is the minimum number of parents for a merge to grant the octopus
@@ -11,6 +13,10 @@ pub struct TentacleMergeConfig {
     pub cthulhu_threshold: usize,
 }
 
+impl RuleConfig for TentacleMergeConfig {
+    const KEY: &'static str = "tentacle_merge";
+}
+
 impl Default for TentacleMergeConfig {
     fn default() -> Self {
         Self {
@@ -1,5 +1,6 @@
 //! The achievements builtin to Herostratus
 
+pub mod external;
 mod impls;
 mod rule;
 mod rule_engine;
@@ -7,6 +8,13 @@ mod rule_plugin;
 #[cfg(test)]
 mod test_rules;
 
-pub use impls::{H002Config, H003Config, H012Config, H013Config, TentacleMergeConfig};
+pub use external::ScriptRuleConfig;
+pub use impls::{
+    H002Config, H003Config, H012Config, H013Config, H024Config, H026Config, HolidayDate,
+    HolidaysConfig, TentacleMergeConfig,
+};
 pub use rule_engine::{RuleEngine, RuleOutput};
-pub use rule_plugin::{RulePlugin, builtin_rules, builtin_rules_all};
+pub use rule_plugin::{
+    ConfigSchema, RuleConfig, RulePlugin, all_rules, builtin_rules, builtin_rules_all,
+    rule_config_schemas,
+};
@@ -47,6 +47,12 @@ pub trait Rule {
     /// Do not bump for refactors that leave behavior observably identical.
     const VERSION: u32 = 1;
 
+    /// Whether the grants for a commit's author should be shared with its co-authors.
+    ///
+    /// Only takes effect if `rules.co_authors` is enabled. Suits rules about what a commit
+    /// contains, since co-authors wrote it too, rather than about the author's history.
+    const CREDITS_CO_AUTHORS: bool = false;
+
     /// Static metadata about the achievement this rule grants.
     ///
     /// One rule = one achievement, enforced structurally by the singular return type.
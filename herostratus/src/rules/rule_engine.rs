@@ -26,6 +26,8 @@ pub struct RuleEngine {
     rules: Vec<Box<dyn RulePlugin>>,
     current_ctx: Option<crate::observer::CommitContext>,
     pending: Vec<RuleOutput>,
+    /// Share the grants of rules that [credit co-authors](RulePlugin::credits_co_authors)
+    co_authors: bool,
 }
 
 impl RuleEngine {
@@ -34,9 +36,19 @@ impl RuleEngine {
             rules,
             current_ctx: None,
             pending: Vec::new(),
+            co_authors: false,
         }
     }
 
+    /// Also grant to the co-authors of a commit, for the rules that opt in
+    ///
+    /// Only applies to grants from [process](RulePlugin::process) and
+    /// [commit_complete](RulePlugin::commit_complete), since `finalize` has no commit context.
+    pub fn with_co_authors(mut self, enabled: bool) -> Self {
+        self.co_authors = enabled;
+        self
+    }
+
     /// Begin a new commit
     pub fn on_commit_start(&mut self, ctx: CommitContext) {
         self.current_ctx = Some(ctx);
@@ -57,10 +69,13 @@ impl RuleEngine {
         for rule in &mut self.rules {
             match rule.process(ctx, obs) {
                 Ok(Some(grant)) => {
-                    self.pending.push(RuleOutput {
-                        meta: rule.meta().clone(),
+                    push_grant(
+                        &mut self.pending,
+                        rule.as_ref(),
+                        ctx,
                         grant,
-                    });
+                        self.co_authors,
+                    );
                 }
                 Ok(None) => {}
                 Err(e) => {
@@ -79,10 +94,13 @@ impl RuleEngine {
         for rule in &mut self.rules {
             match rule.commit_complete(ctx) {
                 Ok(Some(grant)) => {
-                    self.pending.push(RuleOutput {
-                        meta: rule.meta().clone(),
+                    push_grant(
+                        &mut self.pending,
+                        rule.as_ref(),
+                        ctx,
                         grant,
-                    });
+                        self.co_authors,
+                    );
                 }
                 Ok(None) => {}
                 Err(e) => {
@@ -232,11 +250,33 @@ impl RuleEngine {
     }
 }
 
+/// Queue a rule's grant for the commit in `ctx`, and its copies for the co-authors, if enabled
+fn push_grant(
+    pending: &mut Vec<RuleOutput>,
+    rule: &dyn RulePlugin,
+    ctx: &CommitContext,
+    grant: Grant,
+    co_authors: bool,
+) {
+    let shared = if co_authors && rule.credits_co_authors() {
+        grant.shared_with_co_authors(ctx)
+    } else {
+        Vec::new()
+    };
+    for grant in std::iter::once(grant).chain(shared) {
+        pending.push(RuleOutput {
+            meta: rule.meta().clone(),
+            grant,
+        });
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::observer::CoAuthor;
     use crate::observer::Observation;
-    use crate::rules::test_rules::{CountingRule, GrantOnDummy};
+    use crate::rules::test_rules::{CountingRule, GrantOnDummy, SharedGrantOnDummy};
 
     fn ctx() -> CommitContext {
         CommitContext {
@@ -244,6 +284,8 @@ mod tests {
             author_name: "Test".to_string(),
             author_email: "test@example.com".to_string(),
             commit_timestamp: chrono::DateTime::UNIX_EPOCH,
+            author_timestamp: chrono::DateTime::UNIX_EPOCH.fixed_offset(),
+            co_authors: Vec::new(),
         }
     }
 
@@ -265,6 +307,40 @@ mod tests {
         assert_eq!(outputs[0].grant.user_email, "test@example.com");
     }
 
+    #[test]
+    fn co_authors_share_opted_in_grants() {
+        let mut ctx = ctx();
+        ctx.co_authors.push(CoAuthor {
+            name: "Pair".to_string(),
+            email: "pair@example.com".to_string(),
+        });
+        let run = |co_authors: bool| {
+            let rules: Vec<Box<dyn RulePlugin>> = vec![
+                Box::new(GrantOnDummy::new(1)),
+                Box::new(SharedGrantOnDummy(GrantOnDummy::new(2))),
+            ];
+            let mut engine = RuleEngine::new(rules).with_co_authors(co_authors);
+            engine.on_commit_start(ctx.clone());
+            engine.on_observation(&Observation::Dummy);
+            engine
+                .on_commit_complete()
+                .into_iter()
+                .map(|o| (o.meta.id, o.grant.user_email))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            run(true),
+            [
+                (1, "test@example.com".to_string()),
+                (2, "test@example.com".to_string()),
+                (2, "pair@example.com".to_string()),
+            ]
+        );
+        // Rules only share credit if it's enabled in the config
+        assert_eq!(run(false).len(), 2);
+    }
+
     #[test]
     fn ignores_non_matching_observation() {
         let rules: Vec<Box<dyn RulePlugin>> = vec![Box::new(GrantOnDummy::new(100))];
@@ -15,6 +15,10 @@ pub trait RulePlugin {
     fn has_cache(&self) -> bool;
     /// Current version of this rule. See [Rule::VERSION].
     fn version(&self) -> u32;
+    /// Whether this rule shares its grants with co-authors. See [Rule::CREDITS_CO_AUTHORS].
+    fn credits_co_authors(&self) -> bool {
+        false
+    }
     /// Initialize the cache for this rule.
     fn init_cache(&mut self, cache: serde_json::Value) -> eyre::Result<()>;
     /// Finalize the cache for this rule.
@@ -42,6 +46,9 @@ impl<R: Rule> RulePlugin for R {
     fn version(&self) -> u32 {
         R::VERSION
     }
+    fn credits_co_authors(&self) -> bool {
+        R::CREDITS_CO_AUTHORS
+    }
     fn init_cache(&mut self, cache: serde_json::Value) -> eyre::Result<()> {
         let concrete = match cache {
             serde_json::Value::Null => R::Cache::default(),
@@ -75,24 +82,72 @@ impl<R: Rule> RulePlugin for R {
     }
 }
 
+/// Configuration for a rule, read from the `[rules.<KEY>]` table in the config file.
+///
+/// Registered with [RuleFactory::with_config], and read by the factory with [RulesConfig::get].
+/// Rules that share a configuration type register it under the same key.
+pub trait RuleConfig: Default + serde::Serialize + serde::de::DeserializeOwned {
+    /// The name of this rule's table under `[rules]`
+    const KEY: &'static str;
+}
+
+/// The type-erased [RuleConfig] registered with a [RuleFactory]
+pub struct ConfigSchema {
+    key: &'static str,
+    resolve: fn(Option<&toml::Value>) -> eyre::Result<toml::Value>,
+}
+
+impl ConfigSchema {
+    /// The name of the rule's table under `[rules]`
+    pub fn key(&self) -> &'static str {
+        self.key
+    }
+
+    /// Deserialize the given table, or use the default if `None`, and serialize it back
+    pub fn resolve(&self, value: Option<&toml::Value>) -> eyre::Result<toml::Value> {
+        (self.resolve)(value)
+    }
+}
+
+fn resolve_config<C: RuleConfig>(value: Option<&toml::Value>) -> eyre::Result<toml::Value> {
+    let config: C = match value {
+        Some(value) => value.clone().try_into()?,
+        None => C::default(),
+    };
+    Ok(toml::Value::try_from(config)?)
+}
+
 /// A factory to build [Rule]s.
 ///
 /// Each rule registers a [RuleFactory] via [inventory::submit!]. Rules that need configuration
-/// provide a custom factory; simple rules use [RuleFactory::default].
+/// provide a custom factory with [RuleFactory::with_config]; simple rules use
+/// [RuleFactory::default].
 pub struct RuleFactory {
     factory: fn(&RulesConfig) -> Box<dyn RulePlugin>,
+    config: Option<ConfigSchema>,
 }
 
 impl RuleFactory {
-    /// Provide your own factory to build your rule.
-    pub const fn new(factory: fn(&RulesConfig) -> Box<dyn RulePlugin>) -> Self {
-        Self { factory }
+    /// Provide your own factory to build a rule configured by `C`.
+    ///
+    /// Registering `C` here is what makes `[rules.<C::KEY>]` a valid config table.
+    pub const fn with_config<C: RuleConfig>(
+        factory: fn(&RulesConfig) -> Box<dyn RulePlugin>,
+    ) -> Self {
+        Self {
+            factory,
+            config: Some(ConfigSchema {
+                key: C::KEY,
+                resolve: resolve_config::<C>,
+            }),
+        }
     }
 
     /// Create a [RuleFactory] that uses [Default] to build a rule.
     pub const fn default<R: RulePlugin + Default + 'static>() -> Self {
         Self {
             factory: |_| Box::new(R::default()),
+            config: None,
         }
     }
 
@@ -102,17 +157,49 @@ impl RuleFactory {
     }
 }
 
+/// Get the schema of every registered [RuleConfig], sorted by key
+pub fn rule_config_schemas() -> Vec<&'static ConfigSchema> {
+    let mut schemas: Vec<_> = inventory::iter::<RuleFactory>
+        .into_iter()
+        .filter_map(|f| f.config.as_ref())
+        .collect();
+    schemas.sort_by_key(|s| s.key);
+    schemas.dedup_by_key(|s| s.key);
+    schemas
+}
+
 inventory::collect!(RuleFactory);
 
 /// Get a new instance of each registered rule, applying exclude/include filtering.
 pub fn builtin_rules(config: &RulesConfig) -> Vec<Box<dyn RulePlugin>> {
-    let excludes = config.exclude.as_deref().unwrap_or_default();
-    let includes = config.include.as_deref().unwrap_or_default();
+    filter_rules(registered_rules(config), config)
+}
 
-    let rules: Vec<_> = inventory::iter::<RuleFactory>
+/// Get the [builtin_rules], and each [external](crate::rules::external) rule declared in the
+/// config, applying exclude/include filtering.
+///
+/// Building external rules means validating their config, and loading any WebAssembly modules, so
+/// only use this where the external rules are needed.
+pub fn all_rules(config: &RulesConfig) -> Vec<Box<dyn RulePlugin>> {
+    let mut rules = registered_rules(config);
+    let mut taken_ids: Vec<_> = rules.iter().map(|r| r.meta().id).collect();
+    rules.extend(crate::rules::external::external_rules(
+        config,
+        &mut taken_ids,
+    ));
+    filter_rules(rules, config)
+}
+
+fn registered_rules(config: &RulesConfig) -> Vec<Box<dyn RulePlugin>> {
+    inventory::iter::<RuleFactory>
         .into_iter()
         .map(|f| f.build(config))
-        .collect();
+        .collect()
+}
+
+fn filter_rules(rules: Vec<Box<dyn RulePlugin>>, config: &RulesConfig) -> Vec<Box<dyn RulePlugin>> {
+    let excludes = config.exclude.as_deref().unwrap_or_default();
+    let includes = config.include.as_deref().unwrap_or_default();
 
     let mut rules: Vec<_> = rules
         .into_iter()
@@ -18,6 +18,7 @@ impl GrantOnDummy {
                 name: "Grant On Dummy",
                 description: "Grants on every Dummy observation",
                 kind: AchievementKind::PerUser { recurrent: false },
+                points: Meta::DEFAULT_POINTS,
             },
         }
     }
@@ -42,6 +43,26 @@ impl Rule for GrantOnDummy {
     }
 }
 
+/// A [GrantOnDummy] that shares its grants with co-authors. For co-author tests.
+pub struct SharedGrantOnDummy(pub GrantOnDummy);
+
+impl Rule for SharedGrantOnDummy {
+    type Cache = ();
+    const CREDITS_CO_AUTHORS: bool = true;
+
+    fn meta(&self) -> &Meta {
+        self.0.meta()
+    }
+
+    fn consumes(&self) -> &'static [Discriminant<Observation>] {
+        self.0.consumes()
+    }
+
+    fn process(&mut self, ctx: &CommitContext, obs: &Observation) -> eyre::Result<Option<Grant>> {
+        self.0.process(ctx, obs)
+    }
+}
+
 /// A rule that counts Dummy observations and grants in finalize. For testing caching, finalize,
 /// and stateful behavior.
 pub struct CountingRule {
@@ -59,6 +80,7 @@ impl CountingRule {
                 name: "Counting Rule",
                 description: "Counts Dummy observations and grants in finalize",
                 kind: AchievementKind::PerUser { recurrent: false },
+                points: Meta::DEFAULT_POINTS,
             },
             count: 0,
             last_ctx: None,
@@ -3,6 +3,7 @@ use std::path::Path;
 use herostratus::config::Config;
 use herostratus::git::clone::find_local_repository;
 use herostratus_tests::cmd::{CommandExt, TestHarness};
+use herostratus_tests::fixtures::repository::Builder;
 use predicates::prelude::*;
 use predicates::str;
 
@@ -82,9 +83,143 @@ fn search_current_repo_for_fixup_commits() {
     );
 }
 
+#[test]
+fn check_jsonl_format() {
+    let temp = Builder::new()
+        .commit("Initial commit")
+        .commit("fixup! Initial commit")
+        .author("Alice", "alice@example.com")
+        .build()
+        .unwrap();
+    let fixup = temp.repo.head_id().unwrap().detach();
+
+    let h = TestHarness::new();
+    h.write_config(&Config::default().disable("all").enable("H1-fixup"));
+
+    let mut cmd = h.command();
+    cmd.arg("check")
+        .arg(temp.tempdir.path())
+        .arg("--format=jsonl");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let records: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str(l).unwrap())
+        .collect();
+    assert_eq!(records.len(), 2, "{stdout}");
+
+    let grant = &records[0];
+    assert_eq!(grant["type"], "grant");
+    assert_eq!(grant["descriptor_id"], 1);
+    assert_eq!(grant["achievement_id"], "fixup");
+    assert_eq!(grant["commit"], fixup.to_string());
+    assert_eq!(grant["user_name"], "Alice");
+    assert_eq!(grant["user_email"], "alice@example.com");
+
+    let stats = &records[1];
+    assert_eq!(stats["type"], "stats");
+    assert_eq!(stats["commits_processed"], 2);
+    assert_eq!(stats["achievements_granted"], 1);
+    assert_eq!(stats["repository"], grant["repository"]);
+}
+
+#[test]
+fn check_credits_co_authors() {
+    let temp = Builder::new()
+        .commit("Initial commit")
+        .commit("fixup! Initial commit")
+        .author("Alice", "alice@example.com")
+        .body("Co-authored-by: Bob <bob@example.com>")
+        .build()
+        .unwrap();
+
+    let grantees = |config: Config| {
+        let h = TestHarness::new();
+        h.write_config(&config);
+        let mut cmd = h.command();
+        cmd.arg("check")
+            .arg(temp.tempdir.path())
+            .arg("--format=jsonl");
+        let output = cmd.captured_output();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout
+            .lines()
+            .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+            .filter(|r| r["type"] == "grant")
+            .map(|r| r["user_email"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    // Off by default
+    let config = Config::default().disable("all").enable("H1-fixup");
+    assert_eq!(grantees(config.clone()), ["alice@example.com"]);
+
+    let mut config = config;
+    config.rules.as_mut().unwrap().co_authors = Some(true);
+    assert_eq!(grantees(config), ["alice@example.com", "bob@example.com"]);
+}
+
 /// Run check on all local **and** remote branches in the herostratus repository
 ///
 /// The application should not crash nor exit with an error on any branch.
+/// Run `check --format=jsonl` with the given args, and return (commits processed, grants)
+fn check_filtered(path: &Path, args: &[&str]) -> Option<(u64, u64)> {
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("check").arg(path).arg("--format=jsonl").args(args);
+    let output = cmd.captured_output();
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stats: serde_json::Value = serde_json::from_str(stdout.lines().last().unwrap()).unwrap();
+    Some((
+        stats["commits_processed"].as_u64().unwrap(),
+        stats["achievements_granted"].as_u64().unwrap(),
+    ))
+}
+
+#[test]
+fn check_rule_and_commit_filters() {
+    let temp = Builder::new()
+        .commit("Initial commit")
+        .tag("base")
+        .commit("fixup! Initial commit")
+        .author("Alice", "alice@example.com")
+        .file("docs/a.md", b"a")
+        .commit("fixup! Initial commit")
+        .author("Bob", "bob@example.com")
+        .file("src/b.rs", b"b")
+        .build()
+        .unwrap();
+    let path = temp.tempdir.path();
+
+    assert_eq!(check_filtered(path, &["--rule", "H1"]), Some((3, 2)));
+    assert_eq!(
+        check_filtered(path, &["--rule", "fixup", "--exclude-rule", "H1-fixup"]),
+        Some((3, 0))
+    );
+    assert_eq!(
+        check_filtered(path, &["--rule", "H1", "--author", "BOB"]),
+        Some((1, 1))
+    );
+    assert_eq!(
+        check_filtered(path, &["--rule", "H1", "--", "src"]),
+        Some((1, 1))
+    );
+    assert_eq!(
+        check_filtered(path, &["base..HEAD~1", "--rule", "H1"]),
+        Some((1, 1))
+    );
+    assert_eq!(
+        check_filtered(path, &["--rule", "H1", "--since", "2100-01-01"]),
+        Some((0, 0))
+    );
+    assert_eq!(check_filtered(path, &["--rule", "no-such-rule"]), None);
+}
+
 #[test]
 #[cfg_attr(not(feature = "ci"), ignore = "Slow test, only run in CI")]
 fn smoke_test_on_all_own_branches() {
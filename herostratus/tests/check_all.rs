@@ -195,3 +195,248 @@ fn revoke_and_regrant() {
         "Should grant to Bob: {grant_line}"
     );
 }
+
+#[test]
+fn summary_prints_top_scorers() {
+    let temp_upstream = Builder::new()
+        .commit("What the hell")
+        .author("Alice", "alice@example.com")
+        .commit("Nothing to see here")
+        .author("Bob", "bob@example.com")
+        .build()
+        .unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add").arg(&url);
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    h.update_config(|c| c.disable("all").enable("H10-most-profound"));
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--summary");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Most Profound is global-revocable, so it's worth double its 10 points
+    assert!(stdout.contains("## Top scorers"), "{stdout}");
+    assert!(stdout.contains("| 1 | Alice | 20 | 1 |"), "{stdout}");
+    assert!(!stdout.contains("| Bob |"), "{stdout}");
+}
+
+#[test]
+fn check_all_integrations() {
+    let temp_upstream = Builder::new().commit("commit1").build().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add").arg(url);
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let site_dir = h.path().join("site");
+    let site = site_dir.clone();
+    h.update_config(move |mut c| {
+        c.integrations = Some(herostratus::config::IntegrationsConfig {
+            enabled: Some(vec!["csv-export".into(), "static-site".into()]),
+            static_site: Some(herostratus::config::StaticSiteConfig {
+                output_dir: Some(site),
+                ..Default::default()
+            }),
+        });
+        c
+    });
+
+    // The CLI overrides the config, so the site isn't rendered
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--integration=csv-export");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    assert!(h.path().join("export/achievements.csv").exists());
+    assert!(h.path().join("export/repositories.csv").exists());
+    assert!(!site_dir.exists());
+
+    // The config enables the static site, which is rendered after all repositories are checked
+    let mut cmd = h.command();
+    cmd.arg("check-all");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    assert!(site_dir.join("index.html").exists());
+}
+
+#[test]
+fn check_all_jobs_output_is_deterministic() {
+    let upstreams: Vec<_> = (0..4)
+        .map(|i| {
+            Builder::new()
+                .commit(&format!("fixup! repo {i}"))
+                .author(&format!("Author {i}"), &format!("author{i}@example.com"))
+                .commit(&format!("fixup! again {i}"))
+                .build()
+                .unwrap()
+        })
+        .collect();
+
+    let run = |jobs: &str| {
+        let h = TestHarness::new();
+        for upstream in &upstreams {
+            let url = format!("file://{}", upstream.tempdir.path().display());
+            let mut cmd = h.command();
+            cmd.arg("add").arg(url);
+            assert!(cmd.captured_output().status.success());
+        }
+        h.update_config(|c| c.disable("all").enable("H1-fixup"));
+
+        let mut cmd = h.command();
+        cmd.arg("check-all").arg("--jobs").arg(jobs);
+        let output = cmd.captured_output();
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let repositories =
+            std::fs::read_to_string(h.path().join("export/repositories.csv")).unwrap();
+        (stdout, repositories)
+    };
+
+    let (serial_stdout, serial_csv) = run("1");
+    let (parallel_stdout, parallel_csv) = run("3");
+    assert_eq!(serial_stdout.lines().count(), 8, "{serial_stdout}");
+    assert_eq!(serial_stdout, parallel_stdout);
+    assert_eq!(serial_csv, parallel_csv);
+}
+
+/// The number of commits processed, summed over every pipeline run in the log
+fn commits_processed(stderr: &str) -> usize {
+    stderr
+        .lines()
+        .filter_map(|line| line.split_once("after processing ")?.1.split_once(' '))
+        .map(|(n, _)| n.parse::<usize>().unwrap())
+        .sum()
+}
+
+#[test]
+fn check_all_tracks_several_branches() {
+    let temp_upstream = Builder::new()
+        .commit("fixup! shared")
+        .author("Shared", "shared@example.com")
+        .time(1000)
+        .build()
+        .unwrap();
+    temp_upstream.set_branch("release/1.0").unwrap();
+    let release1 = temp_upstream
+        .commit("fixup! release 1.0")
+        .author("Alice", "alice@example.com")
+        .create()
+        .unwrap();
+    temp_upstream.set_branch("main").unwrap();
+    temp_upstream.set_branch("release/2.0").unwrap();
+    let release2 = temp_upstream
+        .commit("fixup! release 2.0")
+        .author("Bob", "bob@example.com")
+        .create()
+        .unwrap();
+    temp_upstream.set_branch("main").unwrap();
+    let main = temp_upstream
+        .commit("fixup! main")
+        .author("Carol", "carol@example.com")
+        .create()
+        .unwrap();
+    let name = temp_upstream.tempdir.path().file_name().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add")
+        .arg(url)
+        .arg("main")
+        .arg("--also-branch")
+        .arg("release/*");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    h.update_config(|c| c.disable("all").enable("H1-fixup"));
+
+    let mut cmd = h.command();
+    cmd.arg("check-all");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The shared commit is only processed once, for main
+    assert_eq!(commits_processed(&stderr), 4, "{stderr}");
+    for commit in [main, release1, release2] {
+        assert_grants(&stdout, commit, "I'll fix that up later");
+    }
+    let cache_dir = h.path().join("cache").join(name);
+    assert!(cache_dir.join("checkpoint.json").exists());
+    assert!(cache_dir.join("checkpoints/release/1.0.json").exists());
+    assert!(cache_dir.join("checkpoints/release/2.0.json").exists());
+
+    // Only the new commit on the release branch is processed
+    temp_upstream.set_branch("release/1.0").unwrap();
+    let patch = temp_upstream
+        .commit("fixup! release 1.0.1")
+        .author("Dave", "dave@example.com")
+        .create()
+        .unwrap();
+    let mut cmd = h.command();
+    cmd.arg("check-all");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(commits_processed(&stderr), 1, "{stderr}");
+    assert_grants(&stdout, patch, "I'll fix that up later");
+    assert!(!stdout.contains(&release1.to_string()), "{stdout}");
+}
+
+#[test]
+fn check_all_depth_does_not_hide_unprocessed_commits_from_other_branches() {
+    let temp_upstream = Builder::new()
+        .commit("fixup! shared")
+        .author("Shared", "shared@example.com")
+        .time(1000)
+        .build()
+        .unwrap();
+    let shared = temp_upstream.repo.head_id().unwrap().detach();
+    temp_upstream.set_branch("release").unwrap();
+    let release = temp_upstream
+        .commit("fixup! release")
+        .author("Alice", "alice@example.com")
+        .create()
+        .unwrap();
+    temp_upstream.set_branch("main").unwrap();
+    for subject in ["fixup! main 1", "fixup! main 2"] {
+        temp_upstream
+            .commit(subject)
+            .author("Bob", "bob@example.com")
+            .create()
+            .unwrap();
+    }
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add")
+        .arg(url)
+        .arg("main")
+        .arg("--also-branch")
+        .arg("release");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    h.update_config(|c| c.disable("all").enable("H1-fixup"));
+
+    // --depth stops main's walk before the shared commit, so release still has to process it
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--depth").arg("2");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert_eq!(commits_processed(&stderr), 4, "{stderr}");
+    for commit in [release.detach(), shared] {
+        assert_grants(&stdout, commit, "I'll fix that up later");
+    }
+}
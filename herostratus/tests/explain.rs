@@ -0,0 +1,157 @@
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness};
+use herostratus_tests::fixtures::repository::Builder;
+
+#[test]
+fn explain_fixup_commit() {
+    let temp_repo = Builder::new()
+        .commit("Add mailmap")
+        .file(
+            ".mailmap",
+            b"Test User <testuser@example.com> <12345+testuser@users.noreply.github.com>\n",
+        )
+        .build()
+        .unwrap();
+    temp_repo
+        .commit("fixup! Add mailmap")
+        .author("testuser", "12345+testuser@users.noreply.github.com")
+        .create()
+        .unwrap();
+
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("explain").arg(temp_repo.path()).arg("HEAD");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Author:    testuser <12345+testuser@users.noreply.github.com>"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("Mailmap:   Test User <testuser@example.com>"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("  Fixup\n      consumed by H1-fixup\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("H1-fixup: granted to Test User <testuser@example.com>"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("H4-non-unicode: no grant (consumed no observations)"),
+        "{stdout}"
+    );
+    // H2 needs to see every commit before it can decide
+    assert!(
+        stdout.contains("H2-shortest-subject-line: no grant\n      stateful:"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_uses_repository_mailmap() {
+    let temp_repo = Builder::new()
+        .commit("fixup! Initial")
+        .author("testuser", "12345+testuser@users.noreply.github.com")
+        .build()
+        .unwrap();
+    let mailmap = temp_repo.tempdir.path().join("mailmap");
+    std::fs::write(
+        &mailmap,
+        "Test User <testuser@example.com> <12345+testuser@users.noreply.github.com>\n",
+    )
+    .unwrap();
+
+    // The repository's name doesn't match its directory, like with `add --name`
+    let h = TestHarness::new();
+    let mut config = Config::default();
+    config.repositories.insert(
+        "custom-name".into(),
+        herostratus::config::RepositoryConfig {
+            path: temp_repo.path().to_path_buf(),
+            mailmap_file: Some(mailmap),
+            ..Default::default()
+        },
+    );
+    h.write_config(&config);
+    let mut cmd = h.command();
+    cmd.arg("explain").arg(temp_repo.path()).arg("HEAD");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Mailmap:   Test User <testuser@example.com>"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_only_selected_rules() {
+    let temp_repo = Builder::new().commit("fixup! Initial").build().unwrap();
+
+    let h = TestHarness::new();
+    h.write_config(&Config::default().disable("H1-fixup"));
+    let mut cmd = h.command();
+    cmd.arg("explain")
+        .arg(temp_repo.path())
+        .arg("--rule")
+        .arg("fixup");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // --rule overrides the config's excludes
+    assert!(stdout.contains("H1-fixup: granted"), "{stdout}");
+    assert!(!stdout.contains("H2-shortest-subject-line"), "{stdout}");
+    assert!(
+        stdout.contains("  Subject {") && stdout.contains("not consumed by any rule"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_shared_credit() {
+    let temp_repo = Builder::new()
+        .commit("fixup! Initial")
+        .body("Co-authored-by: Bob <bob@example.com>")
+        .build()
+        .unwrap();
+
+    let h = TestHarness::new();
+    let mut config = Config::default();
+    config.rules.get_or_insert_default().co_authors = Some(true);
+    h.write_config(&config);
+    let mut cmd = h.command();
+    cmd.arg("explain")
+        .arg(temp_repo.path())
+        .arg("--rule")
+        .arg("fixup");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(
+        stdout.contains("Co-authors: Bob <bob@example.com>\n"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("H1-fixup: granted to Bob <bob@example.com>"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn explain_missing_commit() {
+    let temp_repo = Builder::new().commit("Initial").build().unwrap();
+
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("explain")
+        .arg(temp_repo.path())
+        .arg("does-not-exist");
+    let output = cmd.captured_output();
+    assert!(!output.status.success());
+}
@@ -7,7 +7,7 @@ fn h012_quine_commit() {
     // Set min_matched_chars = 10 so only the original quine commit (588b41b6e9, 10 chars)
     // triggers, not the newer 7-char quine added for fortune-teller testing.
     let mut config = Config::default().disable("all").enable("H12-quine-commit");
-    config.rules.as_mut().unwrap().h12_quine_commit = Some(H012Config {
+    config.rules.as_mut().unwrap().set(H012Config {
         min_matched_chars: 10,
     });
     let h = TestHarness::new();
@@ -0,0 +1,49 @@
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness};
+use herostratus_tests::fixtures::repository::Builder;
+
+#[test]
+fn h021_most_thanked_reviewer() {
+    let temp = Builder::new()
+        .commit("Add the frobnicator")
+        .author("Alice", "alice@example.com")
+        .body("Reviewed-by: Bob <bob@example.com>\nSigned-off-by: Alice <alice@example.com>")
+        .commit("Fix the frobnicator")
+        .author("Alice", "alice@example.com")
+        .body("Reviewed-by: Carol <carol@example.com>")
+        .commit("Document the frobnicator")
+        .author("Bob", "bob@example.com")
+        .body("Reviewed-by: Carol <carol@example.com>\nReviewed-by: Bob <bob@example.com>")
+        .commit("Test the frobnicator")
+        .author("Bob", "bob@example.com")
+        .body("Reviewed-by: Bob <bob@example.com>")
+        .build()
+        .unwrap();
+
+    let h = TestHarness::new();
+    h.write_config(
+        &Config::default()
+            .disable("all")
+            .enable("H21-most-thanked-reviewer"),
+    );
+
+    let mut cmd = h.command();
+    cmd.arg("check")
+        .arg(temp.tempdir.path())
+        .arg("--format=jsonl");
+    let output = cmd.captured_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+
+    // Bob is named by more Reviewed-by trailers than Carol, but half of them are on Bob's own
+    // commits
+    let grants: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|l| serde_json::from_str::<serde_json::Value>(l).unwrap())
+        .filter(|r| r["type"] == "grant")
+        .collect();
+    assert_eq!(grants.len(), 1, "{stdout}");
+    assert_eq!(grants[0]["achievement_id"], "most-thanked-reviewer");
+    assert_eq!(grants[0]["user_name"], "Carol");
+    assert_eq!(grants[0]["user_email"], "carol@example.com");
+}
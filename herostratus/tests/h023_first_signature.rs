@@ -0,0 +1,40 @@
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness, assert_grants};
+use herostratus_tests::fixtures::repository::Builder;
+use herostratus_tests::fixtures::signatures::{SSH_SIGNATURE, X509_SIGNATURE};
+
+#[test]
+fn h023_first_signature() {
+    let temp = Builder::new()
+        .commit("Unsigned")
+        .author("Alice", "alice@example.com")
+        .commit("Signed with SSH")
+        .author("Bob", "bob@example.com")
+        .gpgsig(SSH_SIGNATURE)
+        .build()
+        .unwrap();
+    let first = temp.repo.head_id().unwrap().detach();
+    let second = temp
+        .commit("Signed with X.509")
+        .author("Alice", "alice@example.com")
+        .gpgsig(X509_SIGNATURE)
+        .create()
+        .unwrap()
+        .detach();
+
+    let h = TestHarness::new();
+    h.write_config(
+        &Config::default()
+            .disable("all")
+            .enable("H23-first-signature"),
+    );
+
+    let mut cmd = h.command();
+    cmd.arg("check").arg(temp.tempdir.path()).arg("HEAD");
+    let output = cmd.captured_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+
+    assert_grants(&stdout, first, "John Hancock");
+    assert!(!stdout.contains(&second.to_string()), "{stdout}");
+}
@@ -0,0 +1,36 @@
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness, assert_grants};
+use herostratus_tests::fixtures::repository::Builder;
+
+#[test]
+fn h024_night_owl_uses_local_time() {
+    // 2026-03-02 14:30 UTC is 02:30 the next morning in UTC+12
+    let temp = Builder::new()
+        .commit("Late night in Auckland")
+        .time(1_772_461_800)
+        .timezone(12 * 3600)
+        .build()
+        .unwrap();
+    let night = temp.repo.head_id().unwrap().detach();
+
+    // 2026-03-03 02:00 UTC is 19:00 the evening before in UTC-7
+    let evening = temp
+        .commit("Evening in Denver")
+        .time(1_772_503_200)
+        .timezone(-7 * 3600)
+        .create()
+        .unwrap()
+        .detach();
+
+    let h = TestHarness::new();
+    h.write_config(&Config::default().disable("all").enable("H24-night-owl"));
+
+    let mut cmd = h.command();
+    cmd.arg("check").arg(temp.tempdir.path()).arg("HEAD");
+    let output = cmd.captured_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+
+    assert_grants(&stdout, night, "Night Owl");
+    assert!(!stdout.contains(&evening.to_string()), "{stdout}");
+}
@@ -0,0 +1,50 @@
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness, assert_grants};
+use herostratus_tests::fixtures::repository::Builder;
+
+#[test]
+fn h028_h029_moved_files_only_count_changed_lines() {
+    let lines: Vec<String> = (1..=30).map(|i| format!("line {i}\n")).collect();
+    let content = lines.concat();
+    let trimmed = lines[..25].concat();
+
+    let temp = Builder::new()
+        .commit("Add the library")
+        .file("lib.txt", content.as_bytes())
+        .build()
+        .unwrap();
+    let added = temp.repo.head_id().unwrap().detach();
+
+    // Without rename detection, this would remove and add 30 lines, making it the biggest commit
+    let moved = temp
+        .commit("Move the library")
+        .remove("lib.txt")
+        .file("src/lib.txt", content.as_bytes())
+        .create()
+        .unwrap()
+        .detach();
+    let trimmed = temp
+        .commit("Remove dead code")
+        .file("src/lib.txt", trimmed.as_bytes())
+        .create()
+        .unwrap()
+        .detach();
+
+    let h = TestHarness::new();
+    h.write_config(
+        &Config::default()
+            .disable("all")
+            .enable("H28-mega-commit")
+            .enable("H29-marie-kondo"),
+    );
+
+    let mut cmd = h.command();
+    cmd.arg("check").arg(temp.tempdir.path()).arg("HEAD");
+    let output = cmd.captured_output();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+
+    assert_grants(&stdout, added, "Mega Commit");
+    assert_grants(&stdout, trimmed, "Marie Kondo");
+    assert!(!stdout.contains(&moved.to_string()), "{stdout}");
+}
@@ -0,0 +1,139 @@
+use std::path::Path;
+
+use herostratus_tests::cmd::{CommandExt, TestHarness};
+
+fn write_export(dir: &Path) {
+    std::fs::create_dir_all(dir.join("events")).unwrap();
+    std::fs::write(
+        dir.join("achievements.csv"),
+        "id,human_id,name,description,kind,points\n\
+         1,fixup,Leftovers,Prefix a commit with fixup,per-user,10\n\
+         2,shortest-subject-line,Brevity,The shortest subject line,global-revocable,20\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("events/repo-a.csv"),
+        "timestamp,event,achievement_id,commit,user_name,user_email\n\
+         2026-01-01T00:00:00Z,grant,fixup,0101010101010101010101010101010101010101,Alice,alice@example.com\n\
+         2026-01-02T00:00:00Z,grant,shortest-subject-line,0202020202020202020202020202020202020202,Bob,bob@example.com\n\
+         2026-01-04T00:00:00Z,revoke,shortest-subject-line,0202020202020202020202020202020202020202,Bob,bob@example.com\n\
+         2026-01-04T00:00:00Z,grant,shortest-subject-line,0404040404040404040404040404040404040404,Alice,alice@example.com\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("events/repo-b.csv"),
+        "timestamp,event,achievement_id,commit,user_name,user_email\n\
+         2026-01-03T00:00:00Z,grant,fixup,0303030303030303030303030303030303030303,Bob,bob@example.com\n",
+    )
+    .unwrap();
+}
+
+fn query(export_dir: &Path, args: &[&str]) -> String {
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("query")
+        .arg("--export-dir")
+        .arg(export_dir)
+        .args(args);
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+fn rows(stdout: &str) -> Vec<&str> {
+    stdout.lines().skip(2).collect()
+}
+
+#[test]
+fn query_by_user_across_repositories() {
+    let dir = tempfile::tempdir().unwrap();
+    write_export(dir.path());
+
+    let stdout = query(dir.path(), &["--user", "BOB@example.com"]);
+    assert!(
+        stdout.starts_with("| Date | Repository | Event |"),
+        "{stdout}"
+    );
+    assert_eq!(
+        rows(&stdout),
+        [
+            "| 2026-01-02 00:00:00 | repo-a | grant | Brevity (H2-shortest-subject-line) | Bob <bob@example.com> | 0202020202 | revoked |",
+            "| 2026-01-03 00:00:00 | repo-b | grant | Leftovers (H1-fixup) | Bob <bob@example.com> | 0303030303 | active |",
+            "| 2026-01-04 00:00:00 | repo-a | revoke | Brevity (H2-shortest-subject-line) | Bob <bob@example.com> | 0202020202 |  |",
+        ]
+    );
+
+    // Revoked grants aren't held
+    let stdout = query(dir.path(), &["--user", "bob", "--active"]);
+    assert_eq!(rows(&stdout).len(), 1);
+    assert!(stdout.contains("Leftovers (H1-fixup)"), "{stdout}");
+}
+
+#[test]
+fn query_achievement_history() {
+    let dir = tempfile::tempdir().unwrap();
+    write_export(dir.path());
+
+    // Any form of the achievement ID is accepted
+    for id in [
+        "2",
+        "H2",
+        "shortest-subject-line",
+        "H2-shortest-subject-line",
+    ] {
+        let stdout = query(dir.path(), &["--achievement", id, "--event", "grant"]);
+        let rows = rows(&stdout);
+        assert_eq!(rows.len(), 2, "{id}: {stdout}");
+        assert!(rows[0].contains("Bob") && rows[0].ends_with("| revoked |"));
+        assert!(rows[1].contains("Alice") && rows[1].ends_with("| active |"));
+    }
+}
+
+#[test]
+fn query_json_with_filters() {
+    let dir = tempfile::tempdir().unwrap();
+    write_export(dir.path());
+
+    let stdout = query(
+        dir.path(),
+        &[
+            "--repository",
+            "repo-a",
+            "--since",
+            "2026-01-02",
+            "--until",
+            "2026-01-04",
+            "--format",
+            "json",
+        ],
+    );
+    let results: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        results,
+        serde_json::json!([{
+            "timestamp": "2026-01-02T00:00:00Z",
+            "repository": "repo-a",
+            "event": "grant",
+            "achievement_id": "shortest-subject-line",
+            "achievement_name": "Brevity",
+            "user_name": "Bob",
+            "user_email": "bob@example.com",
+            "commit": "0202020202020202020202020202020202020202",
+            "active": false,
+        }])
+    );
+}
+
+#[test]
+fn query_unknown_achievement() {
+    let dir = tempfile::tempdir().unwrap();
+    write_export(dir.path());
+
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("query")
+        .arg("--export-dir")
+        .arg(dir.path())
+        .arg("--achievement")
+        .arg("H99");
+    let output = cmd.captured_output();
+    assert!(!output.status.success());
+}
@@ -0,0 +1,93 @@
+use herostratus::config::read_config;
+use herostratus_tests::cmd::{CommandExt, TestHarness};
+use herostratus_tests::fixtures::repository::Builder;
+
+fn add(h: &TestHarness, url: &str, name: &str, skip_clone: bool) {
+    let mut cmd = h.command();
+    cmd.arg("add").arg(url).arg("--name").arg(name);
+    if skip_clone {
+        cmd.arg("--skip-clone");
+    }
+    assert!(cmd.captured_output().status.success());
+}
+
+#[test]
+fn remove_without_purge_only_edits_config() {
+    let temp_upstream = Builder::new().commit("first").build().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    add(&h, &url, "a", false);
+    add(&h, &url, "b", true);
+
+    // The URL is ambiguous
+    let mut cmd = h.command();
+    cmd.arg("remove").arg(&url);
+    let output = cmd.captured_output();
+    assert!(!output.status.success());
+    assert_eq!(read_config(h.path()).unwrap().repositories.len(), 2);
+
+    let mut cmd = h.command();
+    cmd.arg("remove").arg("a");
+    assert!(cmd.captured_output().status.success());
+
+    let config = read_config(h.path()).unwrap();
+    assert!(!config.repositories.contains_key("a"));
+    assert!(config.repositories["b"].path.exists());
+
+    // Now that it's unambiguous, the URL works too
+    let mut cmd = h.command();
+    cmd.arg("remove").arg(&url);
+    assert!(cmd.captured_output().status.success());
+    assert!(read_config(h.path()).unwrap().repositories.is_empty());
+
+    let mut cmd = h.command();
+    cmd.arg("remove").arg("a");
+    assert!(!cmd.captured_output().status.success());
+}
+
+#[test]
+fn remove_purge() {
+    let temp_upstream = Builder::new().commit("first").build().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    add(&h, &url, "a", false);
+    add(&h, &url, "b", true);
+    let clone = read_config(h.path()).unwrap().repositories["a"]
+        .path
+        .clone();
+
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    assert!(cmd.captured_output().status.success());
+
+    let events_a = h.path().join("export/events/a.csv");
+    let events_b = h.path().join("export/events/b.csv");
+    let repositories = h.path().join("export/repositories.csv");
+    assert!(h.path().join("cache/a").exists());
+    assert!(events_a.exists());
+
+    // The clone is shared with "b", so it's kept
+    let mut cmd = h.command();
+    cmd.arg("remove")
+        .arg("a")
+        .arg("--purge")
+        .arg("--keep-events");
+    assert!(cmd.captured_output().status.success());
+    assert!(clone.exists());
+    assert!(!h.path().join("cache/a").exists());
+    assert!(events_a.exists());
+    let contents = std::fs::read_to_string(&repositories).unwrap();
+    assert!(contents.lines().any(|l| l.starts_with("a,")), "{contents}");
+
+    let mut cmd = h.command();
+    cmd.arg("remove").arg("b").arg("--purge");
+    assert!(cmd.captured_output().status.success());
+    assert!(!clone.exists());
+    assert!(!h.path().join("cache/b").exists());
+    assert!(!events_b.exists());
+    let contents = std::fs::read_to_string(&repositories).unwrap();
+    assert!(!contents.lines().any(|l| l.starts_with("b,")), "{contents}");
+    assert!(read_config(h.path()).unwrap().repositories.is_empty());
+}
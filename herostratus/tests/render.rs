@@ -140,6 +140,440 @@ fn render_cross_links_work() {
     assert!(achv_page.contains("repo/test-repo.html"));
 }
 
+#[test]
+fn render_leaderboard() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let page = std::fs::read_to_string(output_dir.join("leaderboard.html")).unwrap();
+    assert!(page.contains("user/alice-smith.html"));
+    assert!(page.contains("repo/test-repo.html"));
+    assert!(page.contains("achievement/shortest.html"));
+
+    // The fixture has no points column, so each achievement is worth the default 10 points, and
+    // Alice's global-revocable Brevity is doubled
+    let alice = page
+        .find("<td>30</td>")
+        .expect("Alice has 30 points overall");
+    let bob = page.find("<td>10</td>").expect("Bob has 10 points");
+    assert!(alice < bob, "Alice should be ranked above Bob");
+}
+
+#[test]
+fn render_atom_feeds() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let render = |output_dir: &Path| {
+        let mut cmd = TestHarness::stateless_command();
+        cmd.arg("render")
+            .arg("-e")
+            .arg(dir.path().join("export"))
+            .arg("-o")
+            .arg(output_dir)
+            .arg("-t")
+            .arg(templates_dir())
+            .arg("--base-url")
+            .arg("https://example.com/herostratus/");
+        let output = cmd.captured_output();
+        assert!(output.status.success());
+    };
+    let first = dir.path().join("first");
+    let second = dir.path().join("second");
+    render(&first);
+    render(&second);
+
+    // minijinja escapes slashes in attributes, which XML readers unescape
+    let feed = std::fs::read_to_string(first.join("feed.xml"))
+        .unwrap()
+        .replace("&#x2f;", "/");
+    assert!(feed.contains(r#"<feed xmlns="http://www.w3.org/2005/Atom">"#));
+    assert!(feed.contains(
+        "<id>urn:herostratus:test-repo:fixup:aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa:grant:alice-smith</id>"
+    ));
+    assert!(feed.contains(r#"href="https://example.com/herostratus/feed.xml""#));
+    assert!(feed.contains("<updated>2026-01-03T00:00:00Z</updated>"));
+    let index = std::fs::read_to_string(first.join("index.html")).unwrap();
+    assert!(index.contains(r#"type="application/atom+xml""#));
+
+    let repo_feed = std::fs::read_to_string(first.join("repo/test-repo.xml")).unwrap();
+    assert_eq!(repo_feed.matches("<entry>").count(), 3);
+
+    let user_feed = std::fs::read_to_string(first.join("user/bob-jones.xml")).unwrap();
+    assert_eq!(user_feed.matches("<entry>").count(), 1);
+    assert!(user_feed.contains("Bob Jones earned Leftovers in test-repo"));
+
+    // Re-rendering the same data gives identical feeds, so readers don't see duplicates
+    for feed in ["feed.xml", "repo/test-repo.xml", "user/alice-smith.xml"] {
+        assert_eq!(
+            std::fs::read_to_string(first.join(feed)).unwrap(),
+            std::fs::read_to_string(second.join(feed)).unwrap(),
+        );
+    }
+}
+
+#[test]
+fn render_emit_json() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir())
+        .arg("--emit-json");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let read = |name: &str| -> serde_json::Value {
+        let contents = std::fs::read_to_string(output_dir.join(name)).unwrap();
+        let document: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        assert_eq!(document["schema_version"], 1, "{name}");
+        document["data"].clone()
+    };
+
+    let achievements = read("achievements.json");
+    assert_eq!(achievements.as_array().unwrap().len(), 2);
+    assert_eq!(achievements[0]["human_id"], "fixup");
+    assert_eq!(achievements[0]["unique_holders"], 2);
+
+    let activity = read("activity.json");
+    assert_eq!(activity.as_array().unwrap().len(), 3);
+    assert_eq!(activity[0]["achievement_human_id"], "shortest");
+
+    let repo = read("repos/test-repo.json");
+    assert_eq!(repo["commits_checked"], 42);
+    assert_eq!(repo["events"].as_array().unwrap().len(), 3);
+
+    let user = read("users/alice-smith.json");
+    assert_eq!(user["name"], "Alice Smith");
+    assert_eq!(user["total_achievements"], 2);
+    assert!(
+        user.get("email").is_none(),
+        "emails should not be published"
+    );
+}
+
+#[test]
+fn render_without_emit_json() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    assert!(!output_dir.join("achievements.json").exists());
+    assert!(!output_dir.join("users").exists());
+}
+
+#[test]
+fn render_badges() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let badge = |name: &str| std::fs::read_to_string(output_dir.join(name)).unwrap();
+
+    let alice = badge("badge/user/alice-smith.svg");
+    assert!(alice.contains("herostratus: 2 achievements"));
+    // Brevity is global-revocable, which is rarer than Leftovers
+    assert!(alice.contains(r##"fill="#a350c8""##));
+    let bob = badge("badge/user/bob-jones.svg");
+    assert!(bob.contains("herostratus: 1 achievement<"));
+    assert!(bob.contains(r##"fill="#4c1""##));
+
+    assert!(badge("badge/achievement/shortest.svg").contains("Brevity: holder: Alice Smith"));
+    assert!(badge("badge/achievement/fixup.svg").contains("Leftovers: 2 holders"));
+    assert!(badge("badge/repo/test-repo.svg").contains("herostratus: 3 achievements"));
+
+    let user_page = std::fs::read_to_string(output_dir.join("user/alice-smith.html")).unwrap();
+    assert!(user_page.contains("badge/user/alice-smith.svg"));
+}
+
+#[test]
+fn render_avatars() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+    // Avatar overrides are read from the config in the data directory, relative to it
+    write_file(dir.path(), "avatars/bob.jpg", "bob's face");
+    write_file(
+        dir.path(),
+        "config.toml",
+        "repositories = {}\n\
+         [integrations.static_site.avatars]\n\
+         \"bob@example.com\" = \"avatars/bob.jpg\"\n",
+    );
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("--data-dir")
+        .arg(dir.path())
+        .arg("render")
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let alice = std::fs::read_to_string(output_dir.join("avatar/alice-smith.svg")).unwrap();
+    assert!(alice.starts_with("<svg "));
+    let bob = std::fs::read_to_string(output_dir.join("avatar/bob-jones.jpg")).unwrap();
+    assert_eq!(bob, "bob's face");
+    assert!(!output_dir.join("avatar/bob-jones.svg").exists());
+
+    // minijinja escapes the slashes in attributes
+    let users_page = std::fs::read_to_string(output_dir.join("users.html"))
+        .unwrap()
+        .replace("&#x2f;", "/");
+    assert!(users_page.contains(r#"src="./avatar/alice-smith.svg""#));
+    assert!(users_page.contains(r#"src="./avatar/bob-jones.jpg""#));
+    let user_page = std::fs::read_to_string(output_dir.join("user/bob-jones.html"))
+        .unwrap()
+        .replace("&#x2f;", "/");
+    assert!(user_page.contains(r#"src="../avatar/bob-jones.jpg""#));
+}
+
+#[test]
+fn render_custom_css_header_and_footer() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+    write_file(dir.path(), "brand/extra.css", "nav { color: hotpink; }");
+    write_file(
+        dir.path(),
+        "brand/header.html",
+        r#"<div class="banner">ACME Corp</div>"#,
+    );
+    write_file(
+        dir.path(),
+        "brand/footer.html",
+        r#"<a href="https://acme.example">Intranet</a>"#,
+    );
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir())
+        .arg("--extra-css")
+        .arg(dir.path().join("brand/extra.css"))
+        .arg("--header-html")
+        .arg(dir.path().join("brand/header.html"))
+        .arg("--footer-html")
+        .arg(dir.path().join("brand/footer.html"));
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let css = std::fs::read_to_string(output_dir.join("extra.css")).unwrap();
+    assert_eq!(css, "nav { color: hotpink; }");
+
+    for page in ["index.html", "user/alice-smith.html"] {
+        let html = std::fs::read_to_string(output_dir.join(page))
+            .unwrap()
+            .replace("&#x2f;", "/");
+        assert!(html.contains("extra.css"), "{page}");
+        // The snippets are inserted as-is, without escaping
+        assert!(
+            html.contains(r#"<div class="banner">ACME Corp</div>"#),
+            "{page}"
+        );
+        assert!(
+            html.contains(r#"<a href="https://acme.example">Intranet</a>"#),
+            "{page}"
+        );
+    }
+    let nested = std::fs::read_to_string(output_dir.join("user/alice-smith.html"))
+        .unwrap()
+        .replace("&#x2f;", "/");
+    assert!(nested.contains(r#"href="../extra.css""#));
+}
+
+#[test]
+fn render_customizations_from_config() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+    // Like the avatars, these are read from the config in the data directory, relative to it
+    write_file(dir.path(), "brand/extra.css", "nav { color: hotpink; }");
+    write_file(dir.path(), "brand/header.html", "<b>From the config</b>");
+    write_file(
+        dir.path(),
+        "brand/footer.html",
+        "<b>Footer from the config</b>",
+    );
+    write_file(
+        dir.path(),
+        "brand/cli-footer.html",
+        "<b>Footer from the CLI</b>",
+    );
+    write_file(
+        dir.path(),
+        "config.toml",
+        "repositories = {}\n\
+         [integrations.static_site]\n\
+         extra_css = \"brand/extra.css\"\n\
+         header_html = \"brand/header.html\"\n\
+         footer_html = \"brand/footer.html\"\n",
+    );
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("--data-dir")
+        .arg(dir.path())
+        .arg("render")
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir())
+        .arg("--footer-html")
+        .arg(dir.path().join("brand/cli-footer.html"));
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let css = std::fs::read_to_string(output_dir.join("extra.css")).unwrap();
+    assert_eq!(css, "nav { color: hotpink; }");
+    let index = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    assert!(index.contains("<b>From the config</b>"), "{index}");
+    // The CLI overrides the config
+    assert!(index.contains("<b>Footer from the CLI</b>"), "{index}");
+    assert!(!index.contains("<b>Footer from the config</b>"), "{index}");
+}
+
+#[test]
+fn render_without_customizations() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(&output_dir)
+        .arg("-t")
+        .arg(templates_dir());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    assert!(!output_dir.join("extra.css").exists());
+    let index = std::fs::read_to_string(output_dir.join("index.html")).unwrap();
+    assert!(!index.contains("extra.css"));
+}
+
+#[test]
+fn render_missing_header_html_is_an_error() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let mut cmd = TestHarness::stateless_command();
+    cmd.arg("render")
+        .arg("-e")
+        .arg(dir.path().join("export"))
+        .arg("-o")
+        .arg(dir.path().join("site"))
+        .arg("--header-html")
+        .arg(dir.path().join("missing.html"));
+    let output = cmd.captured_output();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn render_removes_stale_pages() {
+    let dir = tempfile::tempdir().unwrap();
+    setup_fixture(dir.path());
+
+    let output_dir = dir.path().join("site");
+    let render = || {
+        let mut cmd = TestHarness::stateless_command();
+        cmd.arg("render")
+            .arg("-e")
+            .arg(dir.path().join("export"))
+            .arg("-o")
+            .arg(&output_dir)
+            .arg("-t")
+            .arg(templates_dir());
+        let output = cmd.captured_output();
+        assert!(output.status.success());
+    };
+    render();
+    assert!(output_dir.join("user/bob-jones.html").exists());
+    assert!(output_dir.join(".herostratus-manifest.json").exists());
+    // Files the renderer didn't write are left alone
+    write_file(&output_dir, "CNAME", "achievements.example.com");
+    let modified = |page: &str| {
+        std::fs::metadata(output_dir.join(page))
+            .unwrap()
+            .modified()
+            .unwrap()
+    };
+    let shortest_page = modified("achievement/shortest.html");
+    std::thread::sleep(std::time::Duration::from_millis(20));
+
+    // Bob's only achievement is gone
+    write_file(
+        dir.path(),
+        "export/events/test-repo.csv",
+        "timestamp,event,achievement_id,commit,user_name,user_email\n\
+         2026-01-01T00:00:00Z,grant,fixup,aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa,Alice Smith,alice@example.com\n\
+         2026-01-03T00:00:00Z,grant,shortest,cccccccccccccccccccccccccccccccccccccccc,Alice Smith,alice@example.com\n",
+    );
+    render();
+
+    for stale in [
+        "user/bob-jones.html",
+        "user/bob-jones.xml",
+        "avatar/bob-jones.svg",
+        "badge/user/bob-jones.svg",
+    ] {
+        assert!(!output_dir.join(stale).exists(), "{stale}");
+    }
+    assert!(output_dir.join("user/alice-smith.html").exists());
+    assert!(output_dir.join("CNAME").exists());
+    // Bob never had Brevity, so its page didn't change, and wasn't rewritten
+    assert_eq!(modified("achievement/shortest.html"), shortest_page);
+}
+
 #[test]
 fn render_commit_links_use_prefix() {
     let dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use herostratus::config::Config;
+use herostratus::rules::ScriptRuleConfig;
+use herostratus::rules::external::KindConfig;
+use herostratus_tests::cmd::{CommandExt, TestHarness, assert_grants};
+use herostratus_tests::fixtures::repository::Builder;
+
+/// Grants every empty commit it's told about
+const SCRIPT: &str = r#"
+    while read -r line; do
+        case "$line" in
+            *'"type":"observation"'*) echo '{"grants":[{}]}' ;;
+            *) echo '{}' ;;
+        esac
+    done
+"#;
+
+fn script_rule(kind: KindConfig) -> ScriptRuleConfig {
+    ScriptRuleConfig {
+        id: 1000,
+        name: "Nothing to see here".into(),
+        description: "Make an empty commit, but in-house".into(),
+        kind,
+        command: vec!["sh".into(), "-c".into(), SCRIPT.into()],
+        working_dir: None,
+        consumes: vec!["empty_commit".into()],
+        version: 1,
+    }
+}
+
+fn config(kind: KindConfig) -> Config {
+    let mut config = Config::default().disable("all").enable("nothing-to-see");
+    let rules = config.rules.as_mut().unwrap();
+    rules.scripts = Some(BTreeMap::from([(
+        "nothing-to-see".to_string(),
+        script_rule(kind),
+    )]));
+    config
+}
+
+#[test]
+fn script_rule_grants_through_achievement_log() {
+    let temp = Builder::new()
+        .commit("first")
+        .author("Alice", "alice@example.com")
+        .commit("second")
+        .author("Alice", "alice@example.com")
+        .build()
+        .unwrap();
+    let head = temp.repo.head_id().unwrap().detach();
+
+    let h = TestHarness::new();
+    h.write_config(&config(KindConfig::PerUser));
+
+    let mut cmd = h.command();
+    cmd.arg("check").arg(temp.tempdir.path());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    // The script grants both commits, but the per-user kind only lets the newest one through
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_grants(&stdout, head, "Nothing to see here");
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+
+    h.write_config(&config(KindConfig::PerUserRepeat));
+    let mut cmd = h.command();
+    cmd.arg("check").arg(temp.tempdir.path());
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().count(), 2, "{stdout}");
+}
+
+#[test]
+fn script_rule_version_invalidates_cache() {
+    let temp_upstream = Builder::new().commit("first").build().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add").arg(url);
+    assert!(cmd.captured_output().status.success());
+
+    let rules = config(KindConfig::PerUser).rules;
+    h.update_config(|mut c| {
+        c.rules = rules;
+        c
+    });
+
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("processing 1 commits"), "{stderr}");
+
+    // Nothing changed, so nothing is re-processed
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    let output = cmd.captured_output();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("processing 0 commits"), "{stderr}");
+
+    // Bumping the version re-processes the full history
+    h.update_config(|mut c| {
+        let rules = c.rules.as_mut().unwrap();
+        rules
+            .scripts
+            .as_mut()
+            .unwrap()
+            .get_mut("nothing-to-see")
+            .unwrap()
+            .version = 2;
+        c
+    });
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stderr.contains("Rule nothing-to-see version changed"),
+        "{stderr}"
+    );
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "wasm-rules")]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use herostratus::config::Config;
+use herostratus_tests::cmd::{CommandExt, TestHarness, assert_grants};
+use herostratus_tests::fixtures::repository::Builder;
+
+/// Build the herostratus-wasm-example plugin, returning the path to the `.wasm` module
+///
+/// Uses a separate target directory so that it doesn't wait on the lock held by the `cargo test`
+/// that's running this test.
+fn build_example() -> PathBuf {
+    let workspace = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let target_dir = workspace.join("target").join("wasm-example");
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+    let status = Command::new(cargo)
+        .current_dir(&workspace)
+        .args([
+            "build",
+            "--quiet",
+            "--release",
+            "--target",
+            "wasm32-unknown-unknown",
+            "-p",
+            "herostratus-wasm-example",
+        ])
+        .env("CARGO_TARGET_DIR", &target_dir)
+        // Coverage instrumentation (cargo llvm-cov) doesn't support the wasm32 target
+        .env_remove("RUSTFLAGS")
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("RUSTC_WRAPPER")
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .status()
+        .unwrap();
+    assert!(status.success(), "failed to build the example WASM plugin");
+    target_dir.join("wasm32-unknown-unknown/release/herostratus_wasm_example.wasm")
+}
+
+#[test]
+fn wasm_rule_grants_and_round_trips_cache() {
+    let plugin = build_example();
+    let temp_upstream = Builder::new()
+        .commit("Rewrite it in WASM")
+        .author("Alice", "alice@example.com")
+        .commit("Rewrite it in Rust")
+        .author("Bob", "bob@example.com")
+        .build()
+        .unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add").arg(&url);
+    assert!(cmd.captured_output().status.success());
+
+    let mut rules = Config::default().disable("all").enable("web-scale").rules;
+    rules.as_mut().unwrap().wasm = Some(vec![plugin]);
+    h.update_config(|mut c| {
+        c.rules = rules;
+        c
+    });
+
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let first = temp_upstream
+        .repo
+        .rev_parse_single("HEAD~1")
+        .unwrap()
+        .detach();
+    assert_grants(&stdout, first, "Web Scale");
+    assert_eq!(stdout.lines().count(), 1, "{stdout}");
+
+    // The plugin's cache made it out of the WASM sandbox and onto disk
+    let repo_cache = std::fs::read_dir(h.path().join("cache"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let cache = std::fs::read_to_string(repo_cache.join("rule_web-scale.json")).unwrap();
+    assert!(cache.contains(r#""mentions":1"#), "{cache}");
+}
+
+#[test]
+fn wasm_rule_out_of_fuel_is_skipped() {
+    let plugin = build_example();
+    let temp_upstream = Builder::new().commit("Rewrite it in WASM").build().unwrap();
+    let url = format!("file://{}", temp_upstream.tempdir.path().display());
+
+    let h = TestHarness::new();
+    let mut cmd = h.command();
+    cmd.arg("add").arg(&url);
+    assert!(cmd.captured_output().status.success());
+
+    let mut rules = Config::default().disable("all").enable("web-scale").rules;
+    let r = rules.as_mut().unwrap();
+    r.wasm = Some(vec![plugin]);
+    r.wasm_fuel = Some(100);
+    h.update_config(|mut c| {
+        c.rules = rules;
+        c
+    });
+
+    let mut cmd = h.command();
+    cmd.arg("check-all").arg("--no-fetch");
+    let output = cmd.captured_output();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.is_empty(), "{stdout}");
+    assert!(
+        stderr.contains("ran out of fuel (100 per call)"),
+        "{stderr}"
+    );
+}